@@ -33,12 +33,13 @@ pub enum Commands {
         /// Apply a built-in preset as the base config layer
         #[arg(long, value_name = "ID")]
         preset: Option<String>,
-        /// Output format (text, json, html, markdown, table)
+        /// Output format (text, json, html, markdown, table, sarif)
         ///
         /// - text:     Standard colorful terminal output (default)
         /// - json:     Machine-readable JSON for integration
         /// - html:     Self-contained HTML report
         /// - table:    Simple key-value table
+        /// - sarif:    SARIF 2.1.0 for code-scanning dashboards
         #[arg(long, default_value = "text")]
         format: String,
         /// Fail (exit 1) if the highest finding score exceeds this value (0-100)
@@ -84,6 +85,34 @@ pub enum Commands {
         /// Write all current findings to a baseline file (exit 0)
         #[arg(long, value_name = "PATH")]
         write_baseline: Option<PathBuf>,
+        /// Rescan every file instead of reusing cached results for unchanged files
+        #[arg(long)]
+        no_cache: bool,
+        /// Directory for the scan cache (default: the user cache dir)
+        #[arg(
+            long,
+            value_name = "DIR",
+            env = "VEIL_CACHE_DIR",
+            conflicts_with = "no_cache"
+        )]
+        cache_dir: Option<PathBuf>,
+        /// Scan inside zip/jar, tar, tar.gz and gz archives (same as `[archive] enabled = true`)
+        #[arg(long)]
+        scan_archives: bool,
+        /// Check detected AWS/GitHub/Slack credentials against the provider APIs.
+        /// This sends the candidate secrets over the network.
+        #[arg(long)]
+        verify: bool,
+        /// Override a verifier endpoint for this run (e.g. github=http://127.0.0.1:8080).
+        /// Endpoints in the repo or org config are ignored; use this or the user config.
+        #[arg(
+            long,
+            value_name = "ID=URL",
+            env = "VEIL_VERIFY_ENDPOINTS",
+            value_delimiter = ',',
+            value_parser = parse_verify_endpoint
+        )]
+        verify_endpoint: Vec<(String, String)>,
     },
     /// Filter STDIN and mask secrets (outputs to STDOUT)
     Filter,
@@ -145,6 +174,9 @@ pub enum Commands {
     Git(GitCommand),
     /// Show diagnostic definition and system info
     Doctor,
+    /// Scan cache maintenance
+    #[command(subcommand)]
+    Cache(CacheCommand),
 
     /// Rules related commands
     #[command(subcommand)]
@@ -205,7 +237,7 @@ pub struct GuardianArgs {
 pub enum GuardianCommands {
     /// Check lockfile for vulnerabilities
     Check {
-        /// Path to Cargo.lock or package-lock.json
+        /// Lockfile or SBOM to check, or a directory to search for lockfiles recursively
         #[arg(default_value = "Cargo.lock")]
         lockfile: std::path::PathBuf,
 
@@ -221,16 +253,117 @@ pub enum GuardianCommands {
         #[arg(long)]
         osv_details: bool,
 
+        /// Only fail (exit 1) when a vulnerable package has a fixed version to
+        /// upgrade to. Implies --osv-details, which carries the fixed ranges
+        #[arg(long)]
+        fail_on_fixable: bool,
+
+        /// Fail (exit 1) only if a vulnerable package reaches this severity or
+        /// has no rating. Values: Low, Medium, High, Critical. Implies --osv-details
+        #[arg(long)]
+        fail_on_severity: Option<veil_guardian::report::Severity>,
+
+        /// Drop advisories rated below this severity from the report.
+        /// Advisories without a rating are kept. Implies --osv-details
+        #[arg(long)]
+        min_severity: Option<veil_guardian::report::Severity>,
+
         /// Show performance metrics to stderr
         #[arg(long)]
         debug_metrics: bool,
+
+        /// Guardian exception registry (default: ops/guardian-exceptions.toml if it exists)
+        #[arg(long, value_name = "PATH")]
+        exceptions: Option<PathBuf>,
     },
+    /// Manage accepted advisories (guardian exception registry)
+    Exceptions(GuardianExceptionsArgs),
+    /// Manage the offline OSV database used by `check --offline`
+    Db(GuardianDbArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct GuardianDbArgs {
+    #[command(subcommand)]
+    pub command: GuardianDbSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum GuardianDbSubcommand {
+    /// Import OSV bulk export zips (e.g. https://osv-vulnerabilities.storage.googleapis.com/npm/all.zip)
+    Import {
+        /// Export archives; each replaces the ecosystems it contains
+        #[arg(required = true, value_name = "ZIP")]
+        archives: Vec<PathBuf>,
+    },
+    /// Show imported ecosystems and their age
+    Status,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct GuardianExceptionsArgs {
+    /// Path to the guardian exception registry (default: ops/guardian-exceptions.toml)
+    #[arg(long, value_name = "PATH")]
+    pub registry_path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: GuardianExceptionsSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum GuardianExceptionsSubcommand {
+    /// List all guardian exceptions
+    List,
+    /// Accept an advisory for a package
+    Add(GuardianExceptionsAddArgs),
+    /// Remove a guardian exception
+    Remove(GuardianExceptionsRemoveArgs),
+    /// Clean up expired guardian exceptions
+    Cleanup(ExceptionsCleanupArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct GuardianExceptionsAddArgs {
+    /// Advisory ID or alias (e.g., GHSA-xxxx-xxxx-xxxx, CVE-2024-1234)
+    pub advisory: String,
+    /// Package name as it appears in the lockfile
+    #[arg(long)]
+    pub package: String,
+    /// Limit to a version range (e.g., ">= 1.0, < 1.4" or "1.2.3")
+    #[arg(long)]
+    pub versions: Option<String>,
+    /// Reason for accepting the advisory
+    #[arg(long)]
+    pub reason: String,
+    /// Expiration (e.g., 30d, 1w, 1y)
+    #[arg(long)]
+    pub expires: Option<String>,
+    /// Dry run (don't write changes)
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct GuardianExceptionsRemoveArgs {
+    /// Advisory ID or alias the exception was added with
+    pub advisory: String,
+    /// Package name
+    #[arg(long)]
+    pub package: String,
+    /// Version range the exception was added with
+    #[arg(long)]
+    pub versions: Option<String>,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum OutputFormatCli {
     Human,
     Json,
+    /// CycloneDX 1.5 JSON SBOM with VEX statements
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// SPDX 2.3 JSON SBOM
+    Spdx,
 }
 
 #[derive(Subcommand)]
@@ -312,6 +445,10 @@ pub struct GitScanArgs {
     /// Scan changes against the upstream branch or origin/main (PR mode)
     #[arg(long, conflicts_with = "range")]
     pub pr: bool,
+
+    /// Scan every ref (branches, tags, stashes), including deleted files
+    #[arg(long, conflicts_with_all = ["range", "pr"])]
+    pub all: bool,
 }
 
 #[derive(Args, Debug)]
@@ -332,6 +469,16 @@ pub struct FixArgs {
     pub apply: bool,
 }
 
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Remove every cached scan result
+    Clear {
+        /// Directory of the scan cache (default: the user cache dir)
+        #[arg(long, value_name = "DIR", env = "VEIL_CACHE_DIR")]
+        cache_dir: Option<PathBuf>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum PreCommitCommand {
     /// Install the pre-commit hook
@@ -360,6 +507,11 @@ pub enum ConfigCommand {
         #[arg(long, value_enum)]
         format: Option<ConfigFormat>,
     },
+    /// Show which ignore/include pattern (and config layer) decides whether a path is scanned
+    ExplainPath {
+        /// Path to explain, relative to the current directory
+        path: PathBuf,
+    },
 }
 
 #[derive(clap::ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -505,13 +657,25 @@ pub struct ExceptionsRemoveArgs {
     pub id: String,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct ExceptionsCleanupArgs {
     /// Dry run (don't write changes)
     #[arg(long)]
     pub dry_run: bool,
 }
 
+fn parse_verify_endpoint(s: &str) -> Result<(String, String), String> {
+    let Some((id, url)) = s.split_once('=').filter(|(id, _)| !id.is_empty()) else {
+        return Err(format!("Invalid verifier endpoint: {s}. Use ID=URL"));
+    };
+    if !veil_config::validate::is_allowed_verification_endpoint(url) {
+        return Err(format!(
+            "Invalid endpoint for verifier '{id}': must use https:// (http:// is only allowed for localhost)"
+        ));
+    }
+    Ok((id.to_string(), url.to_string()))
+}
+
 fn parse_severity(s: &str) -> Result<Severity, String> {
     let v = s.to_lowercase();
     match v.as_str() {
@@ -0,0 +1,18 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use veil_core::ScanCache;
+
+pub fn clear(cache_dir: Option<&Path>) -> Result<()> {
+    let dir = match cache_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => ScanCache::default_dir().context("could not determine the user cache directory")?,
+    };
+    let removed = ScanCache::clear(&dir)
+        .with_context(|| format!("failed to clear the scan cache in {}", dir.display()))?;
+    println!(
+        "Removed {} cached scan result(s) from {}",
+        removed,
+        dir.display()
+    );
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 // veil_core::rules::Rule needs to be imported directly or via public path
 use veil_core::{try_get_all_rules, Rule};
 
@@ -154,3 +154,61 @@ pub fn dump(
 
     Ok(())
 }
+
+pub fn explain_path(explicit_path: Option<&PathBuf>, path: &Path) -> Result<()> {
+    use crate::config_loader::load_config_layers;
+    use veil_core::{PathFilter, PathVerdict};
+
+    let layers = load_config_layers(explicit_path)?;
+    let filter = PathFilter::new(&layers.effective.core)?;
+    let rel = filter.relative(path);
+    let verdict = filter.verdict(&rel, path.is_dir());
+
+    let ignore_source = |pattern: &str| pattern_source(&layers, pattern, |c| &c.core.ignore);
+    let include_source = |pattern: &str| pattern_source(&layers, pattern, |c| &c.core.include);
+    let reason = match &verdict {
+        PathVerdict::Default => "no ignore or include pattern applies".to_string(),
+        PathVerdict::Builtin(dir) => format!("inside built-in ignored directory '{}'", dir),
+        PathVerdict::Ignored(p) => format!("ignore pattern {:?} ({})", p, ignore_source(p)),
+        PathVerdict::Unignored(p) => {
+            format!(
+                "re-included by ignore pattern {:?} ({})",
+                p,
+                ignore_source(p)
+            )
+        }
+        PathVerdict::Included(p) => format!("include pattern {:?} ({})", p, include_source(p)),
+        PathVerdict::NotIncluded(Some(p)) => {
+            format!("include pattern {:?} ({})", p, include_source(p))
+        }
+        PathVerdict::NotIncluded(None) => "no include pattern matches".to_string(),
+    };
+
+    println!("🔍 {}", rel.display());
+    if verdict.is_excluded() {
+        println!("❌ excluded: {}", reason);
+    } else {
+        println!("✅ scanned: {}", reason);
+    }
+
+    Ok(())
+}
+
+/// Names the layer a pattern came from. Layers merge by appending, so the
+/// last layer that lists the pattern is the one whose entry matched.
+fn pattern_source(
+    layers: &crate::config_loader::ConfigLayers,
+    pattern: &str,
+    list: impl Fn(&veil_config::Config) -> &Vec<String>,
+) -> &'static str {
+    let ordered = [
+        ("repo layer", layers.repo.as_ref()),
+        ("org layer", layers.org.as_ref()),
+        ("user layer", layers.user.as_ref()),
+        ("preset layer", layers.preset.as_ref()),
+    ];
+    ordered
+        .into_iter()
+        .find(|(_, config)| config.is_some_and(|c| list(c).iter().any(|p| p == pattern)))
+        .map_or("built-in default", |(name, _)| name)
+}
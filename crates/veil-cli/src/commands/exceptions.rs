@@ -304,7 +304,7 @@ fn run_add(
     Ok(false)
 }
 
-fn parse_expiry(s: &str) -> Result<chrono::DateTime<Utc>> {
+pub(crate) fn parse_expiry(s: &str) -> Result<chrono::DateTime<Utc>> {
     let s = s.trim();
     let split_idx = s
         .find(|c: char| !c.is_ascii_digit() && c != '-')
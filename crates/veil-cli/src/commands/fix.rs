@@ -1,5 +1,5 @@
 use crate::cli::FixArgs;
-use crate::commands::scan::collect_findings;
+use crate::commands::scan::{collect_findings, CacheMode};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::collections::{HashMap, HashSet};
@@ -35,6 +35,8 @@ pub fn fix(args: &FixArgs) -> Result<()> {
         false, // unsafe
         None,  // limit
         None,  // baseline
+        CacheMode::default(),
+        false, // verify
     )?;
 
     if result.findings.is_empty() {
@@ -1,15 +1,38 @@
-use crate::cli::{GuardianArgs, GuardianCommands, OutputFormatCli};
-use veil_guardian::report::OutputFormat;
-use veil_guardian::{scan_lockfile, ScanOptions};
+use crate::cli::{
+    GuardianArgs, GuardianCommands, GuardianDbArgs, GuardianDbSubcommand,
+    GuardianExceptionsAddArgs, GuardianExceptionsArgs, GuardianExceptionsRemoveArgs,
+    GuardianExceptionsSubcommand, OutputFormatCli,
+};
+use crate::commands::exceptions::parse_expiry;
+use crate::config_loader::load_effective_config;
+use chrono::Utc;
+use prettytable::{format, Cell, Row, Table};
+use std::path::{Path, PathBuf};
+use veil_core::scanner::BUILTIN_IGNORES;
+use veil_guardian::exceptions::{
+    AdvisoryException, ExceptionRegistry, ExceptionRegistryError, DEFAULT_REGISTRY_PATH,
+};
+use veil_guardian::license::LicensePolicy;
+use veil_guardian::providers::osv::OsvMirror;
+use veil_guardian::remediation;
+use veil_guardian::report::{advisory_severity, OutputFormat, Severity};
+use veil_guardian::{is_supported_lockfile, scan_lockfile, scan_lockfiles, ScanOptions};
 
-pub fn run(args: GuardianArgs) -> anyhow::Result<()> {
+/// Exit code of `check` when the only failures are license policy violations.
+const LICENSE_VIOLATION_EXIT_CODE: i32 = 3;
+
+pub fn run(args: GuardianArgs, config_path: Option<&PathBuf>) -> anyhow::Result<()> {
     match args.command {
         GuardianCommands::Check {
             lockfile,
             format,
             offline,
             osv_details,
+            fail_on_fixable,
+            fail_on_severity,
+            min_severity,
             debug_metrics,
+            exceptions,
         } => {
             let metrics = if debug_metrics {
                 Some(std::sync::Arc::new(veil_guardian::Metrics::new()))
@@ -17,29 +40,57 @@ pub fn run(args: GuardianArgs) -> anyhow::Result<()> {
                 None
             };
 
+            let exceptions = load_check_exceptions(exceptions)?;
+            let licenses = load_effective_config(config_path)?.guardian.licenses;
+
             let options = ScanOptions {
                 offline,
-                show_details: osv_details,
+                show_details: osv_details
+                    || fail_on_fixable
+                    || fail_on_severity.is_some()
+                    || min_severity.is_some(),
                 osv_api_url: None,
                 metrics: metrics.clone(),
                 cache_dir: None,
+                exceptions,
+                license_policy: Some(LicensePolicy {
+                    allow: licenses.allow,
+                    deny: licenses.deny,
+                    fail_on_unknown: licenses.fail_on_unknown,
+                }),
             };
 
-            let scan_result = scan_lockfile(
-                &lockfile,
-                options,
-            )
+            let mut scan_result = if lockfile.is_dir() {
+                let lockfiles = discover_lockfiles(&lockfile);
+                if lockfiles.is_empty() {
+                    anyhow::bail!("No supported lockfiles found under {:?}", lockfile);
+                }
+                eprintln!(
+                    "Discovered {} lockfiles under {}",
+                    lockfiles.len(),
+                    lockfile.display()
+                );
+                scan_lockfiles(&lockfiles, options)
+            } else {
+                scan_lockfile(&lockfile, options)
+            }
             .map_err(|e| {
                 anyhow::anyhow!(
-                    "Failed to scan lockfile at {:?}: {}\n\nTip: Ensure the file exists and is a valid Cargo.lock, package-lock.json, pnpm-lock.yaml, or yarn.lock.",
+                    "Failed to scan lockfile at {:?}: {}\n\nTip: Ensure the file exists and is a supported lockfile (Cargo.lock, package-lock.json, pnpm-lock.yaml, yarn.lock, poetry.lock, Pipfile.lock, requirements.txt, go.sum, go.mod, pom.xml, gradle.lockfile, or a CycloneDX/SPDX JSON SBOM).",
                     lockfile,
                     e
                 )
             })?;
 
+            if let Some(min) = min_severity {
+                scan_result.retain_min_severity(min);
+            }
+
             let output_format = match format {
                 OutputFormatCli::Human => OutputFormat::Human,
                 OutputFormatCli::Json => OutputFormat::Json,
+                OutputFormatCli::CycloneDx => OutputFormat::CycloneDx,
+                OutputFormatCli::Spdx => OutputFormat::Spdx,
             };
 
             println!("{}", scan_result.display(output_format));
@@ -48,11 +99,304 @@ pub fn run(args: GuardianArgs) -> anyhow::Result<()> {
                 eprintln!("\n{}", m);
             }
 
-            if !scan_result.is_clean() {
+            // Advisories without a rating block too: nothing shows they are
+            // below the threshold, and `--min-severity` keeps them as well.
+            // With `--fail-on-fixable` only the advisories an upgrade resolves
+            // count, so an unfixed advisory does not hide a fixable one.
+            let candidates: Vec<Option<Severity>> = scan_result
+                .vulnerabilities
+                .iter()
+                .filter_map(|vuln| {
+                    if !fail_on_fixable {
+                        return Some(vuln.severity);
+                    }
+                    let mut fixable = remediation::fixable_advisories(vuln).peekable();
+                    fixable.peek()?;
+                    Some(
+                        fixable
+                            .map(|advisory| advisory_severity(advisory).0)
+                            .filter(|severity| *severity != Severity::Unknown)
+                            .min(),
+                    )
+                })
+                .collect();
+            let unrated = candidates
+                .iter()
+                .filter(|severity| severity.is_none())
+                .count();
+            let blocking = candidates
+                .iter()
+                .filter(|severity| {
+                    fail_on_severity.is_none_or(|threshold| {
+                        severity.is_none_or(|severity| severity.meets(threshold))
+                    })
+                })
+                .count();
+            if let (Some(threshold), true) = (fail_on_severity, blocking > 0) {
+                if unrated > 0 {
+                    eprintln!(
+                        "CI failed: found {} vulnerable package(s) with severity >= {} or without a severity rating ({} unrated)",
+                        blocking, threshold, unrated
+                    );
+                } else {
+                    eprintln!(
+                        "CI failed: found {} vulnerable package(s) with severity >= {}",
+                        blocking, threshold
+                    );
+                }
+            }
+            if blocking > 0 {
                 std::process::exit(1);
             }
+            if !scan_result.license_violations.is_empty() {
+                eprintln!(
+                    "CI failed: found {} package(s) violating the license policy",
+                    scan_result.license_violations.len()
+                );
+                std::process::exit(LICENSE_VIOLATION_EXIT_CODE);
+            }
+
+            Ok(())
+        }
+        GuardianCommands::Exceptions(args) => run_exceptions(&args),
+        GuardianCommands::Db(args) => run_db(&args),
+    }
+}
+
+/// An explicit `--exceptions` path must exist; the repository default is
+/// only used when present.
+fn load_check_exceptions(explicit: Option<PathBuf>) -> anyhow::Result<Option<ExceptionRegistry>> {
+    let path = match explicit {
+        Some(path) => path,
+        None => {
+            let default = PathBuf::from(DEFAULT_REGISTRY_PATH);
+            if !default.exists() {
+                return Ok(None);
+            }
+            default
+        }
+    };
+    Ok(Some(ExceptionRegistry::load(&path)?))
+}
+
+fn run_db(args: &GuardianDbArgs) -> anyhow::Result<()> {
+    let mirror = OsvMirror::new(None)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the cache directory"))?;
+
+    match &args.command {
+        GuardianDbSubcommand::Import { archives } => {
+            for archive in archives {
+                for meta in mirror.import_zip(archive, Utc::now())? {
+                    println!(
+                        "Imported {} {} advisories from {} (updated {})",
+                        meta.advisories,
+                        meta.ecosystem,
+                        meta.source,
+                        meta.updated_at.format("%Y-%m-%d")
+                    );
+                }
+            }
+            println!("Offline database: {}", mirror.dir().display());
+            Ok(())
+        }
+        GuardianDbSubcommand::Status => {
+            let imported = mirror.status();
+            if imported.is_empty() {
+                println!("No OSV database imported. Run `veil guardian db import <ZIP>`.");
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+            table.set_titles(Row::new(vec![
+                Cell::new("Ecosystem"),
+                Cell::new("Advisories"),
+                Cell::new("Updated"),
+                Cell::new("Age"),
+                Cell::new("Imported"),
+                Cell::new("Source"),
+            ]));
+            let now = Utc::now();
+            for meta in imported {
+                table.add_row(Row::new(vec![
+                    Cell::new(meta.ecosystem.osv_name()),
+                    Cell::new(&meta.advisories.to_string()),
+                    Cell::new(&meta.updated_at.format("%Y-%m-%d").to_string()),
+                    Cell::new(&format!("{}d", (now - meta.updated_at).num_days())),
+                    Cell::new(&meta.imported_at.format("%Y-%m-%d %H:%M").to_string()),
+                    Cell::new(&meta.source),
+                ]));
+            }
+            table.printstd();
+            Ok(())
+        }
+    }
+}
 
+fn run_exceptions(args: &GuardianExceptionsArgs) -> anyhow::Result<()> {
+    let path = args
+        .registry_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_REGISTRY_PATH));
+
+    match &args.command {
+        GuardianExceptionsSubcommand::List => run_exceptions_list(&path),
+        GuardianExceptionsSubcommand::Add(cmd_args) => run_exceptions_add(cmd_args, &path),
+        GuardianExceptionsSubcommand::Remove(cmd_args) => run_exceptions_remove(cmd_args, &path),
+        GuardianExceptionsSubcommand::Cleanup(cmd_args) => {
+            let mut registry = match ExceptionRegistry::load(&path) {
+                Ok(registry) => registry,
+                Err(ExceptionRegistryError::NotFound(_)) => {
+                    println!("Registry not found, nothing to cleanup.");
+                    return Ok(());
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let removed = registry.cleanup(Utc::now());
+            if cmd_args.dry_run {
+                println!("Dry Run: Would remove {} expired exceptions.", removed);
+            } else if removed > 0 {
+                registry.save(&path)?;
+                println!("Removed {} expired exceptions.", removed);
+            } else {
+                println!("No expired exceptions found.");
+            }
             Ok(())
         }
     }
 }
+
+fn load_or_default(path: &Path) -> anyhow::Result<ExceptionRegistry> {
+    match ExceptionRegistry::load(path) {
+        Ok(registry) => Ok(registry),
+        Err(ExceptionRegistryError::NotFound(_)) => Ok(ExceptionRegistry::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn run_exceptions_list(path: &Path) -> anyhow::Result<()> {
+    let registry = load_or_default(path)?;
+    if registry.exceptions.is_empty() {
+        println!("No guardian exceptions found in {}.", path.display());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new("Advisory").style_spec("b"),
+        Cell::new("Package").style_spec("b"),
+        Cell::new("Versions").style_spec("b"),
+        Cell::new("Status").style_spec("b"),
+        Cell::new("Expiry").style_spec("b"),
+        Cell::new("Reason").style_spec("b"),
+    ]));
+
+    let now = Utc::now();
+    for entry in &registry.exceptions {
+        let status = if entry.is_expired(now) {
+            "Expired"
+        } else {
+            "Active"
+        };
+        let expiry = entry
+            .expires_at
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|| "Never".to_string());
+        table.add_row(Row::new(vec![
+            Cell::new(&entry.advisory),
+            Cell::new(&entry.package),
+            Cell::new(entry.versions.as_deref().unwrap_or("*")),
+            Cell::new(status),
+            Cell::new(&expiry),
+            Cell::new(&entry.reason),
+        ]));
+    }
+
+    table.printstd();
+    Ok(())
+}
+
+fn run_exceptions_add(args: &GuardianExceptionsAddArgs, path: &Path) -> anyhow::Result<()> {
+    let expires_at = args.expires.as_deref().map(parse_expiry).transpose()?;
+    let mut registry = load_or_default(path)?;
+
+    let entry = AdvisoryException {
+        advisory: args.advisory.clone(),
+        package: args.package.clone(),
+        versions: args.versions.clone(),
+        reason: args.reason.clone(),
+        created_at: Some(Utc::now()),
+        created_by: std::env::var("USER").ok(),
+        expires_at,
+    };
+    let label = format!(
+        "{} in {}{}",
+        entry.advisory,
+        entry.package,
+        entry
+            .versions
+            .as_deref()
+            .map(|v| format!(" ({})", v))
+            .unwrap_or_default()
+    );
+    registry.upsert(entry)?;
+
+    if args.dry_run {
+        println!("Dry Run: Would add guardian exception for {}", label);
+        return Ok(());
+    }
+    registry.save(path)?;
+    println!("Added guardian exception for {}", label);
+    Ok(())
+}
+
+fn run_exceptions_remove(args: &GuardianExceptionsRemoveArgs, path: &Path) -> anyhow::Result<()> {
+    let mut registry = ExceptionRegistry::load(path)?;
+    let before = registry.exceptions.len();
+    registry.exceptions.retain(|e| {
+        !(e.advisory == args.advisory && e.package == args.package && e.versions == args.versions)
+    });
+    if registry.exceptions.len() == before {
+        anyhow::bail!(
+            "Guardian exception for {} in {} not found",
+            args.advisory,
+            args.package
+        );
+    }
+    registry.save(path)?;
+    println!(
+        "Removed guardian exception for {} in {}",
+        args.advisory, args.package
+    );
+    Ok(())
+}
+
+/// Finds supported lockfiles below `root`, honouring `.gitignore` and
+/// skipping the same vendored/build directories as `veil scan`.
+fn discover_lockfiles(root: &Path) -> Vec<PathBuf> {
+    let mut builder = ignore::WalkBuilder::new(root);
+    builder.filter_entry(|entry| {
+        entry.depth() == 0
+            || !entry.file_type().is_some_and(|ft| ft.is_dir())
+            || !entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| BUILTIN_IGNORES.contains(&name))
+    });
+
+    let mut lockfiles: Vec<PathBuf> = builder
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(is_supported_lockfile)
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    lockfiles.sort();
+    lockfiles
+}
@@ -160,11 +160,16 @@ pub fn build_config(answers: &InitAnswers) -> Config {
             fail_on_score: final_fail_score,
             remote_rules_url: answers.remote_rules_url.clone(),
             rules_dir,
+            patterns_root: None,
         },
         masking: MaskingConfig {
             placeholder: placeholder.unwrap_or_else(|| MaskingConfig::default().placeholder),
         },
         output: veil_config::OutputConfig::default(),
+        entropy: veil_config::EntropyConfig::default(),
+        verification: veil_config::VerificationConfig::default(),
+        archive: veil_config::ArchiveConfig::default(),
+        guardian: veil_config::GuardianConfig::default(),
         rules,
     }
 }
@@ -664,6 +664,7 @@ mod tests {
         Finding {
             path,
             line_number: 1,
+            end_line_number: None,
             line_content: "token = raw-secret-value".to_string(),
             rule_id: "pii.test".to_string(),
             matched_content: "raw-secret-value".to_string(),
@@ -687,6 +688,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         }
     }
 }
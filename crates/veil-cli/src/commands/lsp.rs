@@ -1,10 +1,12 @@
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 
-pub fn run(config_path: Option<&PathBuf>, preset_id: Option<&str>, quiet: bool) -> Result<()> {
-    let config = crate::config_loader::load_effective_config_with_preset(config_path, preset_id)?;
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(1);
 
+pub fn run(config_path: Option<&PathBuf>, preset_id: Option<&str>, quiet: bool) -> Result<()> {
     if let Some(preset_id) = preset_id.filter(|_| !quiet) {
         eprintln!(
             "Using preset '{}' as the base config layer; user/org/repo config may override it.",
@@ -12,9 +14,29 @@ pub fn run(config_path: Option<&PathBuf>, preset_id: Option<&str>, quiet: bool)
         );
     }
 
+    // Reloaded by the server whenever veil.toml, a loaded config file, the
+    // baseline or a rule pack changes.
+    let config_path = config_path.cloned();
+    let preset_id = preset_id.map(str::to_string);
+    let config_loader: veil_lsp::server::ConfigLoader = Arc::new(move || {
+        let layers = crate::config_loader::load_config_layers_with_preset(
+            config_path.as_ref(),
+            preset_id.as_deref(),
+        )?;
+        Ok(veil_lsp::server::LoadedConfig {
+            config: layers.effective,
+            config_files: layers.files,
+        })
+    });
+
     let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()?;
-    runtime.block_on(veil_lsp::server::run_stdio_with_config(config))?;
-    Ok(())
+    let result = runtime.block_on(veil_lsp::server::run_stdio_with_config_loader(
+        config_loader,
+    ));
+    // Dropping the runtime would wait for blocking tasks; a workspace scan
+    // stops at its next file once cancelled, so a short grace period is enough.
+    runtime.shutdown_timeout(SHUTDOWN_GRACE);
+    result
 }
@@ -2,7 +2,8 @@ use anyhow::Result;
 use std::fs;
 use std::path::PathBuf;
 use veil_config::{load_config, Config};
-use veil_core::{get_default_rules, scan_path};
+use veil_core::masking::{finding_byte_range, line_offsets};
+use veil_core::{apply_masks_spans, get_default_rules, scan_path, MaskSpan};
 
 pub fn mask(
     paths: &[PathBuf],
@@ -21,37 +22,33 @@ pub fn mask(
         // scan_path gives us Finding which contains masked_line, but not the whole file content structure.
         // We need to rewrite the file.
 
-        // MVP: Read matching files, apply substitution line by line, write back.
+        // Read matching files, replace the matched byte ranges, write back.
         // For efficiency, we only rewrite if findings exist.
 
         let result = scan_path(path, &rules, &config);
-        let mut findings = result.findings;
+        let findings = result.findings;
 
         if findings.is_empty() {
             continue;
         }
 
-        // Sort findings by line number
-        findings.sort_by_key(|f| f.line_number);
-
-        // Read file again
+        // Read file again and replace each finding's byte range (which may
+        // span several lines for multiline rules) in one pass.
         let content = fs::read_to_string(path)?;
-        let mut new_lines = Vec::new();
-        let mut masked_count = 0;
-
-        for (i, line) in content.lines().enumerate() {
-            let line_num = i + 1;
-            // Check if this line has a finding
-            if let Some(finding) = findings.iter().find(|f| f.line_number == line_num) {
-                new_lines.push(finding.masked_snippet.clone());
-                masked_count += 1;
-            } else {
-                new_lines.push(line.to_string());
-            }
-        }
-
-        // Add newline at end if original had? simple join for now
-        let new_content = new_lines.join("\n");
+        let offsets = line_offsets(&content);
+        let spans: Vec<MaskSpan> = findings
+            .iter()
+            .filter_map(|f| finding_byte_range(f, &offsets, content.len()))
+            .map(|range| MaskSpan {
+                start: range.start,
+                end: range.end,
+                placeholder: config.masking.placeholder.clone(),
+                priority: 0,
+            })
+            .collect();
+        let masked_count = spans.len();
+        let new_content =
+            apply_masks_spans(&content, spans, config.output.mask_mode.unwrap_or_default());
 
         if dry_run {
             println!(
@@ -1,3 +1,4 @@
+pub mod cache;
 pub mod check_project;
 pub mod config;
 pub mod doctor;
@@ -6,6 +6,7 @@ use prettytable::{format, Cell, Row, Table};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
 use std::path::{Component, Path, PathBuf};
+use veil_core::explain::{explain_rule, render_remediation, Markup};
 use veil_core::{try_get_all_rules, Rule, Severity};
 
 const TEMPLATE_MANIFEST_HEADER: &str = "path,id,concept,slug,variant,category,severity,score,terms";
@@ -91,27 +92,14 @@ pub fn explain(config_path: Option<&PathBuf>, rule_id: &str) -> Result<()> {
         bail!("Rule not found: {}", rule_id);
     };
 
-    println!("{}:          {}", "ID".bold(), rule.id);
-    println!("{}: {}", "Description".bold(), rule.description);
-    println!("{}:    {}", "Severity".bold(), rule.severity);
-    println!("{}:       {}", "Score".bold(), rule.score);
-    println!("{}:    {}", "Category".bold(), rule.category);
+    println!("{}", explain_rule(&rule, Markup::Text));
 
-    if !rule.tags.is_empty() {
-        println!("{}:        {}", "Tags".bold(), rule.tags.join(", "));
+    if let Some(remediation) = &rule.remediation {
+        println!();
+        println!("{}", "Remediation:".bold().underline());
+        println!("{}", render_remediation(remediation, Markup::Text));
     }
 
-    println!();
-    println!("{}", "Pattern:".bold().underline());
-    println!("{}", rule.pattern.as_str());
-
-    println!();
-    println!("{}", "Context:".bold().underline());
-    println!(
-        "Before: {} lines / After: {} lines",
-        rule.context_lines_before, rule.context_lines_after
-    );
-
     Ok(())
 }
 
@@ -1,6 +1,6 @@
 use crate::formatters::{
     DisplayFinding, FindingStatus, Formatter, HtmlFormatter, JsonFormatter, MarkdownFormatter,
-    Summary, TableFormatter,
+    SarifFormatter, Summary, TableFormatter,
 };
 use crate::output::formatter::print_finding;
 use anyhow::{Context, Result};
@@ -16,6 +16,8 @@ pub struct ScanResultForCli {
     pub summary: Summary,
     pub findings: Vec<veil_core::model::Finding>,
     pub suppressed_findings: Vec<veil_core::model::Finding>,
+    /// Effective rule set used for the scan (for formatters that emit rule metadata).
+    pub rules: Vec<veil_core::model::Rule>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)] // Local Format enum
@@ -25,6 +27,7 @@ pub enum Format {
     Html,
     Table,
     Markdown,
+    Sarif,
 }
 
 impl From<&str> for Format {
@@ -34,11 +37,34 @@ impl From<&str> for Format {
             "html" => Format::Html,
             "table" => Format::Table,
             "md" | "markdown" => Format::Markdown,
+            "sarif" => Format::Sarif,
             _ => Format::Text,
         }
     }
 }
 
+/// How `collect_findings` uses the on-disk scan cache for filesystem scans.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum CacheMode {
+    /// Cache in the user cache directory.
+    #[default]
+    Default,
+    /// Cache in the given directory.
+    Dir(PathBuf),
+    /// Rescan every file (`--no-cache`).
+    Disabled,
+}
+
+impl CacheMode {
+    pub fn from_args(no_cache: bool, cache_dir: Option<PathBuf>) -> Self {
+        match (no_cache, cache_dir) {
+            (true, _) => CacheMode::Disabled,
+            (false, Some(dir)) => CacheMode::Dir(dir),
+            (false, None) => CacheMode::Default,
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn collect_findings(
     paths: &[PathBuf],
@@ -50,6 +76,8 @@ pub fn collect_findings(
     unsafe_output: bool,
     limit: Option<usize>,
     baseline_path: Option<&PathBuf>,
+    cache_mode: CacheMode,
+    verify: bool,
 ) -> Result<ScanResultForCli> {
     let start_time = Instant::now();
 
@@ -110,6 +138,7 @@ pub fn collect_findings(
     let skipped_files_atomic = AtomicUsize::new(0);
     let mut all_findings = Vec::new();
     let mut all_builtin_skips = std::collections::HashSet::new();
+    let mut all_archive_skips = Vec::new();
 
     // Limit Logic (Global)
     let limit_val = if let Some(l) = limit {
@@ -125,11 +154,13 @@ pub fn collect_findings(
     let mut any_file_limit_reached = false;
     let mut any_max_file_size_reached = false;
     let mut any_read_error_reached = false;
+    let mut cache_hits = 0;
 
     // Strategy Selection
     if let Some(commit_sha) = commit {
         // 1. Scan specific commit
         let repo = Repository::open(".")?;
+        let matcher = veil_core::RuleMatcher::new(&rules, &config);
         let obj = repo.revparse_single(commit_sha)?;
         let commit = obj.as_commit().context("Not a commit")?;
         let tree = commit.tree()?;
@@ -148,10 +179,10 @@ pub fn collect_findings(
                         if let Ok(entry) = tree.get_path(path_val) {
                             if let Ok(object) = entry.to_object(&repo) {
                                 if let Some(blob) = object.as_blob() {
-                                    let file_findings = veil_core::scan_data(
+                                    let file_findings = veil_core::scan_data_with_matcher(
                                         path_val,
                                         blob.content(),
-                                        &rules,
+                                        &matcher,
                                         &config,
                                     );
 
@@ -193,6 +224,7 @@ pub fn collect_findings(
             .context("Failed to parse time. Use RFC3339 or YYYY-MM-DD")?;
 
         let repo = Repository::open(".")?;
+        let matcher = veil_core::RuleMatcher::new(&rules, &config);
         let mut revwalk = repo.revwalk()?;
         revwalk.push_head()?;
         revwalk.set_sorting(git2::Sort::TIME)?;
@@ -220,10 +252,10 @@ pub fn collect_findings(
                             if let Ok(entry) = tree.get_path(path) {
                                 if let Ok(object) = entry.to_object(&repo) {
                                     if let Some(blob) = object.as_blob() {
-                                        let file_findings = veil_core::scan_data(
+                                        let file_findings = veil_core::scan_data_with_matcher(
                                             path,
                                             blob.content(),
-                                            &rules,
+                                            &matcher,
                                             &config,
                                         );
                                         let mut is_skipped = false;
@@ -254,78 +286,20 @@ pub fn collect_findings(
         }
     } else if staged {
         // 3. Scan staged
-        let repo = Repository::open(".")?;
-        let diff = if let Ok(head) = repo.head() {
-            if let Ok(head_tree) = head.peel_to_tree() {
-                let index = repo.index()?;
-                let mut diff_opts = DiffOptions::new();
-                repo.diff_tree_to_index(Some(&head_tree), Some(&index), Some(&mut diff_opts))?
-            } else {
-                return Ok(ScanResultForCli {
-                    summary: Summary::new(
-                        0,
-                        0,
-                        0,
-                        0,
-                        0,
-                        0, // baseline_suppressed
-                        false,
-                        false,
-                        false,
-                        false,
-                        start_time.elapsed(),
-                        None, // baseline_path
-                        HashMap::new(),
-                        Vec::new(),
-                    ),
-                    findings: vec![],
-                    suppressed_findings: vec![],
-                });
-            }
-        } else {
-            let index = repo.index()?;
-            let mut diff_opts = DiffOptions::new();
-            repo.diff_tree_to_index(None, Some(&index), Some(&mut diff_opts))?
-        };
-
-        let index = repo.index()?;
-        for delta in diff.deltas() {
-            if delta.status() == Delta::Added || delta.status() == Delta::Modified {
-                if let Some(path) = delta.new_file().path() {
-                    let path_val = path;
-                    if let Ok(true) = repo.is_path_ignored(path_val) {
-                        continue;
-                    }
-                    if let Some(entry) = index.get_path(path_val, 0) {
-                        if let Ok(blob) = repo.find_blob(entry.id) {
-                            let file_findings =
-                                veil_core::scan_data(path_val, blob.content(), &rules, &config);
-                            let mut is_skipped = false;
-                            if let Some(first) = file_findings.first() {
-                                if first.rule_id == veil_core::RULE_ID_BINARY_FILE
-                                    || first.rule_id == veil_core::RULE_ID_MAX_FILE_SIZE
-                                {
-                                    is_skipped = true;
-                                }
-                                if first.rule_id == veil_core::RULE_ID_MAX_FILE_SIZE {
-                                    any_max_file_size_reached = true;
-                                }
-                            }
-                            if is_skipped {
-                                skipped_files_atomic.fetch_add(1, Ordering::Relaxed);
-                            } else {
-                                scanned_files_atomic.fetch_add(1, Ordering::Relaxed);
-                                all_findings.extend(file_findings);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let staged = veil_core::scan_staged(std::path::Path::new("."), &[], &rules, &config)?;
+        scanned_files_atomic.fetch_add(staged.scanned_files, Ordering::Relaxed);
+        skipped_files_atomic.fetch_add(staged.skipped_files, Ordering::Relaxed);
+        any_max_file_size_reached |= staged.max_file_size_reached;
+        all_findings.extend(staged.findings);
     } else {
         // 4. Default FS scan
         let targets = resolve_paths(paths)?;
         let mut current_total = all_findings.len();
+        let cache = match cache_mode {
+            CacheMode::Default => veil_core::ScanCache::open(None, &rules, &config),
+            CacheMode::Dir(dir) => veil_core::ScanCache::open(Some(dir), &rules, &config),
+            CacheMode::Disabled => None,
+        };
 
         for path in targets {
             if let Some(max) = limit_val {
@@ -341,13 +315,16 @@ pub fn collect_findings(
                 run_config.output.max_findings = None;
             }
 
-            let result = veil_core::scan_path(&path, &rules, &run_config);
+            let result =
+                veil_core::scan_path_with_cache(&path, &rules, &run_config, cache.as_ref());
             scanned_files_atomic.fetch_add(result.scanned_files, Ordering::Relaxed);
             skipped_files_atomic.fetch_add(result.skipped_files, Ordering::Relaxed);
+            cache_hits += result.cache_hits;
             any_file_limit_reached |= result.file_limit_reached;
             any_max_file_size_reached |= result.max_file_size_reached;
             any_read_error_reached |= result.read_error_reached;
             all_builtin_skips.extend(result.builtin_skips);
+            all_archive_skips.extend(result.archive_skips);
             let count = result.findings.len();
             all_findings.extend(result.findings);
             current_total += count;
@@ -358,6 +335,17 @@ pub fn collect_findings(
         // We'll mutate a variable defined outside the if/else block.
     }
 
+    // Live verification: only on `--verify`, and a config layer may still veto it.
+    if verify {
+        match veil_core::verifiers::VerificationEngine::from_config(&config) {
+            Some(engine) => engine.verify_findings(&mut all_findings, &rules),
+            None if config.verification.is_disabled() => {
+                eprintln!("Warning: --verify ignored because [verification] enabled = false");
+            }
+            None => eprintln!("Warning: --verify ignored: failed to build the HTTP client"),
+        }
+    }
+
     let scanned_files = scanned_files_atomic.load(Ordering::Relaxed);
     let skipped_files = skipped_files_atomic.load(Ordering::Relaxed);
     let total_files = scanned_files + skipped_files;
@@ -401,6 +389,7 @@ pub fn collect_findings(
         total_files,
         scanned_files,
         skipped_files,
+        cache_hits,
         final_findings.len() + suppressed_findings.len(), // total = new + suppressed
         final_findings.len(),                             // new
         suppressed_findings.len(),
@@ -412,12 +401,14 @@ pub fn collect_findings(
         baseline_path.map(|p| p.to_string_lossy().to_string()),
         severity_counts,
         all_builtin_skips.into_iter().collect(),
+        all_archive_skips,
     );
 
     Ok(ScanResultForCli {
         summary,
         findings: final_findings,
         suppressed_findings,
+        rules,
     })
 }
 
@@ -440,6 +431,10 @@ pub fn scan(
     write_baseline: Option<PathBuf>,
     baseline: Option<PathBuf>,
     preset: Option<String>,
+    cache_mode: CacheMode,
+    scan_archives: bool,
+    verify: bool,
+    verify_endpoints: &[(String, String)],
     quiet: bool,
     no_color: bool, // Passed from cli args
 ) -> Result<bool> {
@@ -456,7 +451,7 @@ pub fn scan(
     }
 
     // Load config here for scan command to support --config arg
-    let config = if config_path.is_some() || preset.is_some() {
+    let mut config = if config_path.is_some() || preset.is_some() {
         let loaded = crate::config_loader::load_effective_config_with_preset(
             config_path,
             preset.as_deref(),
@@ -473,6 +468,23 @@ pub fn scan(
     } else {
         None
     };
+    if scan_archives {
+        let config = match config.as_mut() {
+            Some(config) => config,
+            None => config.insert(crate::config_loader::load_effective_config(None)?),
+        };
+        config.archive.enabled = true;
+    }
+    if !verify_endpoints.is_empty() {
+        let config = match config.as_mut() {
+            Some(config) => config,
+            None => config.insert(crate::config_loader::load_effective_config(None)?),
+        };
+        config
+            .verification
+            .endpoints
+            .extend(verify_endpoints.iter().cloned());
+    }
 
     let result = collect_findings(
         paths,
@@ -484,6 +496,8 @@ pub fn scan(
         unsafe_output,
         limit,
         baseline.as_ref(),
+        cache_mode,
+        verify,
     )?;
 
     if interactive {
@@ -512,7 +526,7 @@ pub fn scan(
     }
 
     // Output Formatting
-    let displays: Vec<DisplayFinding> = if let Format::Html = format {
+    let displays: Vec<DisplayFinding> = if matches!(format, Format::Html | Format::Sarif) {
         // Collect new findings
         let mut listing =
             Vec::with_capacity(result.findings.len() + result.suppressed_findings.len());
@@ -542,7 +556,7 @@ pub fn scan(
 
     let formatter: Box<dyn Formatter> = match format {
         Format::Json => Box::new(JsonFormatter),
-        Format::Html => Box::new(HtmlFormatter::new()),
+        Format::Html => Box::new(HtmlFormatter::new(&result.rules)),
         #[cfg(feature = "table")]
         Format::Table => Box::new(TableFormatter),
         #[cfg(not(feature = "table"))]
@@ -550,7 +564,8 @@ pub fn scan(
             eprintln!("Table format not compiled in");
             Box::new(TextFormatterWrapper { no_color })
         }
-        Format::Markdown => Box::new(MarkdownFormatter),
+        Format::Markdown => Box::new(MarkdownFormatter::new(&result.rules)),
+        Format::Sarif => Box::new(SarifFormatter::new(&result.rules)),
         Format::Text => Box::new(TextFormatterWrapper { no_color }),
     };
 
@@ -632,6 +647,25 @@ pub fn scan(
         std::process::exit(2);
     }
 
+    if !result.summary.archive_skips.is_empty() {
+        eprintln!();
+        eprintln!("{}", "❌ Scan Incomplete (Exit Code 2)".red().bold());
+        eprintln!("At least one archive entry was not scanned.");
+        for skip in &result.summary.archive_skips {
+            eprintln!("  - {} ({})", skip.path.display(), skip.reason);
+        }
+        eprintln!("  What: An [archive] safety limit was hit while unpacking an archive.");
+        eprintln!("  Why:  Passing CI with unscanned archive contents can hide secrets.");
+        eprintln!();
+        eprintln!("{}", "  How to fix:".bold());
+        eprintln!("    A) Ignore archives that are known build artifacts.");
+        eprintln!("    B) Raise the limits in your veil.toml if the archive is legitimate:");
+        eprintln!("       [archive]");
+        eprintln!("       max_depth = 5");
+        eprintln!("       max_entry_bytes = 524288000");
+        std::process::exit(2);
+    }
+
     if result.summary.limit_reached {
         eprintln!();
         eprintln!("{}", "❌ Scan Incomplete (Exit Code 2)".red().bold());
@@ -765,6 +799,9 @@ impl Formatter for TextFormatterWrapper {
         );
         println!("  Total Files:   {}", summary.total_files);
         println!("  Scanned Files: {}", summary.scanned_files);
+        if summary.cache_hits > 0 {
+            println!("  Cache Hits:    {}", summary.cache_hits);
+        }
         if summary.skipped_files > 0 {
             println!(
                 "  Skipped Files: {} (binary/large)",
@@ -847,6 +884,15 @@ impl Formatter for TextFormatterWrapper {
             );
         }
 
+        if !summary.archive_skips.is_empty() {
+            println!(
+                "{}",
+                "  (Scan incomplete: archive entries skipped by [archive] limits)"
+                    .red()
+                    .bold()
+            );
+        }
+
         Ok(())
     }
 }
@@ -861,6 +907,7 @@ mod tests {
             0,
             0,
             0,
+            0,
             new_findings,
             new_findings,
             0,
@@ -872,6 +919,7 @@ mod tests {
             None,
             HashMap::new(),
             Vec::new(),
+            Vec::new(),
         )
     }
 
@@ -879,6 +927,7 @@ mod tests {
         veil_core::Finding {
             path: PathBuf::from("src/config.rs"),
             line_number: 1,
+            end_line_number: None,
             line_content: "token = secret".to_string(),
             rule_id: "creds.test".to_string(),
             matched_content: "secret".to_string(),
@@ -893,6 +942,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         }
     }
 
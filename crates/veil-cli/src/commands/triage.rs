@@ -1,5 +1,5 @@
 use crate::cli::TriageArgs;
-use crate::commands::scan::collect_findings;
+use crate::commands::scan::{collect_findings, CacheMode};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use inquire::Select;
@@ -22,6 +22,8 @@ pub fn triage(args: &TriageArgs) -> Result<()> {
         false, // not unsafe
         None,  // no limit (we want all to triage)
         None,  // baseline
+        CacheMode::default(),
+        false, // no live verification
     )?;
 
     if result.findings.is_empty() {
@@ -4,12 +4,13 @@ use veil_config::{load_config, Config};
 
 #[derive(Debug, Clone)]
 pub struct ConfigLayers {
-    #[allow(dead_code)]
     pub preset: Option<Config>,
     pub org: Option<Config>,
     pub user: Option<Config>,
     pub repo: Option<Config>,
     pub effective: Config,
+    /// Config files the layers were read from, in merge order.
+    pub files: Vec<PathBuf>,
 }
 
 /// New entry point for loading configuration with layers
@@ -39,12 +40,31 @@ fn load_config_layers_with_preset_inner(
     let preset = preset_id
         .map(veil_config::builtin_preset_config)
         .transpose()?;
-    let org = load_org_config()?;
-    let user = load_user_config()?;
-    let repo = load_repo_config(explicit_path)?;
+    let (mut org, org_file) = split_layer(load_org_config()?);
+    let (user, user_file) = split_layer(load_user_config()?);
+    let (mut repo, repo_file) = split_layer(load_repo_config(explicit_path)?);
+    // Verification sends live secrets to these URLs; a repo (or a PR editing
+    // its veil.toml) must not be able to redirect them.
+    for (layer, file) in [(&mut org, &org_file), (&mut repo, &repo_file)] {
+        if let (Some(config), Some(file)) = (layer.as_mut(), file) {
+            drop_verification_endpoints(config, file);
+        }
+    }
+    let files = [user_file, org_file, repo_file]
+        .into_iter()
+        .flatten()
+        .collect();
 
     // Merge logic: Preset -> User -> Org -> Repo (later overrides earlier)
     let mut effective = merge_configs(preset.as_ref(), org.as_ref(), user.as_ref(), repo.as_ref());
+    // Anchored ignore/include patterns are relative to the repo config.
+    effective.core.patterns_root = explicit_path.map(|p| {
+        if p.is_file() {
+            p.parent().unwrap_or(Path::new(".")).to_path_buf()
+        } else {
+            p.clone()
+        }
+    });
 
     // Resolve rules_dir before optional runtime asset validation and rule loading.
     if let Some(dir_str) = &effective.core.rules_dir {
@@ -80,9 +100,28 @@ fn load_config_layers_with_preset_inner(
         user,
         repo,
         effective,
+        files,
     })
 }
 
+fn drop_verification_endpoints(config: &mut Config, file: &Path) {
+    if config.verification.endpoints.is_empty() {
+        return;
+    }
+    config.verification.endpoints.clear();
+    eprintln!(
+        "Warning: ignoring [verification.endpoints] in {:?}; set them in the user config or with --verify-endpoint",
+        file
+    );
+}
+
+fn split_layer(layer: Option<(Config, PathBuf)>) -> (Option<Config>, Option<PathBuf>) {
+    match layer {
+        Some((config, path)) => (Some(config), Some(path)),
+        None => (None, None),
+    }
+}
+
 /// wrapper for backward compatibility
 pub fn load_effective_config(config_path: Option<&PathBuf>) -> Result<Config> {
     Ok(load_config_layers(config_path)?.effective)
@@ -173,7 +212,7 @@ fn merge_configs(
     final_config
 }
 
-fn load_org_config() -> Result<Option<Config>> {
+fn load_org_config() -> Result<Option<(Config, PathBuf)>> {
     // 1. Explicit: VEIL_ORG_CONFIG (strict)
     if let Ok(path_str) = std::env::var("VEIL_ORG_CONFIG") {
         let path = PathBuf::from(&path_str);
@@ -182,27 +221,27 @@ fn load_org_config() -> Result<Option<Config>> {
         }
         let cfg = load_config(&path)
             .map_err(|e| anyhow::anyhow!("Failed to load VEIL_ORG_CONFIG {:?}: {}", path, e))?;
-        return Ok(Some(cfg));
+        return Ok(Some((cfg, path)));
     }
 
     // 2. XDG/HOME: org.toml (soft)
     if let Some(path) = resolve_xdg_path("org.toml") {
         if let Some(cfg) = try_load_soft(&path) {
-            return Ok(Some(cfg));
+            return Ok(Some((cfg, path)));
         }
     }
 
     // 3. /etc/veil/org.toml (soft)
     let etc_path = PathBuf::from("/etc/veil/org.toml");
     if let Some(cfg) = try_load_soft(&etc_path) {
-        return Ok(Some(cfg));
+        return Ok(Some((cfg, etc_path)));
     }
 
     // 4. Legacy: VEIL_ORG_RULES (soft fallback)
     if let Ok(path_str) = std::env::var("VEIL_ORG_RULES") {
         let path = PathBuf::from(&path_str);
         if let Some(cfg) = try_load_soft(&path) {
-            return Ok(Some(cfg));
+            return Ok(Some((cfg, path)));
         } else {
             eprintln!(
                 "Warning: VEIL_ORG_RULES set to {:?} but file not usable.",
@@ -214,7 +253,7 @@ fn load_org_config() -> Result<Option<Config>> {
     Ok(None)
 }
 
-fn load_user_config() -> Result<Option<Config>> {
+fn load_user_config() -> Result<Option<(Config, PathBuf)>> {
     // 1. Explicit: VEIL_USER_CONFIG (strict)
     if let Ok(path_str) = std::env::var("VEIL_USER_CONFIG") {
         let path = PathBuf::from(&path_str);
@@ -223,14 +262,14 @@ fn load_user_config() -> Result<Option<Config>> {
         }
         let cfg = load_config(&path)
             .map_err(|e| anyhow::anyhow!("Failed to load VEIL_USER_CONFIG {:?}: {}", path, e))?;
-        return Ok(Some(cfg));
+        return Ok(Some((cfg, path)));
     }
 
     // 2. XDG/HOME: veil.toml (soft)
     // Note: We use "veil.toml" as the standard user config name, consistent with repo config.
     if let Some(path) = resolve_xdg_path("veil.toml") {
         if let Some(cfg) = try_load_soft(&path) {
-            return Ok(Some(cfg));
+            return Ok(Some((cfg, path)));
         }
     }
 
@@ -267,13 +306,13 @@ fn try_load_soft(path: &Path) -> Option<Config> {
     }
 }
 
-fn load_repo_config(explicit_path: Option<&PathBuf>) -> Result<Option<Config>> {
+fn load_repo_config(explicit_path: Option<&PathBuf>) -> Result<Option<(Config, PathBuf)>> {
     let config_file = explicit_path
         .cloned()
         .unwrap_or_else(|| PathBuf::from("veil.toml"));
 
     match load_config(&config_file) {
-        Ok(c) => Ok(Some(c)),
+        Ok(c) => Ok(Some((c, config_file))),
         Err(e) => {
             // Fail if explicit path was given and missing
             if explicit_path.is_some() && !config_file.exists() {
@@ -315,6 +354,23 @@ fail_on_score = 99
 
         assert!(layers.repo.is_some());
         assert_eq!(layers.effective.core.fail_on_score, Some(99));
+        assert!(layers.files.contains(&path_buf));
+    }
+
+    #[test]
+    fn repo_config_cannot_override_verification_endpoints() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("veil.toml");
+        std::fs::write(
+            &file_path,
+            "[verification]\ntimeout_ms = 1000\n\n[verification.endpoints]\ngithub = \"https://attacker.example\"\n",
+        )
+        .unwrap();
+
+        let layers = load_config_layers(Some(&file_path)).unwrap();
+
+        assert!(layers.effective.verification.endpoints.is_empty());
+        assert_eq!(layers.effective.verification.timeout_ms, 1000);
     }
 
     // Since VEIL_ORG_RULES reads env var which might be shared, we must be careful.
@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use super::{DisplayFinding, FindingStatus, Formatter, Summary};
+use super::{
+    remediated_rule_ids, remediation_by_rule, DisplayFinding, FindingStatus, Formatter, Summary,
+};
 use anyhow::Result;
+use veil_core::explain::{render_remediation, Markup};
+use veil_core::{Remediation, Rule, VerificationStatus};
 
-pub struct HtmlFormatter;
+pub struct HtmlFormatter {
+    remediations: BTreeMap<String, Remediation>,
+}
 
 impl Formatter for HtmlFormatter {
     fn print(&self, findings: &[DisplayFinding], summary: &Summary) -> Result<()> {
@@ -14,8 +20,10 @@ impl Formatter for HtmlFormatter {
 }
 
 impl HtmlFormatter {
-    pub fn new() -> Self {
-        Self
+    pub fn new(rules: &[Rule]) -> Self {
+        Self {
+            remediations: remediation_by_rule(rules),
+        }
     }
 
     pub fn generate_report(&self, findings: &[DisplayFinding], summary: &Summary) -> String {
@@ -84,7 +92,7 @@ impl HtmlFormatter {
                 // Add data-status attribute here
                 format!(
                     r#"<tr class="finding-row" data-severity="{}" data-rule-id="{}" data-file-path="{}" data-status="{}" {}>
-                    <td><span class="badge {}">{}</span></td>
+                    <td><span class="badge {}">{}</span>{}</td>
                     <td>{}</td>
                     <td>{}</td>
                     <td class="mono">{}</td>
@@ -98,6 +106,7 @@ impl HtmlFormatter {
                     opacity_style, // style="opacity: 0.5;"
                     Self::severity_class(inner.score),
                     Self::severity_label(inner.score),
+                    Self::verification_badge(inner.verification),
                     inner.score,
                     html_escape(&inner.rule_id),
                     html_escape(&inner.path.to_string_lossy()),
@@ -108,6 +117,8 @@ impl HtmlFormatter {
             .collect::<Vec<_>>()
             .join("\n");
 
+        let remediation = self.remediation_section(findings);
+
         format!(
             r#"<!DOCTYPE html>
 <html lang="en">
@@ -282,6 +293,9 @@ impl HtmlFormatter {
         .bg-high {{ background-color: var(--sev-high); }}
         .bg-medium {{ background-color: var(--sev-medium); }}
         .bg-low {{ background-color: var(--sev-low); }}
+        .bg-verified {{ background-color: var(--sev-critical); margin-left: 0.25rem; }}
+        .bg-invalid {{ background-color: #38a169; margin-left: 0.25rem; }}
+        .bg-unverified {{ background-color: var(--text-secondary); margin-left: 0.25rem; }}
 
         .mono {{
             font-family: "SFMono-Regular", Consolas, "Liberation Mono", Menlo, Courier, monospace;
@@ -294,6 +308,18 @@ impl HtmlFormatter {
             font-style: italic;
         }}
 
+        /* Remediation */
+        #remediation {{ margin-top: 2rem; }}
+        .remediation-card {{
+            background: var(--bg-card);
+            border: 1px solid var(--border-color);
+            border-radius: 0.5rem;
+            padding: 1rem 1.5rem;
+            margin-bottom: 1rem;
+        }}
+        .remediation-card h3 {{ margin: 0 0 0.5rem 0; }}
+        .history-rewrite {{ color: var(--sev-critical); font-weight: 600; }}
+
     </style>
 </head>
 <body>
@@ -379,6 +405,7 @@ impl HtmlFormatter {
                 {rows}
             </tbody>
         </table>
+        {remediation}
     </div>
 
     <script>
@@ -438,7 +465,32 @@ impl HtmlFormatter {
             baseline_suppressed = summary.baseline_suppressed,
             severity_summary = severity_summary,
             top_rules = top_rules_html,
-            rows = rows
+            rows = rows,
+            remediation = remediation
+        )
+    }
+
+    fn remediation_section(&self, findings: &[DisplayFinding]) -> String {
+        let cards = remediated_rule_ids(findings, &self.remediations)
+            .into_iter()
+            .map(|rule_id| {
+                let remediation = &self.remediations[rule_id];
+                let mut card = format!(
+                    r#"<div class="remediation-card" data-rule-id="{0}"><h3 class="mono">{0}</h3>"#,
+                    html_escape(rule_id)
+                );
+                card.push_str(&render_remediation(remediation, Markup::Html));
+                card.push_str("</div>");
+                card
+            })
+            .collect::<Vec<_>>();
+
+        if cards.is_empty() {
+            return String::new();
+        }
+        format!(
+            r#"<section id="remediation"><h2>Remediation</h2>{}</section>"#,
+            cards.join("\n")
         )
     }
 
@@ -454,6 +506,12 @@ impl HtmlFormatter {
         }
     }
 
+    fn verification_badge(status: Option<VerificationStatus>) -> String {
+        status
+            .map(|status| format!(r#" <span class="badge bg-{0}">{0}</span>"#, status.as_str()))
+            .unwrap_or_default()
+    }
+
     fn severity_label(score: u32) -> &'static str {
         if score >= 90 {
             "CRITICAL"
@@ -512,11 +570,12 @@ mod tests {
 
     #[test]
     fn test_html_generation() {
-        let formatter = HtmlFormatter::new();
+        let formatter = HtmlFormatter::new(&[]);
         let display_findings = vec![DisplayFinding {
             inner: Finding {
                 path: PathBuf::from("test.txt"),
                 line_number: 1,
+                end_line_number: None,
                 line_content: "secret=123".to_string(),
                 matched_content: "password".to_string(),
                 masked_snippet: "********".to_string(),
@@ -531,6 +590,7 @@ mod tests {
                 commit_sha: None,
                 author: None,
                 date: None,
+                verification: None,
             },
             status: FindingStatus::New,
         }];
@@ -539,6 +599,7 @@ mod tests {
             total_files: 1,
             scanned_files: 1,
             skipped_files: 0,
+            cache_hits: 0,
             total_findings: 1,
             new_findings: 1,
             baseline_suppressed: 0,
@@ -550,6 +611,7 @@ mod tests {
             baseline_path: Some("baseline.json".to_string()),
             severity_counts: HashMap::new(),
             builtin_skips: Vec::new(),
+            archive_skips: Vec::new(),
         };
 
         let report = formatter.generate_report(&display_findings, &summary);
@@ -557,5 +619,50 @@ mod tests {
         assert!(report.contains("test.txt"));
         assert!(report.contains("test_rule"));
         assert!(report.contains("********"));
+        assert!(!report.contains(r#"<section id="remediation">"#));
+    }
+
+    #[test]
+    fn test_html_remediation_section() {
+        let rules = veil_core::get_default_rules();
+        let formatter = HtmlFormatter::new(&rules);
+        let mut finding = Finding {
+            path: PathBuf::from("key.pem"),
+            line_number: 1,
+            end_line_number: None,
+            line_content: String::new(),
+            matched_content: String::new(),
+            masked_snippet: "<REDACTED>".to_string(),
+            rule_id: "creds.key.private_pem".to_string(),
+            severity: Severity::Critical,
+            score: 95,
+            grade: veil_core::rules::grade::Grade::Critical,
+            span: Default::default(),
+            utf16_range: Default::default(),
+            context_before: vec![],
+            context_after: vec![],
+            commit_sha: None,
+            author: None,
+            date: None,
+            verification: None,
+        };
+        let mut display_findings = vec![DisplayFinding {
+            inner: finding.clone(),
+            status: FindingStatus::New,
+        }];
+        finding.rule_id = "creds.github.pat.ghp".to_string();
+        display_findings.push(DisplayFinding {
+            inner: finding,
+            status: FindingStatus::New,
+        });
+
+        let section = formatter.remediation_section(&display_findings);
+        assert!(section.contains(r#"data-rule-id="creds.github.pat.ghp""#));
+        assert!(section.contains(r#"<a href="https://github.com/settings/tokens""#));
+        assert!(section.contains("History rewrite required"));
+        assert!(
+            section.find("creds.github.pat.ghp").unwrap()
+                < section.find("creds.key.private_pem").unwrap()
+        );
     }
 }
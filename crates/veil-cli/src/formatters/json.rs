@@ -18,6 +18,7 @@ mod tests {
             inner: Finding {
                 path: PathBuf::from("test.txt"),
                 line_number: 1,
+                end_line_number: None,
                 line_content: "secret=123".to_string(),
                 matched_content: "123".to_string(),
                 masked_snippet: "secret=***".to_string(),
@@ -32,6 +33,7 @@ mod tests {
                 commit_sha: None,
                 author: None,
                 date: None,
+                verification: None,
             },
             status: FindingStatus::New,
         }];
@@ -40,6 +42,7 @@ mod tests {
             total_files: 10,
             scanned_files: 8,
             skipped_files: 2,
+            cache_hits: 0,
             total_findings: 5,
             new_findings: 0,
             baseline_suppressed: 0,
@@ -51,6 +54,7 @@ mod tests {
             baseline_path: None,
             severity_counts: HashMap::new(),
             builtin_skips: Vec::new(),
+            archive_skips: Vec::new(),
         };
 
         let result = formatter.print(&findings, &summary);
@@ -1,7 +1,36 @@
-use crate::formatters::{DisplayFinding, Formatter, Summary};
+use crate::formatters::{
+    remediated_rule_ids, remediation_by_rule, DisplayFinding, Formatter, Summary,
+};
 use anyhow::Result;
+use std::collections::BTreeMap;
+use veil_core::explain::{render_remediation, Markup};
+use veil_core::{Remediation, Rule};
 
-pub struct MarkdownFormatter;
+pub struct MarkdownFormatter {
+    remediations: BTreeMap<String, Remediation>,
+}
+
+impl MarkdownFormatter {
+    pub fn new(rules: &[Rule]) -> Self {
+        Self {
+            remediations: remediation_by_rule(rules),
+        }
+    }
+
+    fn remediation_section(&self, findings: &[DisplayFinding]) -> String {
+        let mut out = String::new();
+        for rule_id in remediated_rule_ids(findings, &self.remediations) {
+            let remediation = &self.remediations[rule_id];
+            out.push_str(&format!("\n### `{}`\n", rule_id));
+            out.push_str(&render_remediation(remediation, Markup::Markdown));
+            out.push('\n');
+        }
+        if out.is_empty() {
+            return out;
+        }
+        format!("\n## Remediation\n{}", out)
+    }
+}
 
 impl Formatter for MarkdownFormatter {
     fn print(&self, findings: &[DisplayFinding], summary: &Summary) -> Result<()> {
@@ -19,9 +48,17 @@ impl Formatter for MarkdownFormatter {
             return Ok(());
         }
 
+        // The verification column only appears when live verification ran.
+        let show_verification = findings.iter().any(|f| f.inner.verification.is_some());
+
         println!("\n## Findings");
-        println!("| Severity | Score | Rule ID | File | Line | Match |");
-        println!("|---|---|---|---|---|---|");
+        if show_verification {
+            println!("| Severity | Score | Verification | Rule ID | File | Line | Match |");
+            println!("|---|---|---|---|---|---|---|");
+        } else {
+            println!("| Severity | Score | Rule ID | File | Line | Match |");
+            println!("|---|---|---|---|---|---|");
+        }
 
         for finding in findings {
             let inner = &finding.inner;
@@ -33,10 +70,20 @@ impl Formatter for MarkdownFormatter {
             // Escape pipe chars in content to prevent breaking md table
             let clean_match = match_content.replace("|", "\\|");
 
+            let verification_cell = if show_verification {
+                format!(
+                    " {} |",
+                    inner.verification.map_or("-", |status| status.as_str())
+                )
+            } else {
+                String::new()
+            };
+
             println!(
-                "| {:?} | {} | {} | {} | {} | `{}` |",
+                "| {:?} | {} |{} {} | {} | {} | `{}` |",
                 inner.severity,
                 inner.score,
+                verification_cell,
                 inner.rule_id,
                 inner.path.display(),
                 inner.line_number,
@@ -44,6 +91,8 @@ impl Formatter for MarkdownFormatter {
             );
         }
 
+        print!("{}", self.remediation_section(findings));
+
         Ok(())
     }
 }
@@ -58,11 +107,12 @@ mod tests {
 
     #[test]
     fn test_markdown_output() {
-        let formatter = MarkdownFormatter;
+        let formatter = MarkdownFormatter::new(&[]);
         let findings = vec![DisplayFinding {
             inner: Finding {
                 path: PathBuf::from("test.txt"),
                 line_number: 1,
+                end_line_number: None,
                 line_content: "secret=123".to_string(),
                 matched_content: "123".to_string(),
                 masked_snippet: "secret=***".to_string(),
@@ -77,6 +127,7 @@ mod tests {
                 commit_sha: None,
                 author: None,
                 date: None,
+                verification: None,
             },
             status: FindingStatus::New,
         }];
@@ -84,6 +135,7 @@ mod tests {
             total_files: 5,
             scanned_files: 5,
             skipped_files: 2,
+            cache_hits: 0,
             total_findings: 5,
             new_findings: 0,
             baseline_suppressed: 0,
@@ -95,9 +147,45 @@ mod tests {
             baseline_path: None,
             severity_counts: HashMap::new(),
             builtin_skips: Vec::new(),
+            archive_skips: Vec::new(),
         };
 
         let result = formatter.print(&findings, &summary);
         assert!(result.is_ok());
+        assert!(formatter.remediation_section(&findings).is_empty());
+    }
+
+    #[test]
+    fn test_markdown_remediation_section() {
+        let formatter = MarkdownFormatter::new(&veil_core::get_default_rules());
+        let findings = vec![DisplayFinding {
+            inner: Finding {
+                path: PathBuf::from(".env"),
+                line_number: 1,
+                end_line_number: None,
+                line_content: String::new(),
+                matched_content: String::new(),
+                masked_snippet: "AWS_KEY=<REDACTED>".to_string(),
+                rule_id: "creds.aws.access_key_id".to_string(),
+                severity: Severity::High,
+                score: 85,
+                grade: veil_core::rules::grade::Grade::High,
+                span: Default::default(),
+                utf16_range: Default::default(),
+                context_before: vec![],
+                context_after: vec![],
+                commit_sha: None,
+                author: None,
+                date: None,
+                verification: None,
+            },
+            status: FindingStatus::New,
+        }];
+
+        let section = formatter.remediation_section(&findings);
+        assert!(section.starts_with("\n## Remediation\n"));
+        assert!(section.contains("### `creds.aws.access_key_id`"));
+        assert!(section.contains("- **Provider**: AWS"));
+        assert!(section.contains("1. Create a new access key"));
     }
 }
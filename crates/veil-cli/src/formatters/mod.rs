@@ -1,16 +1,19 @@
 use anyhow::Result;
-use std::collections::HashMap;
-use veil_core::model::{Finding, Severity};
+use std::collections::{BTreeMap, HashMap};
+use veil_core::model::{Finding, Remediation, Rule, Severity};
+use veil_core::scanner::archive::ArchiveSkip;
 
 pub mod html;
 pub mod json;
 pub mod markdown;
+pub mod sarif;
 #[cfg(feature = "table")]
 pub mod table;
 
 pub use html::HtmlFormatter;
 pub use json::JsonFormatter;
 pub use markdown::MarkdownFormatter;
+pub use sarif::SarifFormatter;
 #[cfg(feature = "table")]
 pub use table::TableFormatter;
 
@@ -21,6 +24,8 @@ pub struct Summary {
     pub total_files: usize,
     pub scanned_files: usize,
     pub skipped_files: usize,
+    /// Files whose findings were served from the scan cache.
+    pub cache_hits: usize,
     pub total_findings: usize,
     pub new_findings: usize,
     pub baseline_suppressed: usize,
@@ -36,6 +41,8 @@ pub struct Summary {
     pub baseline_path: Option<String>,
     pub severity_counts: HashMap<Severity, usize>,
     pub builtin_skips: Vec<String>,
+    /// Archive entries that were not scanned because an `[archive]` limit was hit.
+    pub archive_skips: Vec<ArchiveSkip>,
 }
 
 impl Summary {
@@ -44,6 +51,7 @@ impl Summary {
         total_files: usize,
         scanned_files: usize,
         skipped_files: usize,
+        cache_hits: usize,
         total_findings: usize,
         new_findings: usize,
         baseline_suppressed: usize,
@@ -55,11 +63,13 @@ impl Summary {
         baseline_path: Option<String>,
         severity_counts: HashMap<Severity, usize>,
         builtin_skips: Vec<String>,
+        archive_skips: Vec<ArchiveSkip>,
     ) -> Self {
         Self {
             total_files,
             scanned_files,
             skipped_files,
+            cache_hits,
             total_findings,
             new_findings,
             baseline_suppressed,
@@ -71,6 +81,7 @@ impl Summary {
             baseline_path,
             severity_counts,
             builtin_skips,
+            archive_skips,
         }
     }
 }
@@ -90,3 +101,30 @@ pub struct DisplayFinding {
 pub trait Formatter {
     fn print(&self, findings: &[DisplayFinding], summary: &Summary) -> Result<()>;
 }
+
+/// Remediation guidance keyed by rule id, for formatters that print it.
+pub(crate) fn remediation_by_rule(rules: &[Rule]) -> BTreeMap<String, Remediation> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            rule.remediation
+                .clone()
+                .map(|remediation| (rule.id.clone(), remediation))
+        })
+        .collect()
+}
+
+/// Rule ids of `findings` that have remediation guidance, sorted and deduplicated.
+pub(crate) fn remediated_rule_ids<'a>(
+    findings: &'a [DisplayFinding],
+    remediations: &BTreeMap<String, Remediation>,
+) -> Vec<&'a str> {
+    let mut ids: Vec<&str> = findings
+        .iter()
+        .map(|finding| finding.inner.rule_id.as_str())
+        .filter(|id| remediations.contains_key(*id))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
@@ -0,0 +1,461 @@
+use crate::formatters::{DisplayFinding, FindingStatus, Formatter, Summary};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use veil_core::model::{Finding, Rule, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const FINGERPRINT_KEY: &str = "veil/v1";
+
+/// SARIF 2.1.0 output for code-scanning dashboards and IDE viewers.
+///
+/// Result text only ever carries `masked_snippet`, which the scanner already
+/// rendered with the active `MaskMode`; raw `matched_content`/`line_content`
+/// are never written.
+pub struct SarifFormatter {
+    rules: BTreeMap<String, RuleMeta>,
+}
+
+#[derive(Clone)]
+struct RuleMeta {
+    description: String,
+    severity: Severity,
+    score: u32,
+    category: String,
+    tags: Vec<String>,
+}
+
+impl From<&Rule> for RuleMeta {
+    fn from(rule: &Rule) -> Self {
+        Self {
+            description: rule.description.clone(),
+            severity: rule.severity.clone(),
+            score: rule.base_score.unwrap_or(rule.score),
+            category: rule.category.clone(),
+            tags: rule.tags.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'a str,
+    version: &'a str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    name: String,
+    short_description: SarifMessage,
+    default_configuration: SarifRuleConfiguration,
+    properties: SarifRuleProperties,
+}
+
+#[derive(Serialize)]
+struct SarifRuleConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifRuleProperties {
+    tags: Vec<String>,
+    category: String,
+    severity: String,
+    #[serde(rename = "security-severity")]
+    security_severity: String,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    partial_fingerprints: BTreeMap<&'static str, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SarifSuppression>,
+    properties: SarifResultProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<u32>,
+    snippet: SarifMessage,
+}
+
+#[derive(Serialize)]
+struct SarifSuppression {
+    kind: &'static str,
+    justification: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifResultProperties {
+    score: u32,
+    grade: String,
+    severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification: Option<&'static str>,
+}
+
+impl Formatter for SarifFormatter {
+    fn print(&self, findings: &[DisplayFinding], summary: &Summary) -> Result<()> {
+        let json = self.generate_report(findings, summary)?;
+        println!("{}", json);
+        Ok(())
+    }
+}
+
+impl SarifFormatter {
+    pub fn new(rules: &[Rule]) -> Self {
+        Self {
+            rules: rules
+                .iter()
+                .map(|rule| (rule.id.clone(), RuleMeta::from(rule)))
+                .collect(),
+        }
+    }
+
+    pub fn generate_report(
+        &self,
+        findings: &[DisplayFinding],
+        _summary: &Summary,
+    ) -> Result<String> {
+        // Only describe rules that produced results; packs can hold thousands of rules.
+        let mut referenced: BTreeMap<&str, RuleMeta> = BTreeMap::new();
+        for finding in findings {
+            let id = finding.inner.rule_id.as_str();
+            if !referenced.contains_key(id) {
+                let meta = self
+                    .rules
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| fallback_meta(&finding.inner));
+                referenced.insert(id, meta);
+            }
+        }
+
+        let rule_index: BTreeMap<&str, usize> = referenced
+            .keys()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+
+        let rules = referenced
+            .iter()
+            .map(|(id, meta)| SarifRule {
+                id: id.to_string(),
+                name: id.to_string(),
+                short_description: SarifMessage {
+                    text: meta.description.clone(),
+                },
+                default_configuration: SarifRuleConfiguration {
+                    level: level_for(&meta.severity),
+                },
+                properties: SarifRuleProperties {
+                    tags: meta.tags.clone(),
+                    category: meta.category.clone(),
+                    severity: meta.severity.to_string(),
+                    security_severity: security_severity(meta.score),
+                },
+            })
+            .collect();
+
+        let results = findings
+            .iter()
+            .map(|display| {
+                let finding = &display.inner;
+                let description = referenced
+                    .get(finding.rule_id.as_str())
+                    .map(|meta| meta.description.as_str())
+                    .filter(|text| !text.is_empty())
+                    .unwrap_or(finding.rule_id.as_str());
+                let suppressions = match display.status {
+                    FindingStatus::New => Vec::new(),
+                    FindingStatus::Suppressed => vec![SarifSuppression {
+                        kind: "external",
+                        justification: "Suppressed by veil baseline",
+                    }],
+                };
+
+                SarifResult {
+                    rule_id: finding.rule_id.clone(),
+                    rule_index: rule_index[finding.rule_id.as_str()],
+                    level: level_for(&finding.severity),
+                    message: SarifMessage {
+                        text: format!("{}: {}", description, finding.masked_snippet.trim()),
+                    },
+                    locations: vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation {
+                                uri: artifact_uri(&finding.path),
+                            },
+                            region: region_for(finding),
+                        },
+                    }],
+                    partial_fingerprints: BTreeMap::from([(
+                        FINGERPRINT_KEY,
+                        veil_core::baseline::generate_fingerprint(finding),
+                    )]),
+                    suppressions,
+                    properties: SarifResultProperties {
+                        score: finding.score,
+                        grade: format!("{:?}", finding.grade),
+                        severity: finding.severity.to_string(),
+                        verification: finding.verification.map(|status| status.as_str()),
+                    },
+                }
+            })
+            .collect();
+
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "veil",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://github.com/mt4110/veil-rs",
+                        rules,
+                    },
+                },
+                results,
+            }],
+        };
+
+        Ok(serde_json::to_string_pretty(&log)?)
+    }
+}
+
+fn fallback_meta(finding: &Finding) -> RuleMeta {
+    RuleMeta {
+        description: String::new(),
+        severity: finding.severity.clone(),
+        score: finding.score,
+        category: String::new(),
+        tags: Vec::new(),
+    }
+}
+
+fn level_for(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+/// Maps the 0-100 rule score onto the 0.0-10.0 scale code-scanning UIs expect.
+fn security_severity(score: u32) -> String {
+    format!("{:.1}", f64::from(score.min(100)) / 10.0)
+}
+
+fn region_for(finding: &Finding) -> SarifRegion {
+    let range = finding.utf16_range;
+    let has_columns =
+        finding.end_line_number.is_some() || range.end.character > range.start.character;
+    SarifRegion {
+        start_line: finding.line_number.max(1),
+        end_line: finding.end_line_number,
+        start_column: has_columns.then_some(range.start.character + 1),
+        end_column: has_columns.then_some(range.end.character + 1),
+        snippet: SarifMessage {
+            text: finding.masked_snippet.clone(),
+        },
+    }
+}
+
+fn artifact_uri(path: &Path) -> String {
+    let relative = if path.is_absolute() {
+        std::env::current_dir()
+            .ok()
+            .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+    } else {
+        Some(path.to_path_buf())
+    };
+
+    match relative {
+        Some(rel) => {
+            let uri = rel.to_string_lossy().replace('\\', "/");
+            uri.strip_prefix("./").unwrap_or(&uri).to_string()
+        }
+        None => {
+            let uri = path.to_string_lossy().replace('\\', "/");
+            format!("file:///{}", uri.trim_start_matches('/'))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use veil_core::model::{Position, Range};
+
+    fn finding(status: FindingStatus) -> DisplayFinding {
+        DisplayFinding {
+            inner: Finding {
+                path: PathBuf::from("./src/config.rs"),
+                line_number: 3,
+                end_line_number: None,
+                line_content: "token = AKIA1234567890123456".to_string(),
+                matched_content: "AKIA1234567890123456".to_string(),
+                masked_snippet: "token = <REDACTED>".to_string(),
+                rule_id: "creds.aws.access_key_id".to_string(),
+                severity: Severity::Critical,
+                score: 95,
+                grade: veil_core::rules::grade::Grade::Critical,
+                span: Default::default(),
+                utf16_range: Range {
+                    start: Position {
+                        line: 2,
+                        character: 8,
+                    },
+                    end: Position {
+                        line: 2,
+                        character: 28,
+                    },
+                },
+                context_before: vec![],
+                context_after: vec![],
+                commit_sha: None,
+                author: None,
+                date: None,
+                verification: None,
+            },
+            status,
+        }
+    }
+
+    fn summary() -> Summary {
+        Summary::new(
+            1,
+            1,
+            0,
+            0,
+            1,
+            1,
+            0,
+            false,
+            false,
+            false,
+            false,
+            std::time::Duration::from_millis(0),
+            None,
+            HashMap::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn sarif_maps_rule_metadata_locations_and_fingerprints() {
+        let rules: Vec<Rule> = veil_core::get_default_rules()
+            .into_iter()
+            .filter(|rule| rule.id == "creds.aws.access_key_id")
+            .collect();
+        let formatter = SarifFormatter::new(&rules);
+        let findings = vec![finding(FindingStatus::New)];
+
+        let report = formatter.generate_report(&findings, &summary()).unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        let rule = &run["tool"]["driver"]["rules"][0];
+        assert_eq!(rule["id"], "creds.aws.access_key_id");
+        assert_eq!(rule["shortDescription"]["text"], rules[0].description);
+        assert_eq!(rule["defaultConfiguration"]["level"], "error");
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleIndex"], 0);
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/config.rs");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(location["region"]["startColumn"], 9);
+        assert_eq!(location["region"]["endColumn"], 29);
+        assert_eq!(
+            result["partialFingerprints"]["veil/v1"],
+            veil_core::baseline::generate_fingerprint(&findings[0].inner)
+        );
+        assert!(result.get("suppressions").is_none());
+        assert!(!report.contains("AKIA1234567890123456"));
+    }
+
+    #[test]
+    fn sarif_marks_baseline_suppressed_results() {
+        let formatter = SarifFormatter::new(&[]);
+        let report = formatter
+            .generate_report(&[finding(FindingStatus::Suppressed)], &summary())
+            .unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["suppressions"][0]["kind"], "external");
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"][0]["id"],
+            "creds.aws.access_key_id"
+        );
+    }
+}
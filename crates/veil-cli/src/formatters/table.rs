@@ -18,18 +18,25 @@ impl Formatter for TableFormatter {
         let mut table = Table::new();
         table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
 
-        table.set_titles(Row::new(vec![
+        // The verification column only appears when live verification ran.
+        let show_verification = findings.iter().any(|f| f.inner.verification.is_some());
+
+        let mut titles = vec![
             Cell::new("Severity").with_style(Attr::Bold),
             Cell::new("Score").with_style(Attr::Bold),
             Cell::new("Rule ID").with_style(Attr::Bold),
             Cell::new("File").with_style(Attr::Bold), // Simplification
             Cell::new("Line").with_style(Attr::Bold),
             Cell::new("Match").with_style(Attr::Bold),
-        ]));
+        ];
+        if show_verification {
+            titles.insert(2, Cell::new("Verification").with_style(Attr::Bold));
+        }
+        table.set_titles(Row::new(titles));
 
         for finding in findings {
             let inner = &finding.inner;
-            table.add_row(Row::new(vec![
+            let mut cells = vec![
                 Cell::new(&format!("{:?}", inner.severity))
                     .with_style(Attr::Bold)
                     .with_style(Attr::ForegroundColor(match inner.severity {
@@ -42,7 +49,14 @@ impl Formatter for TableFormatter {
                 Cell::new(&inner.path.display().to_string()),
                 Cell::new(&inner.line_number.to_string()),
                 Cell::new(&inner.matched_content),
-            ]));
+            ];
+            if show_verification {
+                cells.insert(
+                    2,
+                    Cell::new(inner.verification.map_or("-", |status| status.as_str())),
+                );
+            }
+            table.add_row(Row::new(cells));
         }
 
         table.printstd();
@@ -71,6 +85,7 @@ mod tests {
             inner: Finding {
                 path: PathBuf::from("test.txt"),
                 line_number: 1,
+                end_line_number: None,
                 line_content: "secret=123".to_string(),
                 matched_content: "123".to_string(),
                 masked_snippet: "secret=***".to_string(),
@@ -85,6 +100,7 @@ mod tests {
                 commit_sha: None,
                 author: None,
                 date: None,
+                verification: None,
             },
             status: FindingStatus::New,
         }];
@@ -93,6 +109,7 @@ mod tests {
             total_files: 5,
             scanned_files: 5,
             skipped_files: 2,
+            cache_hits: 0,
             total_findings: 5,
             new_findings: 0,
             baseline_suppressed: 0,
@@ -104,6 +121,7 @@ mod tests {
             baseline_path: None,
             severity_counts: HashMap::new(),
             builtin_skips: Vec::new(),
+            archive_skips: Vec::new(),
         };
 
         // Table print output captures invalid compilation if we don't return Result
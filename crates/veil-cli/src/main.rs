@@ -7,6 +7,7 @@ mod output;
 use clap::Parser;
 use cli::{Cli, Commands, RulesCommand};
 use colored::Colorize;
+use commands::scan::CacheMode;
 
 use std::process::exit;
 
@@ -56,6 +57,11 @@ fn main() -> anyhow::Result<()> {
             fail_on_severity,
             write_baseline,
             baseline,
+            no_cache,
+            cache_dir,
+            scan_archives,
+            verify,
+            verify_endpoint,
         }) => {
             // Quiet overrides progress
             let show_progress = *progress && !cli.quiet;
@@ -77,6 +83,10 @@ fn main() -> anyhow::Result<()> {
                 write_baseline.clone(),
                 baseline.clone(),
                 preset.clone(),
+                CacheMode::from_args(*no_cache, cache_dir.clone()),
+                *scan_archives,
+                *verify,
+                verify_endpoint,
                 cli.quiet,
                 cli.no_color,
             )
@@ -119,6 +129,9 @@ fn main() -> anyhow::Result<()> {
                 format,
             } => commands::config::dump(cli.config.as_ref(), preset.as_deref(), *layer, *format)
                 .map(|_| false),
+            crate::cli::ConfigCommand::ExplainPath { path } => {
+                commands::config::explain_path(cli.config.as_ref(), path).map(|_| false)
+            }
         },
         Some(Commands::PreCommit(cmd)) => match cmd {
             crate::cli::PreCommitCommand::Init => commands::pre_commit::init().map(|_| false),
@@ -129,6 +142,11 @@ fn main() -> anyhow::Result<()> {
             crate::cli::GitCommand::Scan(args) => commands::git::scan(args).map(|_| false),
         },
         Some(Commands::Doctor) => commands::doctor::doctor().map(|_| false),
+        Some(Commands::Cache(cmd)) => match cmd {
+            crate::cli::CacheCommand::Clear { cache_dir } => {
+                commands::cache::clear(cache_dir.as_deref()).map(|_| false)
+            }
+        },
         Some(Commands::Rules(cmd)) => match cmd {
             RulesCommand::List { severity } => {
                 commands::rules::list(cli.config.as_ref(), severity.clone()).map(|_| false)
@@ -143,7 +161,9 @@ fn main() -> anyhow::Result<()> {
         Some(Commands::Lsp { preset }) => {
             commands::lsp::run(cli.config.as_ref(), preset.as_deref(), cli.quiet).map(|_| false)
         }
-        Some(Commands::Guardian(args)) => commands::guardian::run(args.clone()).map(|_| false),
+        Some(Commands::Guardian(args)) => {
+            commands::guardian::run(args.clone(), cli.config.as_ref()).map(|_| false)
+        }
         Some(Commands::Sot(cmd)) => commands::sot::run(cmd).map(|_| false),
         Some(Commands::Exceptions(args)) => commands::exceptions::run(args).map(|_| false),
         Some(Commands::Update) => commands::update::update().map(|_| false),
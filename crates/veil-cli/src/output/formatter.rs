@@ -1,5 +1,5 @@
 use colored::*;
-use veil_core::{Finding, Grade, Severity};
+use veil_core::{Finding, Grade, Severity, VerificationStatus};
 
 pub fn print_finding(finding: &Finding) {
     let severity_colored = match finding.severity {
@@ -17,11 +17,19 @@ pub fn print_finding(finding: &Finding) {
         Grade::Critical => "CRITICAL".red().bold().on_black(),
     };
 
+    let verification = match finding.verification {
+        Some(VerificationStatus::Verified) => " [verified]".red().bold().to_string(),
+        Some(VerificationStatus::Invalid) => " [invalid]".green().to_string(),
+        Some(VerificationStatus::Unverified) => " [unverified]".dimmed().to_string(),
+        None => String::new(),
+    };
+
     println!(
-        "[{}] [{}] (Score: {}) [{}] {}:{}  {}",
+        "[{}] [{}] (Score: {}){} [{}] {}:{}  {}",
         grade_colored,
         severity_colored,
         finding.score,
+        verification,
         finding.rule_id.cyan(),
         finding.path.display(),
         finding.line_number,
@@ -0,0 +1,76 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs::File;
+use std::io::Write;
+use tempfile::TempDir;
+
+fn write_zip(path: &std::path::Path, entries: &[(&str, &[u8])]) {
+    let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+    for (name, data) in entries {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+#[test]
+fn scan_archives_reports_nested_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_zip(
+        &temp_dir.path().join("release.zip"),
+        &[("app/.env", b"AWS_ACCESS_KEY_ID=AKIA1234567890ABCDEF\n")],
+    );
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args([
+            "scan",
+            ".",
+            "--format",
+            "json",
+            "--no-cache",
+            "--scan-archives",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("release.zip!/app/.env"));
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["scan", ".", "--format", "json", "--no-cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("release.zip!/").not());
+
+    Ok(())
+}
+
+#[test]
+fn archive_limits_make_the_scan_incomplete() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    write_zip(
+        &temp_dir.path().join("big.zip"),
+        &[("data.txt", &[b'a'; 4096])],
+    );
+    std::fs::write(
+        temp_dir.path().join("veil.toml"),
+        "[archive]\nenabled = true\nmax_entry_bytes = 1024\n",
+    )?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["scan", ".", "--format", "json", "--no-cache"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("\"reason\": \"max_entry_bytes\""))
+        .stderr(predicate::str::contains(
+            "big.zip!/data.txt (max_entry_bytes)",
+        ));
+
+    Ok(())
+}
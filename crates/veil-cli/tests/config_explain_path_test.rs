@@ -0,0 +1,115 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::tempdir;
+
+fn explain(dir: &std::path::Path, path: &str) -> assert_cmd::assert::Assert {
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(dir)
+        .args(["config", "explain-path", path])
+        .assert()
+}
+
+#[test]
+fn explain_path_reports_deciding_pattern_and_layer() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("veil.toml"),
+        "[core]\ninclude = [\"src\", \"test\"]\nignore = [\"test\", \"*.snap\", \"!keep.snap\"]\n",
+    )
+    .unwrap();
+
+    explain(dir.path(), "src/latest.rs")
+        .success()
+        .stdout(predicate::str::contains(
+            "✅ scanned: include pattern \"src\" (repo layer)",
+        ));
+    explain(dir.path(), "test/a.rs")
+        .success()
+        .stdout(predicate::str::contains(
+            "❌ excluded: ignore pattern \"test\" (repo layer)",
+        ));
+    explain(dir.path(), "src/keep.snap")
+        .success()
+        .stdout(predicate::str::contains(
+            "✅ scanned: include pattern \"src\"",
+        ));
+    explain(dir.path(), "docs/readme.md")
+        .success()
+        .stdout(predicate::str::contains(
+            "❌ excluded: no include pattern matches",
+        ));
+}
+
+#[test]
+fn scan_ignore_matches_whole_path_components() {
+    let dir = tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("src/latest.rs"),
+        "AWS_ACCESS_KEY_ID=AKIA1234567890ABCDEF\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("veil.toml"),
+        "[core]\nignore = [\"test\"]\n",
+    )
+    .unwrap();
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(dir.path())
+        .args(["scan", ".", "--format", "json", "--no-cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/latest.rs"));
+}
+
+#[test]
+fn anchored_ignore_applies_when_scanning_a_subdirectory() {
+    let dir = tempdir().unwrap();
+    let project = dir.path().join("project");
+    std::fs::create_dir_all(project.join("src/generated")).unwrap();
+    std::fs::write(
+        project.join("src/generated/a.txt"),
+        "AWS_ACCESS_KEY_ID=AKIA1234567890ABCDEF\n",
+    )
+    .unwrap();
+    std::fs::write(
+        project.join("veil.toml"),
+        "[core]\nignore = [\"/src/generated\"]\n",
+    )
+    .unwrap();
+
+    explain(&project, "src/generated/a.txt")
+        .success()
+        .stdout(predicate::str::contains(
+            "❌ excluded: ignore pattern \"/src/generated\" (repo layer)",
+        ));
+    for target in [".", "src"] {
+        #[allow(deprecated)]
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+        cmd.current_dir(&project)
+            .args(["scan", target, "--format", "json", "--no-cache"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("generated").not());
+    }
+
+    // With --config, patterns are anchored at the config's directory.
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(dir.path())
+        .args([
+            "scan",
+            "project/src",
+            "--config",
+            "project/veil.toml",
+            "--format",
+            "json",
+            "--no-cache",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("generated").not());
+}
@@ -0,0 +1,61 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn git(repo_path: &Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=Alice",
+            "-c",
+            "user.email=alice@example.com",
+        ])
+        .args(args)
+        .current_dir(repo_path)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn git_scan_all_finds_deleted_secrets_on_other_branches() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    git(repo_path, &["init", "-q", "-b", "main"]);
+    std::fs::write(repo_path.join("README.md"), "hello\n")?;
+    git(repo_path, &["add", "."]);
+    git(repo_path, &["commit", "-q", "-m", "init"]);
+
+    git(repo_path, &["checkout", "-q", "-b", "feature"]);
+    std::fs::write(
+        repo_path.join("deploy.env"),
+        "AWS_ACCESS_KEY_ID=AKIA1234567890ABCDEF\n",
+    )?;
+    git(repo_path, &["add", "."]);
+    git(repo_path, &["commit", "-q", "-m", "add key"]);
+    git(repo_path, &["rm", "-q", "deploy.env"]);
+    git(repo_path, &["commit", "-q", "-m", "remove key"]);
+    git(repo_path, &["checkout", "-q", "main"]);
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(repo_path)
+        .args(["git", "scan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 0 secrets."));
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(repo_path)
+        .args(["git", "scan", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found 1 secrets."))
+        .stdout(predicate::str::contains("deploy.env"))
+        .stdout(predicate::str::contains("Alice"));
+
+    Ok(())
+}
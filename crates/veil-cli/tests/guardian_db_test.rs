@@ -0,0 +1,121 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+
+const PACKAGE_LOCK: &str = r#"{
+    "name": "app",
+    "lockfileVersion": 3,
+    "packages": {
+        "": { "name": "app", "version": "1.0.0" },
+        "node_modules/a-lib": { "version": "1.0.0" },
+        "node_modules/b-lib": { "version": "2.0.0" }
+    }
+}"#;
+
+fn write_export(path: &Path) {
+    let records = [
+        r#"{ "id": "GHSA-a1", "modified": "2026-01-15T00:00:00Z", "summary": "A1 issue",
+             "affected": [ { "package": { "ecosystem": "npm", "name": "a-lib" },
+               "ranges": [ { "type": "SEMVER",
+                 "events": [ { "introduced": "0" }, { "fixed": "1.2.0" } ] } ] } ] }"#,
+        r#"{ "id": "GHSA-b1", "modified": "2026-01-10T00:00:00Z", "summary": "B1 issue",
+             "affected": [ { "package": { "ecosystem": "npm", "name": "b-lib" },
+               "ranges": [ { "type": "SEMVER",
+                 "events": [ { "introduced": "3.0.0" } ] } ] } ] }"#,
+    ];
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+    for (i, record) in records.iter().enumerate() {
+        writer
+            .start_file(
+                format!("{}.json", i),
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(record.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+fn veil(cache_home: &Path, workdir: &Path) -> Command {
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(workdir)
+        .env("XDG_CACHE_HOME", cache_home)
+        .env_remove("OSV_API_URL");
+    cmd
+}
+
+#[test]
+fn guardian_offline_check_uses_imported_database() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let cache_home = temp_dir.path().join("cache");
+    std::fs::write(temp_dir.path().join("package-lock.json"), PACKAGE_LOCK)?;
+    write_export(&temp_dir.path().join("npm.zip"));
+
+    veil(&cache_home, temp_dir.path())
+        .args(["guardian", "check", "package-lock.json", "--offline"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("guardian db import"));
+
+    veil(&cache_home, temp_dir.path())
+        .args(["guardian", "db", "import", "npm.zip"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Imported 2 npm advisories from npm.zip (updated 2026-01-15)",
+        ));
+
+    veil(&cache_home, temp_dir.path())
+        .args(["guardian", "db", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("npm"))
+        .stdout(predicate::str::contains("2026-01-15"));
+
+    veil(&cache_home, temp_dir.path())
+        .args([
+            "guardian",
+            "check",
+            "package-lock.json",
+            "--offline",
+            "--osv-details",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("[GHSA-a1] A1 issue"))
+        .stdout(predicate::str::contains("Upgrade: 1.0.0 -> 1.2.0"))
+        .stdout(predicate::str::contains("Status: Hit (Offline DB)"))
+        .stdout(predicate::str::contains("GHSA-b1").not())
+        .stdout(predicate::str::contains(
+            "  npm: 2 advisories from npm.zip, updated 2026-01-15",
+        ));
+
+    veil(&cache_home, temp_dir.path())
+        .args([
+            "guardian",
+            "check",
+            "package-lock.json",
+            "--offline",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(r#""offline_databases""#));
+
+    Ok(())
+}
+
+#[test]
+fn guardian_db_status_without_import() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    veil(&temp_dir.path().join("cache"), temp_dir.path())
+        .args(["guardian", "db", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No OSV database imported"));
+    Ok(())
+}
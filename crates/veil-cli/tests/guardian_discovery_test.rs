@@ -0,0 +1,51 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+// Path dependencies are never sent to OSV, so this runs without network and
+// only the builtin advisory DB (GF-001) reports.
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "dummy-vulnerable-crate"
+version = "0.9.9"
+"#;
+
+#[test]
+fn guardian_check_directory_discovers_lockfiles() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    for dir in ["services/api", "services/worker", "node_modules/pkg"] {
+        let dir = temp_dir.path().join(dir);
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("Cargo.lock"), LOCKFILE)?;
+    }
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["guardian", "check", ".", "--format", "json"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Discovered 2 lockfiles"))
+        .stdout(predicate::str::contains("GF-001"))
+        .stdout(predicate::str::contains("services/api/Cargo.lock"))
+        .stdout(predicate::str::contains("services/worker/Cargo.lock"))
+        .stdout(predicate::str::contains("node_modules").not());
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_directory_without_lockfiles_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["guardian", "check", "."])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No supported lockfiles found"));
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+// Path dependencies are never sent to OSV, so only the builtin advisory DB
+// (GF-001) reports.
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "dummy-vulnerable-crate"
+version = "0.9.9"
+"#;
+
+fn veil(dir: &TempDir) -> Command {
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(dir.path());
+    cmd
+}
+
+#[test]
+fn guardian_exception_suppresses_advisory_until_expiry() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    veil(&temp_dir)
+        .args([
+            "guardian",
+            "exceptions",
+            "add",
+            "GF-001",
+            "--package",
+            "dummy-vulnerable-crate",
+            "--versions",
+            "< 1.0.0",
+            "--reason",
+            "only used in tests",
+            "--expires",
+            "30d",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added guardian exception"));
+    assert!(temp_dir
+        .path()
+        .join("ops/guardian-exceptions.toml")
+        .exists());
+
+    veil(&temp_dir)
+        .args(["guardian", "check", "Cargo.lock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No vulnerabilities found"))
+        .stdout(predicate::str::contains(
+            "Suppressed by guardian exceptions:",
+        ))
+        .stdout(predicate::str::contains(
+            "[GF-001] dummy-vulnerable-crate v0.9.9 (crates.io): only used in tests",
+        ));
+
+    veil(&temp_dir)
+        .args(["guardian", "check", "Cargo.lock", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""suppressed""#));
+
+    // Re-adding the same key updates it in place; a past expiry re-fails.
+    veil(&temp_dir)
+        .args([
+            "guardian",
+            "exceptions",
+            "add",
+            "GF-001",
+            "--package",
+            "dummy-vulnerable-crate",
+            "--versions",
+            "< 1.0.0",
+            "--reason",
+            "only used in tests",
+            "--expires=-1d",
+        ])
+        .assert()
+        .success();
+
+    veil(&temp_dir)
+        .args(["guardian", "exceptions", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GF-001"))
+        .stdout(predicate::str::contains("Expired"));
+
+    veil(&temp_dir)
+        .args(["guardian", "check", "Cargo.lock"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Expired exceptions (reported again):",
+        ));
+
+    veil(&temp_dir)
+        .args(["guardian", "exceptions", "cleanup"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 1 expired exceptions."));
+
+    veil(&temp_dir)
+        .args(["guardian", "exceptions", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No guardian exceptions found"));
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_explicit_missing_registry_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    veil(&temp_dir)
+        .args([
+            "guardian",
+            "check",
+            "Cargo.lock",
+            "--exceptions",
+            "missing.toml",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "guardian exception registry not found",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+// Path dependencies are never sent to OSV, so only the builtin advisory DB
+// reports: GF-001 affects "< 1.0.0".
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "dummy-vulnerable-crate"
+version = "0.9.9"
+"#;
+
+#[test]
+fn guardian_check_reports_fixed_version() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["guardian", "check", "Cargo.lock"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("(1 fixable by upgrading)"))
+        .stdout(predicate::str::contains("Upgrade: 0.9.9 -> 1.0.0"));
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["guardian", "check", "Cargo.lock", "--format", "json"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(r#""fixed_version": "1.0.0""#));
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_fail_on_fixable_blocks_when_fix_exists() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["guardian", "check", "Cargo.lock", "--fail-on-fixable"])
+        .assert()
+        .code(1);
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_fail_on_fixable_passes_clean_lockfile() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = TempDir::new()?;
+    std::fs::write(
+        temp_dir.path().join("Cargo.lock"),
+        "version = 3\n\n[[package]]\nname = \"dummy-vulnerable-crate\"\nversion = \"1.0.0\"\n",
+    )?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args(["guardian", "check", "Cargo.lock", "--fail-on-fixable"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No vulnerabilities found"));
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_fail_on_severity_blocks_unrated_advisories(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    // Builtin advisories carry no severity; nothing shows they are below the
+    // threshold, so they block.
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args([
+            "guardian",
+            "check",
+            "Cargo.lock",
+            "--fail-on-severity",
+            "critical",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("GF-001"))
+        .stderr(predicate::str::contains("(1 unrated)"));
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(temp_dir.path())
+        .args([
+            "guardian",
+            "check",
+            "Cargo.lock",
+            "--min-severity",
+            "severe",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid severity: severe"));
+
+    Ok(())
+}
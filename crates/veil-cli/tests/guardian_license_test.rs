@@ -0,0 +1,107 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+
+const PACKAGE_LOCK: &str = r#"{
+    "name": "app",
+    "lockfileVersion": 3,
+    "packages": {
+        "": { "name": "app", "dependencies": { "ok-lib": "^1.0.0", "gpl-lib": "^2.0.0" } },
+        "node_modules/ok-lib": { "version": "1.0.0", "license": "MIT OR GPL-3.0-only" },
+        "node_modules/gpl-lib": { "version": "2.0.0", "license": "GPL-3.0-only" }
+    }
+}"#;
+
+/// Imports an OSV export without advisories for the lockfile's packages, so
+/// `--offline` scans find no vulnerabilities.
+fn import_empty_database(cache_home: &Path, workdir: &Path) {
+    let archive = workdir.join("npm.zip");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+    writer
+        .start_file("0.json", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    writer
+        .write_all(
+            br#"{ "id": "GHSA-other", "modified": "2026-01-15T00:00:00Z",
+                  "affected": [ { "package": { "ecosystem": "npm", "name": "other-lib" } } ] }"#,
+        )
+        .unwrap();
+    writer.finish().unwrap();
+
+    veil(cache_home, workdir)
+        .args(["guardian", "db", "import", "npm.zip"])
+        .assert()
+        .success();
+}
+
+fn veil(cache_home: &Path, workdir: &Path) -> Command {
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    cmd.current_dir(workdir)
+        .env("XDG_CACHE_HOME", cache_home)
+        .env_remove("OSV_API_URL");
+    cmd
+}
+
+#[test]
+fn guardian_check_fails_on_denied_license() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let cache_home = temp_dir.path().join("cache");
+    std::fs::write(temp_dir.path().join("package-lock.json"), PACKAGE_LOCK)?;
+    std::fs::write(
+        temp_dir.path().join("veil.toml"),
+        "[guardian.licenses]\ndeny = [\"GPL-3.0-only\"]\n",
+    )?;
+    import_empty_database(&cache_home, temp_dir.path());
+
+    veil(&cache_home, temp_dir.path())
+        .args(["guardian", "check", "package-lock.json", "--offline"])
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains(
+            "No vulnerabilities found in 2 packages.",
+        ))
+        .stdout(predicate::str::contains("License policy violations (1):"))
+        .stdout(predicate::str::contains(
+            "- gpl-lib v2.0.0 (npm): GPL-3.0-only (denied: GPL-3.0-only)",
+        ))
+        .stdout(predicate::str::contains("ok-lib").not())
+        .stderr(predicate::str::contains(
+            "found 1 package(s) violating the license policy",
+        ));
+
+    let output = veil(&cache_home, temp_dir.path())
+        .args([
+            "guardian",
+            "check",
+            "package-lock.json",
+            "--offline",
+            "--format",
+            "json",
+        ])
+        .output()?;
+    assert_eq!(output.status.code(), Some(3));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["license_violations"][0]["package_name"], "gpl-lib");
+    assert_eq!(json["license_violations"][0]["license"], "GPL-3.0-only");
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_passes_without_license_policy() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let cache_home = temp_dir.path().join("cache");
+    std::fs::write(temp_dir.path().join("package-lock.json"), PACKAGE_LOCK)?;
+    import_empty_database(&cache_home, temp_dir.path());
+
+    veil(&cache_home, temp_dir.path())
+        .args(["guardian", "check", "package-lock.json", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("License policy").not());
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+// Path dependencies are never sent to OSV, so only the builtin advisory DB
+// reports: GF-001 affects "< 1.0.0".
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "dummy-vulnerable-crate"
+version = "0.9.9"
+"#;
+
+#[test]
+fn guardian_check_writes_cyclonedx_with_vex() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .args(["guardian", "check", "Cargo.lock", "--format", "cyclonedx"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(1));
+
+    let bom: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+    assert_eq!(
+        bom["components"][0]["purl"],
+        "pkg:cargo/dummy-vulnerable-crate@0.9.9"
+    );
+    assert_eq!(bom["vulnerabilities"][0]["id"], "GF-001");
+    assert_eq!(bom["vulnerabilities"][0]["analysis"]["state"], "in_triage");
+    assert_eq!(
+        bom["vulnerabilities"][0]["recommendation"],
+        "Upgrade to >= 1.0.0"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn guardian_check_writes_spdx() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    std::fs::write(temp_dir.path().join("Cargo.lock"), LOCKFILE)?;
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .args(["guardian", "check", "Cargo.lock", "--format", "spdx"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(1));
+
+    let spdx: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(spdx["spdxVersion"], "SPDX-2.3");
+    let package = &spdx["packages"][0];
+    assert_eq!(package["name"], "dummy-vulnerable-crate");
+    assert_eq!(
+        package["externalRefs"][1]["comment"],
+        "Affected; Upgrade to >= 1.0.0"
+    );
+
+    Ok(())
+}
@@ -37,7 +37,11 @@ fn rules_explain_shows_details() {
         .success()
         .stdout(predicate::str::contains("AWS Access Key ID"))
         .stdout(predicate::str::contains("Pattern:"))
-        .stdout(predicate::str::contains("Context:"));
+        .stdout(predicate::str::contains("Context:"))
+        .stdout(predicate::str::contains("Remediation:"))
+        .stdout(predicate::str::contains(
+            "https://console.aws.amazon.com/iam/home#/security_credentials",
+        ));
 }
 
 #[test]
@@ -0,0 +1,47 @@
+use assert_cmd::Command;
+use std::fs;
+
+#[test]
+fn sarif_output_is_valid_and_masks_secrets() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(
+        temp_dir.path().join("fake_secrets.py"),
+        "aws_key = \"AKIA1234567890123456\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_veil"))
+        .current_dir(temp_dir.path())
+        .arg("scan")
+        .arg(".")
+        .arg("--format")
+        .arg("sarif")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let sarif: serde_json::Value =
+        serde_json::from_str(&stdout).expect("stdout must be a single SARIF document");
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "veil");
+
+    let results = run["results"].as_array().unwrap();
+    let aws = results
+        .iter()
+        .find(|r| r["ruleId"] == "creds.aws.access_key_id")
+        .expect("AWS finding should be reported");
+    let location = &aws["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "fake_secrets.py");
+    assert_eq!(location["region"]["startLine"], 1);
+    assert!(aws["partialFingerprints"]["veil/v1"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+
+    assert!(
+        !stdout.contains("AKIA1234567890123456"),
+        "raw secret leaked into SARIF output"
+    );
+}
@@ -0,0 +1,70 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn scan_json(dir: &std::path::Path, cache_dir: &std::path::Path) -> Value {
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    let output = cmd
+        .current_dir(dir)
+        .args(["scan", ".", "--format", "json", "--cache-dir"])
+        .arg(cache_dir)
+        .output()
+        .unwrap();
+    serde_json::from_slice(&output.stdout).expect("scan should print JSON")
+}
+
+#[test]
+fn second_scan_reports_cache_hits() -> Result<(), Box<dyn std::error::Error>> {
+    let work = TempDir::new()?;
+    let cache = TempDir::new()?;
+    std::fs::write(
+        work.path().join("config.env"),
+        "AWS_ACCESS_KEY_ID=AKIA1234567890ABCDEF\n",
+    )?;
+    std::fs::write(work.path().join("main.rs"), "fn main() {}\n")?;
+
+    let first = scan_json(work.path(), cache.path());
+    assert_eq!(first["summary"]["cache_hits"], 0);
+
+    let second = scan_json(work.path(), cache.path());
+    assert_eq!(second["summary"]["cache_hits"], 2);
+    assert_eq!(
+        second["summary"]["new_findings"],
+        first["summary"]["new_findings"]
+    );
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    let output = cmd
+        .current_dir(work.path())
+        .args(["--no-color", "scan", ".", "--cache-dir"])
+        .arg(cache.path())
+        .output()?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("  Cache Hits:    2\n"));
+
+    Ok(())
+}
+
+#[test]
+fn cache_clear_forces_a_full_rescan() -> Result<(), Box<dyn std::error::Error>> {
+    let work = TempDir::new()?;
+    let cache = TempDir::new()?;
+    std::fs::write(work.path().join("main.rs"), "fn main() {}\n")?;
+
+    scan_json(work.path(), cache.path());
+
+    #[allow(deprecated)]
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_veil"));
+    let output = cmd
+        .env("VEIL_CACHE_DIR", cache.path())
+        .args(["cache", "clear"])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Removed 1 cached scan result(s)"));
+
+    let rescan = scan_json(work.path(), cache.path());
+    assert_eq!(rescan["summary"]["cache_hits"], 0);
+
+    Ok(())
+}
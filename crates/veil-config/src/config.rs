@@ -1,6 +1,6 @@
 use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct Config {
@@ -10,6 +10,14 @@ pub struct Config {
     pub masking: MaskingConfig,
     #[serde(default)]
     pub output: OutputConfig,
+    #[serde(default, skip_serializing_if = "EntropyConfig::is_default")]
+    pub entropy: EntropyConfig,
+    #[serde(default, skip_serializing_if = "VerificationConfig::is_default")]
+    pub verification: VerificationConfig,
+    #[serde(default, skip_serializing_if = "ArchiveConfig::is_default")]
+    pub archive: ArchiveConfig,
+    #[serde(default, skip_serializing_if = "GuardianConfig::is_default")]
+    pub guardian: GuardianConfig,
     #[serde(default)]
     pub rules: HashMap<String, RuleConfig>,
 }
@@ -107,6 +115,11 @@ pub struct CoreConfig {
     pub fail_on_score: Option<u32>,
     pub remote_rules_url: Option<String>,
     pub rules_dir: Option<String>,
+    /// Directory `/`-anchored `ignore`/`include` patterns resolve against:
+    /// the directory of the repo config. Set by the config loader, never
+    /// read from a file; `None` means the working directory.
+    #[serde(skip)]
+    pub patterns_root: Option<std::path::PathBuf>,
 }
 
 impl Config {
@@ -162,6 +175,11 @@ impl Config {
             self.output.max_findings = other.output.max_findings;
             self.output.max_findings_is_set = other.output.max_findings_is_set;
         }
+
+        self.entropy.merge(other.entropy);
+        self.verification.merge(other.verification);
+        self.archive.merge(other.archive);
+        self.guardian.merge(other.guardian);
     }
 }
 
@@ -175,6 +193,7 @@ impl Default for CoreConfig {
             fail_on_score: None,
             remote_rules_url: None,
             rules_dir: None,
+            patterns_root: None,
         }
     }
 }
@@ -202,6 +221,310 @@ fn default_placeholder() -> String {
     "<REDACTED>".to_string()
 }
 
+/// Heuristic detection of high-entropy tokens that no vendor regex covers.
+///
+/// Disabled by default. Thresholds are Shannon entropy in bits per character
+/// and apply per charset; a keyword on the same line lowers the threshold by
+/// `keyword_boost`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct EntropyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_entropy_min_length")]
+    pub min_length: usize,
+    #[serde(default = "default_base64_threshold")]
+    pub base64_threshold: f64,
+    #[serde(default = "default_hex_threshold")]
+    pub hex_threshold: f64,
+    #[serde(default = "default_alphanumeric_threshold")]
+    pub alphanumeric_threshold: f64,
+    #[serde(default = "default_entropy_keywords")]
+    pub keywords: Vec<String>,
+    #[serde(default = "default_keyword_boost")]
+    pub keyword_boost: f64,
+    /// Extra regex patterns; tokens matching any of them are never reported.
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+impl Default for EntropyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_length: default_entropy_min_length(),
+            base64_threshold: default_base64_threshold(),
+            hex_threshold: default_hex_threshold(),
+            alphanumeric_threshold: default_alphanumeric_threshold(),
+            keywords: default_entropy_keywords(),
+            keyword_boost: default_keyword_boost(),
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+impl EntropyConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn merge(&mut self, other: EntropyConfig) {
+        let defaults = EntropyConfig::default();
+        if other.enabled {
+            self.enabled = true;
+        }
+        if other.min_length != defaults.min_length {
+            self.min_length = other.min_length;
+        }
+        if other.base64_threshold != defaults.base64_threshold {
+            self.base64_threshold = other.base64_threshold;
+        }
+        if other.hex_threshold != defaults.hex_threshold {
+            self.hex_threshold = other.hex_threshold;
+        }
+        if other.alphanumeric_threshold != defaults.alphanumeric_threshold {
+            self.alphanumeric_threshold = other.alphanumeric_threshold;
+        }
+        if other.keywords != defaults.keywords {
+            self.keywords = other.keywords;
+        }
+        if other.keyword_boost != defaults.keyword_boost {
+            self.keyword_boost = other.keyword_boost;
+        }
+        for pattern in other.allowlist {
+            if !self.allowlist.contains(&pattern) {
+                self.allowlist.push(pattern);
+            }
+        }
+    }
+}
+
+/// Live verification of detected credentials against vendor APIs.
+///
+/// Only runs when a scan is started with `--verify`; it sends candidate
+/// secrets to the provider (or to the override in `endpoints`, keyed by
+/// verifier id such as `aws`, `github` or `slack`). `enabled = false` in any
+/// layer turns it off even with `--verify`, and the last layer that sets
+/// `enabled` wins.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct VerificationConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default = "default_verification_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub endpoints: BTreeMap<String, String>,
+}
+
+impl Default for VerificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: None,
+            timeout_ms: default_verification_timeout_ms(),
+            endpoints: BTreeMap::new(),
+        }
+    }
+}
+
+impl VerificationConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// `false` unless some layer set `enabled = false`.
+    pub fn is_disabled(&self) -> bool {
+        self.enabled == Some(false)
+    }
+
+    fn merge(&mut self, other: VerificationConfig) {
+        if other.enabled.is_some() {
+            self.enabled = other.enabled;
+        }
+        if other.timeout_ms != default_verification_timeout_ms() {
+            self.timeout_ms = other.timeout_ms;
+        }
+        self.endpoints.extend(other.endpoints);
+    }
+}
+
+fn default_verification_timeout_ms() -> u64 {
+    5000
+}
+
+/// Traversal of zip/jar, tar, tar.gz and gz files during filesystem scans.
+///
+/// Disabled by default. The byte limits mirror the zip-bomb bounds used when
+/// verifying evidence packs; sizes are uncompressed and counted per top-level
+/// archive, including nested archives.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ArchiveConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Nesting depth; 1 scans the archive's entries but not archives inside it.
+    #[serde(default = "default_archive_max_depth")]
+    pub max_depth: u32,
+    /// Largest archive file (on disk) that is opened at all.
+    #[serde(default = "default_archive_max_archive_bytes")]
+    pub max_archive_bytes: u64,
+    #[serde(default = "default_archive_max_entry_bytes")]
+    pub max_entry_bytes: u64,
+    #[serde(default = "default_archive_max_total_bytes")]
+    pub max_total_bytes: u64,
+    #[serde(default = "default_archive_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_depth: default_archive_max_depth(),
+            max_archive_bytes: default_archive_max_archive_bytes(),
+            max_entry_bytes: default_archive_max_entry_bytes(),
+            max_total_bytes: default_archive_max_total_bytes(),
+            max_entries: default_archive_max_entries(),
+        }
+    }
+}
+
+impl ArchiveConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn merge(&mut self, other: ArchiveConfig) {
+        let defaults = ArchiveConfig::default();
+        if other.enabled {
+            self.enabled = true;
+        }
+        if other.max_depth != defaults.max_depth {
+            self.max_depth = other.max_depth;
+        }
+        if other.max_archive_bytes != defaults.max_archive_bytes {
+            self.max_archive_bytes = other.max_archive_bytes;
+        }
+        if other.max_entry_bytes != defaults.max_entry_bytes {
+            self.max_entry_bytes = other.max_entry_bytes;
+        }
+        if other.max_total_bytes != defaults.max_total_bytes {
+            self.max_total_bytes = other.max_total_bytes;
+        }
+        if other.max_entries != defaults.max_entries {
+            self.max_entries = other.max_entries;
+        }
+    }
+}
+
+/// Settings for `veil guardian check`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct GuardianConfig {
+    #[serde(default, skip_serializing_if = "LicensePolicyConfig::is_default")]
+    pub licenses: LicensePolicyConfig,
+}
+
+impl GuardianConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn merge(&mut self, other: GuardianConfig) {
+        self.licenses.merge(other.licenses);
+    }
+}
+
+/// License compliance policy for dependencies, as SPDX license identifiers.
+///
+/// A package passes when its license expression can be satisfied using only
+/// licenses that are not denied and, if `allow` is non-empty, are allowed.
+/// Packages without a known license pass unless `fail_on_unknown` is set.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct LicensePolicyConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub fail_on_unknown: bool,
+}
+
+impl LicensePolicyConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn merge(&mut self, other: LicensePolicyConfig) {
+        for id in other.allow {
+            if !self.allow.contains(&id) {
+                self.allow.push(id);
+            }
+        }
+        for id in other.deny {
+            if !self.deny.contains(&id) {
+                self.deny.push(id);
+            }
+        }
+        if other.fail_on_unknown {
+            self.fail_on_unknown = true;
+        }
+    }
+}
+
+fn default_archive_max_depth() -> u32 {
+    3
+}
+
+fn default_archive_max_archive_bytes() -> u64 {
+    500 * 1024 * 1024 // 500MB
+}
+
+fn default_archive_max_entry_bytes() -> u64 {
+    200 * 1024 * 1024 // 200MB
+}
+
+fn default_archive_max_total_bytes() -> u64 {
+    1024 * 1024 * 1024 // 1GB
+}
+
+fn default_archive_max_entries() -> usize {
+    10_000
+}
+
+fn default_entropy_min_length() -> usize {
+    20
+}
+
+fn default_base64_threshold() -> f64 {
+    4.5
+}
+
+fn default_hex_threshold() -> f64 {
+    3.0
+}
+
+fn default_alphanumeric_threshold() -> f64 {
+    4.0
+}
+
+fn default_entropy_keywords() -> Vec<String> {
+    [
+        "key",
+        "secret",
+        "token",
+        "password",
+        "passwd",
+        "pwd",
+        "auth",
+        "credential",
+        "api",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn default_keyword_boost() -> f64 {
+    0.5
+}
+
 #[derive(Debug, Clone)]
 pub struct RuleConfig {
     pub enabled: bool,
@@ -405,6 +728,129 @@ mod tests {
         assert_eq!(config.core.max_file_count, None);
     }
 
+    #[test]
+    fn entropy_section_defaults_to_disabled_and_is_not_serialized() {
+        let config = Config::default();
+        assert!(!config.entropy.enabled);
+
+        let saved = toml::to_string(&config).unwrap();
+        assert!(!saved.contains("[entropy]"));
+    }
+
+    #[test]
+    fn verification_is_opt_in_and_merges_endpoints() {
+        let mut base = Config::default();
+        assert_eq!(base.verification.enabled, None);
+        assert!(!toml::to_string(&base).unwrap().contains("[verification]"));
+
+        let other: Config = toml::from_str(
+            r#"
+[verification]
+enabled = true
+
+[verification.endpoints]
+github = "http://127.0.0.1:9000"
+"#,
+        )
+        .unwrap();
+        base.merge(other);
+
+        assert_eq!(base.verification.enabled, Some(true));
+        assert_eq!(base.verification.timeout_ms, 5000);
+        assert_eq!(
+            base.verification
+                .endpoints
+                .get("github")
+                .map(String::as_str),
+            Some("http://127.0.0.1:9000")
+        );
+
+        let later: Config = toml::from_str("[verification]\nenabled = false\n").unwrap();
+        base.merge(later);
+        assert!(base.verification.is_disabled());
+
+        base.merge(Config::default());
+        assert!(base.verification.is_disabled());
+    }
+
+    #[test]
+    fn archive_scanning_is_opt_in_and_merges_limits() {
+        let mut base = Config::default();
+        assert!(!base.archive.enabled);
+        assert!(!toml::to_string(&base).unwrap().contains("[archive]"));
+
+        let other: Config = toml::from_str(
+            r#"
+[archive]
+enabled = true
+max_depth = 1
+"#,
+        )
+        .unwrap();
+        base.merge(other);
+
+        assert!(base.archive.enabled);
+        assert_eq!(base.archive.max_depth, 1);
+        assert_eq!(base.archive.max_entries, 10_000);
+        assert_eq!(base.archive.max_entry_bytes, 200 * 1024 * 1024);
+    }
+
+    #[test]
+    fn guardian_license_policy_merges_lists() {
+        let mut base: Config = toml::from_str(
+            r#"
+[guardian.licenses]
+allow = ["MIT", "Apache-2.0"]
+"#,
+        )
+        .unwrap();
+        assert!(!toml::to_string(&Config::default())
+            .unwrap()
+            .contains("[guardian"));
+
+        let other: Config = toml::from_str(
+            r#"
+[guardian.licenses]
+allow = ["MIT", "ISC"]
+deny = ["GPL-3.0-only"]
+fail_on_unknown = true
+"#,
+        )
+        .unwrap();
+        base.merge(other);
+
+        let licenses = &base.guardian.licenses;
+        assert_eq!(licenses.allow, vec!["MIT", "Apache-2.0", "ISC"]);
+        assert_eq!(licenses.deny, vec!["GPL-3.0-only"]);
+        assert!(licenses.fail_on_unknown);
+    }
+
+    #[test]
+    fn merge_enables_entropy_and_keeps_lower_layer_thresholds() {
+        let mut base: Config = toml::from_str(
+            r#"
+[entropy]
+hex_threshold = 3.5
+allowlist = ["^build-"]
+"#,
+        )
+        .unwrap();
+        let other: Config = toml::from_str(
+            r#"
+[entropy]
+enabled = true
+allowlist = ["^fixture-"]
+"#,
+        )
+        .unwrap();
+
+        base.merge(other);
+
+        assert!(base.entropy.enabled);
+        assert_eq!(base.entropy.hex_threshold, 3.5);
+        assert_eq!(base.entropy.allowlist, vec!["^build-", "^fixture-"]);
+    }
+
     #[test]
     fn merge_copies_non_default_max_findings() {
         let mut base = Config::default();
@@ -3,7 +3,10 @@ pub mod loader;
 pub mod presets;
 pub mod validate;
 
-pub use config::{Config, MaskMode, OutputConfig, RuleConfig};
+pub use config::{
+    ArchiveConfig, Config, EntropyConfig, GuardianConfig, LicensePolicyConfig, MaskMode,
+    OutputConfig, RuleConfig, VerificationConfig,
+};
 pub use loader::load_config;
 pub use presets::{
     apply_builtin_preset_as_base, builtin_preset_config, BUILTIN_PRESET_IDS,
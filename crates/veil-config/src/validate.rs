@@ -27,6 +27,69 @@ pub fn validate_config(config: &Config) -> Result<()> {
         }
     }
 
+    for (field, patterns) in [
+        ("core.ignore", &config.core.ignore),
+        ("core.include", &config.core.include),
+    ] {
+        for pattern in patterns {
+            if let Err(e) = check_path_pattern(pattern) {
+                bail!("Invalid glob in '{}': {:?}: {}", field, pattern, e);
+            }
+        }
+    }
+
+    if config.entropy.min_length < 8 {
+        bail!("Invalid config field 'entropy.min_length': must be at least 8");
+    }
+    for pattern in &config.entropy.allowlist {
+        if let Err(e) = regex::Regex::new(pattern) {
+            bail!("Invalid regex in 'entropy.allowlist': {}", e);
+        }
+    }
+
+    if config.verification.timeout_ms == 0 || config.verification.timeout_ms > 60_000 {
+        bail!("Invalid config field 'verification.timeout_ms': must be between 1 and 60000");
+    }
+    for (id, endpoint) in &config.verification.endpoints {
+        if !is_allowed_verification_endpoint(endpoint) {
+            bail!(
+                "Invalid endpoint for verifier '{}': must use https:// (http:// is only allowed for localhost)",
+                id
+            );
+        }
+    }
+
+    let archive = &config.archive;
+    if archive.max_depth == 0 {
+        bail!("Invalid config field 'archive.max_depth': must be at least 1");
+    }
+    if archive.max_archive_bytes == 0
+        || archive.max_entry_bytes == 0
+        || archive.max_total_bytes == 0
+        || archive.max_entries == 0
+    {
+        bail!("Invalid config field 'archive': size and entry limits must be greater than 0");
+    }
+
+    let licenses = &config.guardian.licenses;
+    for id in licenses.allow.iter().chain(&licenses.deny) {
+        if id.trim().is_empty() {
+            bail!(
+                "Invalid config field 'guardian.licenses': license identifiers must not be empty"
+            );
+        }
+    }
+    if let Some(id) = licenses
+        .allow
+        .iter()
+        .find(|id| licenses.deny.iter().any(|d| d.eq_ignore_ascii_case(id)))
+    {
+        bail!(
+            "Invalid config field 'guardian.licenses': '{}' is both allowed and denied",
+            id
+        );
+    }
+
     for (id, rule) in &config.rules {
         if let Some(pattern) = &rule.pattern {
             if pattern.is_empty() {
@@ -59,6 +122,40 @@ pub fn validate_config(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// `core.ignore`/`core.include` use gitignore syntax; the scanner compiles
+/// them the same way, so a pattern that passes here cannot fail at scan time.
+fn check_path_pattern(pattern: &str) -> Result<(), globset::Error> {
+    let mut glob = pattern.trim_end();
+    if glob.starts_with('#') {
+        return Ok(());
+    }
+    glob = glob.strip_prefix('!').unwrap_or(glob);
+    glob = glob.strip_prefix('/').unwrap_or(glob);
+    glob = glob.strip_suffix('/').unwrap_or(glob);
+    globset::GlobBuilder::new(glob)
+        .literal_separator(true)
+        .backslash_escape(true)
+        .build()
+        .map(|_| ())
+}
+
+/// Verification sends live secrets, so plaintext HTTP is only accepted for
+/// local mock servers.
+pub fn is_allowed_verification_endpoint(endpoint: &str) -> bool {
+    if endpoint.starts_with("https://") {
+        return true;
+    }
+    let Some(rest) = endpoint.strip_prefix("http://") else {
+        return false;
+    };
+    let host = rest.split(['/', '?']).next().unwrap_or_default();
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if !name.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => host,
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +236,51 @@ mod tests {
             .to_string()
             .contains("must be between 1 and 1,000,000"));
     }
+
+    #[test]
+    fn test_verification_endpoints_require_https_or_localhost() {
+        let mut config = Config::default();
+        config
+            .verification
+            .endpoints
+            .insert("github".to_string(), "http://127.0.0.1:8080".to_string());
+        assert!(validate_config(&config).is_ok());
+
+        config
+            .verification
+            .endpoints
+            .insert("slack".to_string(), "http://example.com".to_string());
+        assert!(validate_config(&config)
+            .unwrap_err()
+            .to_string()
+            .contains("verifier 'slack'"));
+    }
+
+    #[test]
+    fn test_license_policy_rejects_conflicting_lists() {
+        let mut config = Config::default();
+        config.guardian.licenses.allow = vec!["MIT".into(), "GPL-3.0-only".into()];
+        config.guardian.licenses.deny = vec!["AGPL-3.0-only".into()];
+        assert!(validate_config(&config).is_ok());
+
+        config.guardian.licenses.deny.push("gpl-3.0-only".into());
+        assert!(validate_config(&config)
+            .unwrap_err()
+            .to_string()
+            .contains("'GPL-3.0-only' is both allowed and denied"));
+    }
+
+    #[test]
+    fn test_path_patterns_must_be_valid_globs() {
+        let mut config = Config::default();
+        config.core.ignore = vec!["**/fixtures/".into(), "!keep.env".into()];
+        config.core.include = vec![".".into(), "/src".into()];
+        assert!(validate_config(&config).is_ok());
+
+        config.core.ignore.push("secrets[".into());
+        assert!(validate_config(&config)
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid glob in 'core.ignore'"));
+    }
 }
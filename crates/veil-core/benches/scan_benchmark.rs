@@ -3,7 +3,8 @@ use regex::Regex;
 use std::path::PathBuf;
 use veil_config::Config;
 use veil_core::model::Rule;
-use veil_core::scanner::{scan_content, utils::scan_data};
+use veil_core::scanner::{scan_content, scan_content_with_matcher, utils::scan_data};
+use veil_core::RuleMatcher;
 
 fn main() {
     divan::main();
@@ -30,6 +31,9 @@ fn get_rules() -> Vec<Rule> {
             context_lines_before: 2,
             context_lines_after: 0,
             placeholder: None,
+            multiline: false,
+            keywords: Vec::new(),
+            remediation: None,
         },
         Rule {
             id: "Generic_API_Key".to_string(),
@@ -49,6 +53,9 @@ fn get_rules() -> Vec<Rule> {
             context_lines_before: 2,
             context_lines_after: 0,
             placeholder: None,
+            multiline: false,
+            keywords: Vec::new(),
+            remediation: None,
         },
     ]
 }
@@ -57,6 +64,32 @@ fn get_config() -> Config {
     Config::default()
 }
 
+/// Built-in rules plus the 1000 JP security templates.
+fn get_large_ruleset() -> Vec<Rule> {
+    let templates = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../veil/rules_ja/templates/jp_security_templates_1000");
+    let mut rules = veil_core::get_default_rules();
+    rules.extend(veil_core::rules::pack::load_rule_templates_parallel(&templates).unwrap());
+    rules
+}
+
+/// ~400KB of mostly benign source-like lines with a few secrets and JP PII.
+fn get_mixed_content() -> String {
+    let lines = [
+        "fn handle(req: Request) -> Response { log::info!(\"processing\"); }\n",
+        "const TIMEOUT_MS: u64 = 5000; // keep in sync with the gateway\n",
+        "  - name: build\n    run: cargo build --release\n",
+        "お問い合わせありがとうございます。担当者より折り返しご連絡します。\n",
+    ];
+    let mut content = String::with_capacity(400 * 1024);
+    for i in 0..6000 {
+        content.push_str(lines[i % lines.len()]);
+    }
+    content.push_str("aws_access_key_id = AKIA1234567890ABCDEF\n");
+    content.push_str("電話番号: 090-1234-5678\n");
+    content
+}
+
 // Benchmarks
 
 #[divan::bench]
@@ -111,6 +144,44 @@ fn scan_content_large(bencher: Bencher) {
     bencher.bench(|| scan_content(divan::black_box(&content), &path, &rules, &config));
 }
 
+#[divan::bench(sample_count = 10)]
+fn large_ruleset_build_matcher(bencher: Bencher) {
+    let rules = get_large_ruleset();
+    let config = get_config();
+
+    bencher.bench(|| RuleMatcher::new(divan::black_box(&rules), &config));
+}
+
+#[divan::bench(sample_count = 10)]
+fn large_ruleset_prefiltered(bencher: Bencher) {
+    let rules = get_large_ruleset();
+    assert!(rules.len() >= 1000);
+    let config = get_config();
+    let matcher = RuleMatcher::new(&rules, &config);
+    let content = get_mixed_content();
+    let path = PathBuf::from("mixed.txt");
+
+    bencher
+        .bench(|| scan_content_with_matcher(divan::black_box(&content), &path, &matcher, &config));
+}
+
+/// The pre-prefilter behaviour: every enabled rule's regex on every line.
+#[divan::bench(sample_count = 10)]
+fn large_ruleset_every_rule_every_line(bencher: Bencher) {
+    let rules = get_large_ruleset();
+    let content = get_mixed_content();
+
+    bencher.bench(|| {
+        let mut matches = 0usize;
+        for line in divan::black_box(&content).lines() {
+            for rule in rules.iter().filter(|rule| rule.enabled && !rule.multiline) {
+                matches += rule.pattern.find_iter(line).count();
+            }
+        }
+        matches
+    });
+}
+
 #[divan::bench]
 fn apply_masks_stress(bencher: Bencher) {
     use veil_config::MaskMode;
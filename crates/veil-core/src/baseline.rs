@@ -151,6 +151,7 @@ mod tests {
         Finding {
             path: path.into(),
             line_number: line,
+            end_line_number: None,
             line_content: format!("{} = {}", "key", secret),
             rule_id: "test.rule".into(),
             matched_content: secret.into(),
@@ -165,6 +166,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         }
     }
 
@@ -205,6 +207,7 @@ mod tests {
         let f = Finding {
             path: "src/config.py".into(),
             line_number: 42,
+            end_line_number: None,
             line_content: "aws_key = AKIA123...".into(),
             rule_id: "creds.aws.access_key".into(),
             matched_content: "AKIA123...".into(),
@@ -219,6 +222,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         };
 
         let fp1 = generate_fingerprint(&f);
@@ -232,6 +236,7 @@ mod tests {
         let f = Finding {
             path: "src/config.py".into(),
             line_number: 42,
+            end_line_number: None,
             line_content: "aws_key = AKIA123...".into(),
             rule_id: "creds.aws.access_key".into(),
             matched_content: "AKIA123...".into(),
@@ -246,6 +251,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         };
 
         let snapshot = from_findings(&[f], "0.9.1-test");
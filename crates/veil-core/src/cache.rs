@@ -0,0 +1,420 @@
+//! On-disk cache of per-file scan results.
+//!
+//! Entries are keyed by the file path, a blake3 hash of its content, the
+//! ruleset digest and the config digest (see `summary::v1`), so any change to
+//! the file, the rules or the settings that shape findings is a cache miss.
+//!
+//! Raw secrets never reach the cache: `line_content`, `matched_content` and
+//! the context lines are dropped before writing and rebuilt from the file on a
+//! hit, and scans in plain mask mode bypass the cache entirely.
+//!
+//! Each edit to a file leaves its old entry behind, so a hit refreshes the
+//! entry's mtime and `open` removes entries untouched for `ENTRY_TTL` (at
+//! most once per `PRUNE_INTERVAL`). `clear` empties the cache on request.
+
+use crate::model::{Finding, FindingSpan, Range, Rule};
+use crate::summary::v1::{config_digest, ruleset_digest};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use veil_config::{Config, MaskMode};
+
+/// Bump when the entry layout or scanner semantics change.
+const CACHE_FORMAT: u32 = 2;
+
+/// Entries neither read nor written for this long are evicted.
+const ENTRY_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Minimum time between two eviction passes over the cache directory.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Marker whose mtime records the last eviction pass.
+const PRUNE_MARKER: &str = ".last-prune";
+
+pub struct ScanCache {
+    dir: PathBuf,
+    ruleset_digest: String,
+    config_digest: String,
+    max_file_size: u64,
+}
+
+/// A looked-up file: its cache key plus the content that was hashed, kept so a
+/// hit can be re-hydrated without reading the file again.
+pub struct CacheProbe {
+    key: String,
+    content: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedFinding {
+    finding: Finding,
+    span: FindingSpan,
+    utf16_range: Range,
+    context_before_len: usize,
+}
+
+impl ScanCache {
+    /// Opens (creating if needed) the cache in `custom_dir` or the platform
+    /// cache directory. Returns `None` when the cache cannot be used.
+    pub fn open(custom_dir: Option<PathBuf>, rules: &[Rule], config: &Config) -> Option<Self> {
+        if config.output.mask_mode == Some(MaskMode::Plain) {
+            return None;
+        }
+
+        let dir = match custom_dir {
+            Some(dir) => dir,
+            None => Self::default_dir()?,
+        };
+        fs::create_dir_all(&dir).ok()?;
+        prune_if_due(&dir);
+
+        Some(Self {
+            dir,
+            ruleset_digest: ruleset_digest(rules),
+            config_digest: config_digest(&per_file_settings(config)),
+            max_file_size: config
+                .core
+                .max_file_size
+                .unwrap_or(crate::DEFAULT_MAX_FILE_SIZE_BYTES),
+        })
+    }
+
+    /// `~/.cache/veil/scan` or the platform equivalent.
+    pub fn default_dir() -> Option<PathBuf> {
+        let proj_dirs = ProjectDirs::from("com", "veil-rs", "veil")?;
+        Some(proj_dirs.cache_dir().join("scan"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes every entry (and leftover temp file) from the cache in `dir`.
+    /// Returns the number of entries removed; a missing directory is empty.
+    pub fn clear(dir: &Path) -> io::Result<usize> {
+        let mut removed = 0;
+        for path in cache_files(dir)? {
+            fs::remove_file(&path)?;
+            if is_entry(&path) {
+                removed += 1;
+            }
+        }
+        match fs::remove_file(dir.join(PRUNE_MARKER)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        Ok(removed)
+    }
+
+    /// Reads and hashes `path`. Returns `None` for files the scanner would
+    /// skip (too large, unreadable, binary or not UTF-8); those are always
+    /// scanned normally so their skip reasons are reported.
+    pub fn probe(&self, path: &Path) -> Option<CacheProbe> {
+        if fs::metadata(path).ok()?.len() > self.max_file_size {
+            return None;
+        }
+        let bytes = fs::read(path).ok()?;
+        if bytes[..bytes.len().min(8192)].contains(&0) {
+            return None;
+        }
+        let content = String::from_utf8(bytes).ok()?;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&CACHE_FORMAT.to_le_bytes());
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update(&[0]);
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(&[0]);
+        hasher.update(blake3::hash(content.as_bytes()).as_bytes());
+        hasher.update(self.ruleset_digest.as_bytes());
+        hasher.update(self.config_digest.as_bytes());
+
+        Some(CacheProbe {
+            key: hasher.finalize().to_hex().to_string(),
+            content,
+        })
+    }
+
+    pub fn get(&self, probe: &CacheProbe) -> Option<Vec<Finding>> {
+        let path = self.path_for(&probe.key);
+        let raw = fs::read_to_string(&path).ok()?;
+        let entries: Vec<CachedFinding> = serde_json::from_str(&raw).ok()?;
+        let lines: Vec<&str> = probe.content.lines().collect();
+        let findings = entries
+            .into_iter()
+            .map(|entry| rehydrate(entry, &lines))
+            .collect::<Option<Vec<_>>>()?;
+        // Keep entries in use from being evicted; failing to touch is harmless.
+        let _ = fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        Some(findings)
+    }
+
+    pub fn put(&self, probe: &CacheProbe, findings: &[Finding]) -> io::Result<()> {
+        let entries: Vec<CachedFinding> = findings
+            .iter()
+            .map(|finding| CachedFinding {
+                finding: Finding {
+                    line_content: String::new(),
+                    matched_content: String::new(),
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    ..finding.clone()
+                },
+                span: finding.span,
+                utf16_range: finding.utf16_range,
+                context_before_len: finding.context_before.len(),
+            })
+            .collect();
+        let json = serde_json::to_string(&entries).map_err(io::Error::other)?;
+
+        // Atomic write; concurrent writers of the same key write identical data.
+        let path = self.path_for(&probe.key);
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&temp_path, json)?;
+        fs::rename(temp_path, path)
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Runs `prune` unless the last pass was less than `PRUNE_INTERVAL` ago.
+/// Eviction is best effort: errors leave the cache as it was.
+fn prune_if_due(dir: &Path) {
+    let marker = dir.join(PRUNE_MARKER);
+    let now = SystemTime::now();
+    let last = fs::metadata(&marker).and_then(|meta| meta.modified());
+    if last.is_ok_and(|last| {
+        now.duration_since(last)
+            .is_ok_and(|age| age < PRUNE_INTERVAL)
+    }) {
+        return;
+    }
+    if fs::write(&marker, b"").is_ok() {
+        let _ = prune(dir, now.checked_sub(ENTRY_TTL).unwrap_or(now));
+    }
+}
+
+/// Removes cache files last modified before `cutoff`. Returns the number of
+/// entries removed.
+fn prune(dir: &Path, cutoff: SystemTime) -> io::Result<usize> {
+    let mut removed = 0;
+    for path in cache_files(dir)? {
+        let modified = fs::metadata(&path).and_then(|meta| meta.modified());
+        if modified.is_ok_and(|modified| modified < cutoff)
+            && fs::remove_file(&path).is_ok()
+            && is_entry(&path)
+        {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Entries (`<key>.json`) and temp files (`<key>.<pid>.tmp`) in `dir`. Other
+/// files are left alone, since `--cache-dir` may point anywhere.
+fn cache_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut files = Vec::new();
+    for entry in read_dir {
+        let path = entry?.path();
+        let is_temp = path.extension().is_some_and(|ext| ext == "tmp");
+        if path.is_file() && (is_entry(&path) || is_temp) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn is_entry(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// Settings that only shape the walk or the run (ignores, file-count and
+/// findings limits) do not change per-file results, so they are left out of
+/// the cache key.
+fn per_file_settings(config: &Config) -> Config {
+    let mut config = config.clone();
+    config.core.include.clear();
+    config.core.ignore.clear();
+    config.core.max_file_count = None;
+    config.core.fail_on_score = None;
+    config.output.max_findings = None;
+    config.output.max_findings_is_set = false;
+    config
+}
+
+fn rehydrate(entry: CachedFinding, lines: &[&str]) -> Option<Finding> {
+    let mut finding = entry.finding;
+    let start = finding.line_number.checked_sub(1)?;
+    let end = finding.end_line_number.unwrap_or(finding.line_number) - 1;
+    let block = lines.get(start..=end)?.join("\n");
+    let end_line_offset = block.len() - lines[end].len();
+
+    finding.matched_content = block
+        .get(entry.span.byte_start..end_line_offset + entry.span.byte_end)?
+        .to_string();
+    finding.line_content = block;
+    finding.context_before = lines[start.saturating_sub(entry.context_before_len)..start]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+    finding.span = entry.span;
+    finding.utf16_range = entry.utf16_range;
+    Some(finding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_restores_findings_without_storing_secrets() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let work_dir = tempfile::tempdir().unwrap();
+        let path = work_dir.path().join("app.env");
+        let secret = "AKIA1234567890ABCDEF";
+        fs::write(&path, format!("# aws\nAWS_KEY={secret}\n")).unwrap();
+
+        let rules = crate::get_default_rules();
+        let config = Config::default();
+        let cache = ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules, &config).unwrap();
+        let findings = crate::scan_file(&path, &rules, &config, None);
+        assert!(!findings.is_empty());
+
+        let probe = cache.probe(&path).unwrap();
+        assert!(cache.get(&probe).is_none());
+        cache.put(&probe, &findings).unwrap();
+
+        for entry in fs::read_dir(cache_dir.path()).unwrap() {
+            let stored = fs::read_to_string(entry.unwrap().path()).unwrap();
+            assert!(!stored.contains(secret), "raw secret written to cache");
+        }
+
+        let cached = cache.get(&cache.probe(&path).unwrap()).unwrap();
+        assert_eq!(cached.len(), findings.len());
+        for (hit, original) in cached.iter().zip(&findings) {
+            assert_eq!(hit.rule_id, original.rule_id);
+            assert_eq!(hit.matched_content, original.matched_content);
+            assert_eq!(hit.line_content, original.line_content);
+            assert_eq!(hit.context_before, original.context_before);
+            assert_eq!(hit.span, original.span);
+            assert_eq!(hit.masked_snippet, original.masked_snippet);
+            assert_eq!(hit.score, original.score);
+        }
+    }
+
+    #[test]
+    fn content_rules_and_config_changes_miss() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let work_dir = tempfile::tempdir().unwrap();
+        let path = work_dir.path().join("notes.txt");
+        fs::write(&path, "nothing here\n").unwrap();
+
+        let rules = crate::get_default_rules();
+        let config = Config::default();
+        let cache = ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules, &config).unwrap();
+        cache.put(&cache.probe(&path).unwrap(), &[]).unwrap();
+        assert!(cache.get(&cache.probe(&path).unwrap()).unwrap().is_empty());
+
+        fs::write(&path, "nothing here either\n").unwrap();
+        assert!(cache.get(&cache.probe(&path).unwrap()).is_none());
+
+        let mut entropy_config = Config::default();
+        entropy_config.entropy.enabled = true;
+        let other = ScanCache::open(
+            Some(cache_dir.path().to_path_buf()),
+            &rules,
+            &entropy_config,
+        )
+        .unwrap();
+        fs::write(&path, "nothing here\n").unwrap();
+        assert!(other.get(&other.probe(&path).unwrap()).is_none());
+
+        let fewer_rules =
+            ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules[1..], &config).unwrap();
+        assert!(fewer_rules
+            .get(&fewer_rules.probe(&path).unwrap())
+            .is_none());
+    }
+
+    fn age(path: &Path, by: Duration) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - by)
+            .unwrap();
+    }
+
+    #[test]
+    fn open_evicts_entries_unused_for_the_ttl() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let work_dir = tempfile::tempdir().unwrap();
+        let rules = crate::get_default_rules();
+        let config = Config::default();
+        let cache = ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules, &config).unwrap();
+
+        let mut probes = Vec::new();
+        for name in ["stale.txt", "reused.txt", "fresh.txt"] {
+            let path = work_dir.path().join(name);
+            fs::write(&path, format!("{name}\n")).unwrap();
+            let probe = cache.probe(&path).unwrap();
+            cache.put(&probe, &[]).unwrap();
+            probes.push(probe);
+        }
+        let old = ENTRY_TTL + Duration::from_secs(60);
+        age(&cache.path_for(&probes[0].key), old);
+        age(&cache.path_for(&probes[1].key), old);
+        // A hit counts as a use.
+        assert!(cache.get(&probes[1]).is_some());
+
+        // The pass that ran when the cache was first opened is recent.
+        ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules, &config).unwrap();
+        assert!(cache.path_for(&probes[0].key).exists());
+
+        age(&cache_dir.path().join(PRUNE_MARKER), PRUNE_INTERVAL);
+        ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules, &config).unwrap();
+        assert!(cache.get(&probes[0]).is_none());
+        assert!(cache.get(&probes[1]).is_some());
+        assert!(cache.get(&probes[2]).is_some());
+    }
+
+    #[test]
+    fn clear_removes_only_cache_files() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let work_dir = tempfile::tempdir().unwrap();
+        let path = work_dir.path().join("notes.txt");
+        fs::write(&path, "nothing here\n").unwrap();
+        fs::write(cache_dir.path().join("README"), "keep me").unwrap();
+
+        let rules = crate::get_default_rules();
+        let config = Config::default();
+        let cache = ScanCache::open(Some(cache_dir.path().to_path_buf()), &rules, &config).unwrap();
+        let probe = cache.probe(&path).unwrap();
+        cache.put(&probe, &[]).unwrap();
+        fs::write(cache_dir.path().join("abc.123.tmp"), "").unwrap();
+
+        assert_eq!(ScanCache::clear(cache_dir.path()).unwrap(), 1);
+        assert!(cache.get(&probe).is_none());
+        let left: Vec<_> = fs::read_dir(cache_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(left, ["README"]);
+
+        let missing = cache_dir.path().join("missing");
+        assert_eq!(ScanCache::clear(&missing).unwrap(), 0);
+    }
+}
@@ -0,0 +1,223 @@
+//! Rule explanations and remediation guidance, rendered the same way for
+//! `veil rules explain`, the LSP hover and the report formatters.
+//!
+//! Renderers return the body only; each surface adds its own heading
+//! (`Remediation:`, `### rule.id`, an HTML card, ...).
+
+use crate::model::{Remediation, Rule};
+
+/// Shown when a leaked secret must also be purged from git history.
+pub const HISTORY_REWRITE_NOTE: &str =
+    "History rewrite required: purge the secret from git history after rotating it.";
+
+/// Output format of the renderers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    Text,
+    Markdown,
+    Html,
+}
+
+/// What a rule detects and how findings are scored, without its remediation.
+pub fn explain_rule(rule: &Rule, markup: Markup) -> String {
+    let tags = rule.tags.join(", ");
+    let context = format!(
+        "{} lines before / {} lines after",
+        rule.context_lines_before, rule.context_lines_after
+    );
+    match markup {
+        Markup::Text => {
+            let mut lines = vec![
+                format!("ID:          {}", rule.id),
+                format!("Description: {}", rule.description),
+                format!("Severity:    {}", rule.severity),
+                format!("Score:       {}", rule.score),
+                format!("Category:    {}", rule.category),
+            ];
+            if !rule.tags.is_empty() {
+                lines.push(format!("Tags:        {tags}"));
+            }
+            lines.push(format!("Pattern:     {}", rule.pattern.as_str()));
+            lines.push(format!("Context:     {context}"));
+            lines.join("\n")
+        }
+        Markup::Markdown => {
+            let mut lines = vec![
+                format!("**{}**", rule.id),
+                String::new(),
+                rule.description.clone(),
+                String::new(),
+                format!("- Severity: {}", rule.severity),
+                format!("- Score: {}", rule.score),
+                format!("- Category: {}", rule.category),
+            ];
+            if !rule.tags.is_empty() {
+                lines.push(format!("- Tags: {tags}"));
+            }
+            lines.push(format!("- Pattern: `` {} ``", rule.pattern.as_str()));
+            lines.push(format!("- Context: {context}"));
+            lines.join("\n")
+        }
+        Markup::Html => {
+            let mut out = format!(
+                r#"<h3 class="mono">{}</h3><p>{}</p>"#,
+                escape_html(&rule.id),
+                escape_html(&rule.description)
+            );
+            let mut fields = vec![
+                ("Severity", rule.severity.to_string()),
+                ("Score", rule.score.to_string()),
+                ("Category", rule.category.clone()),
+            ];
+            if !rule.tags.is_empty() {
+                fields.push(("Tags", tags));
+            }
+            fields.push(("Pattern", rule.pattern.as_str().to_string()));
+            fields.push(("Context", context));
+            for (label, value) in fields {
+                out.push_str(&meta_html(label, &escape_html(&value)));
+            }
+            out
+        }
+    }
+}
+
+/// How to revoke and rotate a leaked secret.
+pub fn render_remediation(remediation: &Remediation, markup: Markup) -> String {
+    match markup {
+        Markup::Text => {
+            let mut lines = Vec::new();
+            if let Some(provider) = &remediation.provider {
+                lines.push(format!("Provider: {provider}"));
+            }
+            if let Some(url) = &remediation.revoke_url {
+                lines.push(format!("Revoke:   {url}"));
+            }
+            for (index, step) in remediation.rotation_steps.iter().enumerate() {
+                lines.push(format!("{}. {}", index + 1, step));
+            }
+            if remediation.history_rewrite {
+                lines.push(HISTORY_REWRITE_NOTE.to_string());
+            }
+            lines.join("\n")
+        }
+        Markup::Markdown => {
+            let mut lines = Vec::new();
+            if let Some(provider) = &remediation.provider {
+                lines.push(format!("- **Provider**: {provider}"));
+            }
+            if let Some(url) = &remediation.revoke_url {
+                lines.push(format!("- **Revoke**: <{url}>"));
+            }
+            if remediation.history_rewrite {
+                lines.push(format!("- **{HISTORY_REWRITE_NOTE}**"));
+            }
+            if !remediation.rotation_steps.is_empty() {
+                if !lines.is_empty() {
+                    lines.push(String::new());
+                }
+                for (index, step) in remediation.rotation_steps.iter().enumerate() {
+                    lines.push(format!("{}. {}", index + 1, step));
+                }
+            }
+            lines.join("\n")
+        }
+        Markup::Html => {
+            let mut out = String::new();
+            if let Some(provider) = &remediation.provider {
+                out.push_str(&meta_html("Provider", &escape_html(provider)));
+            }
+            if let Some(url) = &remediation.revoke_url {
+                out.push_str(&meta_html(
+                    "Revoke",
+                    &format!(
+                        r#"<a href="{0}" rel="noopener noreferrer">{0}</a>"#,
+                        escape_html(url)
+                    ),
+                ));
+            }
+            if !remediation.rotation_steps.is_empty() {
+                let steps: String = remediation
+                    .rotation_steps
+                    .iter()
+                    .map(|step| format!("<li>{}</li>", escape_html(step)))
+                    .collect();
+                out.push_str(&format!("<ol>{steps}</ol>"));
+            }
+            if remediation.history_rewrite {
+                out.push_str(&format!(
+                    r#"<div class="history-rewrite">{HISTORY_REWRITE_NOTE}</div>"#
+                ));
+            }
+            out
+        }
+    }
+}
+
+fn meta_html(label: &str, value_html: &str) -> String {
+    format!(r#"<div><span class="meta-label">{label}:</span>{value_html}</div>"#)
+}
+
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remediation() -> Remediation {
+        Remediation {
+            provider: Some("Example".to_string()),
+            revoke_url: Some("https://example.com/revoke?a=1&b=2".to_string()),
+            rotation_steps: vec!["Issue a <new> token".to_string()],
+            history_rewrite: true,
+        }
+    }
+
+    #[test]
+    fn remediation_renders_every_field_in_each_markup() {
+        let remediation = remediation();
+
+        assert_eq!(
+            render_remediation(&remediation, Markup::Text),
+            format!(
+                "Provider: Example\nRevoke:   https://example.com/revoke?a=1&b=2\n1. Issue a <new> token\n{HISTORY_REWRITE_NOTE}"
+            )
+        );
+        assert_eq!(
+            render_remediation(&remediation, Markup::Markdown),
+            format!(
+                "- **Provider**: Example\n- **Revoke**: <https://example.com/revoke?a=1&b=2>\n- **{HISTORY_REWRITE_NOTE}**\n\n1. Issue a <new> token"
+            )
+        );
+        let html = render_remediation(&remediation, Markup::Html);
+        assert!(html.contains(r#"<a href="https://example.com/revoke?a=1&amp;b=2""#));
+        assert!(html.contains("<ol><li>Issue a &lt;new&gt; token</li></ol>"));
+        assert!(html.contains(HISTORY_REWRITE_NOTE));
+    }
+
+    #[test]
+    fn explanation_lists_rule_metadata() {
+        let rule = crate::get_default_rules()
+            .into_iter()
+            .find(|rule| rule.id == "creds.aws.access_key_id")
+            .expect("builtin AWS rule");
+
+        let text = explain_rule(&rule, Markup::Text);
+        assert!(text.starts_with("ID:          creds.aws.access_key_id\n"));
+        assert!(text.contains(&format!("Pattern:     {}", rule.pattern.as_str())));
+
+        let markdown = explain_rule(&rule, Markup::Markdown);
+        assert!(markdown.starts_with("**creds.aws.access_key_id**\n\n"));
+        assert!(markdown.contains(&format!("- Score: {}", rule.score)));
+
+        let html = explain_rule(&rule, Markup::Html);
+        assert!(html.contains(r#"<span class="meta-label">Category:</span>"#));
+    }
+}
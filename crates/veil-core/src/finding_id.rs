@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 
 use std::str::FromStr;
 
+use crate::model::Finding;
+use crate::scanner::path_filter::relative_to;
+
 /// A deterministic identifier for a Finding.
 ///
 /// FindingId is generated from a set of stable properties:
@@ -71,6 +74,24 @@ impl FindingId {
 
         Self(*hasher.finalize().as_bytes())
     }
+
+    /// The id of a scanner finding, shared by every surface (LSP, Local API) so
+    /// that an exception recorded from one matches the others. Only public
+    /// parts feed it: rule, path relative to `root`, range and masked snippet.
+    pub fn for_finding(finding: &Finding, root: &Path) -> Self {
+        let range = finding.utf16_range;
+        Self::new(
+            &finding.rule_id,
+            &relative_to(&finding.path, root),
+            &SpanData {
+                start_line: u64::from(range.start.line),
+                start_col: u64::from(range.start.character),
+                end_line: u64::from(range.end.line),
+                end_col: u64::from(range.end.character),
+            },
+            &finding.masked_snippet,
+        )
+    }
 }
 
 impl fmt::Display for FindingId {
@@ -205,4 +226,36 @@ mod tests {
         let decoded: FindingId = serde_json::from_str(&json).unwrap();
         assert_eq!(id, decoded);
     }
+
+    #[test]
+    fn for_finding_ignores_raw_match_and_path_prefix() {
+        let mut finding = crate::model::Finding {
+            path: PathBuf::from("/repo/src/config.rs"),
+            line_number: 7,
+            end_line_number: None,
+            line_content: "token = secret-one".to_string(),
+            rule_id: "creds.test".to_string(),
+            matched_content: "secret-one".to_string(),
+            masked_snippet: "token = <REDACTED>".to_string(),
+            severity: crate::model::Severity::High,
+            score: 80,
+            grade: crate::rules::grade::Grade::High,
+            span: Default::default(),
+            utf16_range: Default::default(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            commit_sha: None,
+            author: None,
+            date: None,
+            verification: None,
+        };
+        let id = FindingId::for_finding(&finding, Path::new("/repo"));
+
+        finding.matched_content = "secret-two".to_string();
+        finding.path = PathBuf::from("./src/config.rs");
+        assert_eq!(FindingId::for_finding(&finding, Path::new("/repo")), id);
+
+        finding.utf16_range.start.character = 8;
+        assert_ne!(FindingId::for_finding(&finding, Path::new("/repo")), id);
+    }
 }
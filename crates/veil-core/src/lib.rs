@@ -1,5 +1,8 @@
 pub mod baseline;
+pub mod cache;
+pub mod explain;
 pub mod finding_id;
+pub mod history;
 pub mod masking;
 pub mod metrics;
 pub mod model;
@@ -8,22 +11,34 @@ pub mod remote;
 pub mod rules;
 pub mod scanner;
 pub mod scoring;
+pub mod staged;
 pub mod summary;
 pub mod validators;
+pub mod verifiers;
 pub mod verify;
 
+pub use crate::cache::ScanCache;
 pub use crate::masking::{apply_masks, apply_masks_spans, MaskSpan, DEFAULT_PLACEHOLDER};
 pub use finding_id::FindingId;
-pub use model::{Finding, FindingSpan, Position, Range, Rule, Severity};
+pub use history::{pr_range, scan_history, HistoryScan, HistoryScope};
+pub use model::{
+    Finding, FindingSpan, Position, Range, Remediation, Rule, Severity, VerificationStatus,
+};
 pub use registry::Registry;
 pub use rules::builtin::{get_all_rules, get_default_rules, try_get_all_rules};
 pub use rules::grade::{calculate_grade, Grade};
+pub use scanner::matcher::RuleMatcher;
 pub use scanner::result::ScanResult;
 pub use scanner::{
-    scan_content, scan_file, scan_path, utils::scan_data, DEFAULT_MAX_FILE_COUNT,
-    DEFAULT_MAX_FILE_SIZE_BYTES, RULE_ID_BINARY_FILE, RULE_ID_MAX_FILE_SIZE, RULE_ID_READ_ERROR,
+    path_filter::{PathFilter, PathVerdict},
+    scan_content, scan_content_with_matcher, scan_file, scan_file_with_matcher, scan_path,
+    scan_path_cancellable, scan_path_with_cache,
+    utils::{scan_data, scan_data_with_matcher},
+    DEFAULT_MAX_FILE_COUNT, DEFAULT_MAX_FILE_SIZE_BYTES, RULE_ID_BINARY_FILE,
+    RULE_ID_MAX_FILE_SIZE, RULE_ID_READ_ERROR,
 };
 pub use scoring::{calculate_score, grade_from_score, severity_min_score, ScoreParams};
+pub use staged::{scan_staged, StagedScan};
 pub use verify::{verify_evidence_pack, VerifyError, VerifyOptions, VerifyResult, VerifyStatus};
 
 // Placeholder for future remote rule fetching
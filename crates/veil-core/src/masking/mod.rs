@@ -1,3 +1,4 @@
+use crate::model::Finding;
 use veil_config::MaskMode;
 
 pub const DEFAULT_PLACEHOLDER: &str = "<REDACTED>";
@@ -150,6 +151,11 @@ pub fn apply_masks_spans(content: &str, mut spans: Vec<MaskSpan>, mode: MaskMode
         };
 
         result.push_str(&replacement);
+        // Multi-line spans keep their line breaks so rewritten files keep the
+        // same line numbering as the findings that produced them.
+        for _ in secret_chunk.matches('\n') {
+            result.push('\n');
+        }
         last_pos = range.end;
     }
 
@@ -160,6 +166,30 @@ pub fn apply_masks_spans(content: &str, mut spans: Vec<MaskSpan>, mode: MaskMode
     result
 }
 
+/// Byte offset at which each line of `content` starts, matching `str::lines`.
+pub fn line_offsets(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+/// Absolute byte range of a finding within the content it was scanned from.
+///
+/// `offsets` comes from [`line_offsets`]. Returns `None` when the finding does
+/// not fit the content (e.g. the file changed after the scan).
+pub fn finding_byte_range(
+    finding: &Finding,
+    offsets: &[usize],
+    content_len: usize,
+) -> Option<std::ops::Range<usize>> {
+    let start_line = offsets.get(finding.line_number.checked_sub(1)?)?;
+    let end_index = finding.end_line_number.unwrap_or(finding.line_number) - 1;
+    let end_line = offsets.get(end_index)?;
+    let start = start_line + finding.span.byte_start;
+    let end = end_line + finding.span.byte_end;
+    (start < end && end <= content_len).then_some(start..end)
+}
+
 // Deprecated or removed mask_string?
 // Scanner uses apply_masks now.
 
@@ -421,4 +451,54 @@ mod tests {
         let result = apply_masks_spans(text, spans, MaskMode::Partial);
         assert_eq!(result, "****３４５６");
     }
+
+    #[test]
+    fn test_mask_spans_keep_line_breaks_of_multiline_block() {
+        let text = "key:\n-----BEGIN\nabc\n-----END\nafter";
+        let start = text.find("-----BEGIN").unwrap();
+        let end = text.find("after").unwrap() - 1;
+        let spans = vec![MaskSpan {
+            start,
+            end,
+            placeholder: "<SECRET>".to_string(),
+            priority: 300,
+        }];
+
+        let result = apply_masks_spans(text, spans, MaskMode::Redact);
+        assert_eq!(result, "key:\n<SECRET>\n\n\nafter");
+        assert_eq!(result.lines().count(), text.lines().count());
+    }
+
+    #[test]
+    fn test_finding_byte_range_spans_lines() {
+        let text = "a\nxxBEGIN\nbody\nEND!\n";
+        let offsets = line_offsets(text);
+        assert_eq!(offsets, vec![0, 2, 10, 15, 20]);
+
+        let finding = Finding {
+            path: std::path::PathBuf::from("key.pem"),
+            line_number: 2,
+            end_line_number: Some(4),
+            line_content: String::new(),
+            rule_id: "test".to_string(),
+            matched_content: String::new(),
+            masked_snippet: String::new(),
+            severity: crate::model::Severity::High,
+            score: 0,
+            grade: crate::rules::grade::Grade::Safe,
+            span: crate::model::FindingSpan {
+                byte_start: 2,
+                byte_end: 3,
+            },
+            utf16_range: Default::default(),
+            context_before: vec![],
+            context_after: vec![],
+            commit_sha: None,
+            author: None,
+            date: None,
+            verification: None,
+        };
+        let range = finding_byte_range(&finding, &offsets, text.len()).unwrap();
+        assert_eq!(&text[range], "BEGIN\nbody\nEND");
+    }
 }
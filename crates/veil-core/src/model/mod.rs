@@ -35,6 +35,52 @@ impl From<&str> for Severity {
     }
 }
 
+/// Outcome of checking a candidate secret against its provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationStatus {
+    /// The provider accepted the credential: it is live.
+    Verified,
+    /// The check could not reach a conclusion (network error, rate limit, ...).
+    Unverified,
+    /// The provider rejected the credential (revoked, expired or fake).
+    Invalid,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationStatus::Verified => "verified",
+            VerificationStatus::Unverified => "unverified",
+            VerificationStatus::Invalid => "invalid",
+        }
+    }
+}
+
+impl fmt::Display for VerificationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What to do once a secret matched by a rule has leaked.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Remediation {
+    /// Service that issued the credential (e.g. "AWS", "GitHub").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Where the credential can be revoked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoke_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rotation_steps: Vec<String>,
+    /// Whether the secret must also be purged from git history (e.g. because
+    /// it cannot be revoked).
+    #[serde(default)]
+    pub history_rewrite: bool,
+}
+
 #[derive(Clone)]
 pub struct Rule {
     pub id: String,
@@ -59,8 +105,23 @@ pub struct Rule {
     pub validator: Option<fn(&str) -> bool>,
 
     pub placeholder: Option<String>,
+
+    /// Match against the whole file buffer instead of line by line, so a
+    /// pattern can span several lines (PEM blocks, wrapped JSON values).
+    pub multiline: bool,
+
+    /// Literals that must occur in a line for the rule to run there
+    /// (ASCII case-insensitive). Empty means the rule is prefiltered by the
+    /// combined `RegexSet` instead.
+    pub keywords: Vec<String>,
+
+    /// Revocation and rotation guidance shown alongside findings.
+    pub remediation: Option<Remediation>,
 }
 
+/// Byte offsets of a match. `byte_start` is relative to the finding's start
+/// line; `byte_end` is relative to its end line (the same line unless
+/// `Finding::end_line_number` is set).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default, Hash)]
 pub struct FindingSpan {
     pub byte_start: usize,
@@ -103,6 +164,9 @@ impl fmt::Debug for Rule {
                 },
             )
             .field("placeholder", &self.placeholder)
+            .field("multiline", &self.multiline)
+            .field("keywords", &self.keywords)
+            .field("remediation", &self.remediation)
             .finish()
     }
 }
@@ -111,6 +175,9 @@ impl fmt::Debug for Rule {
 pub struct Finding {
     pub path: PathBuf,
     pub line_number: usize,
+    /// Last line of a match produced by a multiline rule; `None` for single-line matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line_number: Option<usize>,
     pub line_content: String, // Raw line content
     pub rule_id: String,
     pub matched_content: String, // Raw matched secret (new)
@@ -138,4 +205,9 @@ pub struct Finding {
     pub author: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date: Option<String>,
+
+    /// Result of live verification; `None` when verification is disabled or
+    /// no verifier exists for the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<VerificationStatus>,
 }
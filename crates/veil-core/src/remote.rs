@@ -57,6 +57,9 @@ pub fn fetch_remote_rules(url: &str, timeout_secs: u64) -> Result<Vec<Rule>, Rem
             validator: None, // Remote rules cannot have code validators
             base_score: None,
             placeholder: None,
+            multiline: false,
+            keywords: Vec::new(),
+            remediation: None,
             context_lines_before: 2,
             context_lines_after: 0,
         });
@@ -161,6 +161,9 @@ pub fn try_get_all_rules(config: &Config, extra_rules: Vec<Rule>) -> Result<Vec<
                     validator_id,
                     validator,
                     placeholder: rule_conf.placeholder.clone(),
+                    multiline: false,
+                    keywords: Vec::new(),
+                    remediation: None,
                 };
                 rule_map.insert(id.clone(), rule);
             } else {
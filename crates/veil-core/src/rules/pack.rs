@@ -1,4 +1,4 @@
-use crate::model::Rule;
+use crate::model::{Remediation, Rule};
 use crate::validators::resolve_validator;
 use anyhow::{Context, Result};
 use rayon::prelude::*;
@@ -64,6 +64,9 @@ struct RuleConfigRaw {
     validator: Option<String>,
     // placeholder is optional, but we will enforce canonicalization later
     placeholder: Option<String>,
+    multiline: Option<bool>,
+    keywords: Option<Vec<String>>,
+    remediation: Option<Remediation>,
 }
 
 pub fn load_rule_pack(dir: &Path) -> Result<Vec<Rule>> {
@@ -340,6 +343,20 @@ fn parse_rules_from_content(content: &str, source: Option<&Path>) -> Result<Vec<
         let regex = Regex::new(&raw.pattern)
             .with_context(|| format!("Invalid regex for rule {}: {}", raw.id, raw.pattern))?;
 
+        if let Some(url) = raw
+            .remediation
+            .as_ref()
+            .and_then(|remediation| remediation.revoke_url.as_deref())
+        {
+            if !url.starts_with("https://") {
+                anyhow::bail!(
+                    "remediation.revoke_url for rule '{}' must use https (source: {:?})",
+                    raw.id,
+                    source
+                );
+            }
+        }
+
         let validator_id = raw.validator.clone();
         let validator = match validator_id.as_deref() {
             Some(id) => Some(resolve_validator(id).with_context(|| {
@@ -366,6 +383,9 @@ fn parse_rules_from_content(content: &str, source: Option<&Path>) -> Result<Vec<
             validator_id,
             validator,
             placeholder: raw.placeholder,
+            multiline: raw.multiline.unwrap_or(false),
+            keywords: raw.keywords.unwrap_or_default(),
+            remediation: raw.remediation,
         };
 
         rules.push(rule);
@@ -453,6 +473,110 @@ pattern = "b"
         assert_eq!(rules[1].id, "rule.a");
     }
 
+    #[test]
+    fn test_multiline_flag_is_parsed() {
+        let dir = setup_test_dir("multiline_flag");
+
+        let rules_toml = r#"
+[[rules]]
+id = "rule.block"
+description = "Block"
+pattern = '''BEGIN[\s\S]*?END'''
+multiline = true
+
+[[rules]]
+id = "rule.line"
+description = "Line"
+pattern = "line"
+"#;
+        File::create(dir.join("rules.toml"))
+            .unwrap()
+            .write_all(rules_toml.as_bytes())
+            .unwrap();
+
+        let rules = load_rule_pack(&dir).unwrap();
+        assert!(
+            rules
+                .iter()
+                .find(|r| r.id == "rule.block")
+                .unwrap()
+                .multiline
+        );
+        assert!(
+            !rules
+                .iter()
+                .find(|r| r.id == "rule.line")
+                .unwrap()
+                .multiline
+        );
+    }
+
+    #[test]
+    fn test_keywords_are_parsed() {
+        let dir = setup_test_dir("keywords");
+
+        let rules_toml = r#"
+[[rules]]
+id = "rule.keyword"
+description = "Keyword"
+pattern = "tok_[a-z]{8}"
+keywords = ["tok_"]
+"#;
+        File::create(dir.join("rules.toml"))
+            .unwrap()
+            .write_all(rules_toml.as_bytes())
+            .unwrap();
+
+        let rules = load_rule_pack(&dir).unwrap();
+        assert_eq!(rules[0].keywords, vec!["tok_".to_string()]);
+    }
+
+    #[test]
+    fn test_remediation_is_parsed_and_revoke_url_must_be_https() {
+        let dir = setup_test_dir("remediation");
+
+        let rules_toml = r#"
+[[rules]]
+id = "rule.remediation"
+description = "Remediation"
+pattern = "tok_[a-z]{8}"
+
+[rules.remediation]
+provider = "Example"
+revoke_url = "https://example.com/tokens"
+rotation_steps = ["Revoke the token", "Issue a new one"]
+history_rewrite = true
+
+[[rules]]
+id = "rule.plain"
+description = "Plain"
+pattern = "plain"
+"#;
+        File::create(dir.join("rules.toml"))
+            .unwrap()
+            .write_all(rules_toml.as_bytes())
+            .unwrap();
+
+        let rules = load_rule_pack(&dir).unwrap();
+        let remediation = rules[0].remediation.as_ref().unwrap();
+        assert_eq!(remediation.provider.as_deref(), Some("Example"));
+        assert_eq!(
+            remediation.revoke_url.as_deref(),
+            Some("https://example.com/tokens")
+        );
+        assert_eq!(remediation.rotation_steps.len(), 2);
+        assert!(remediation.history_rewrite);
+        assert!(rules[1].remediation.is_none());
+
+        let insecure = rules_toml.replace("https://example.com", "http://example.com");
+        File::create(dir.join("rules.toml"))
+            .unwrap()
+            .write_all(insecure.as_bytes())
+            .unwrap();
+        let err = load_rule_pack(&dir).unwrap_err();
+        assert!(format!("{err:#}").contains("must use https"));
+    }
+
     #[test]
     fn test_pinned_digest_signature_accepts_matching_pack() {
         let dir = setup_test_dir("pinned_digest_accepts_matching_pack");
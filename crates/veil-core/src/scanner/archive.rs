@@ -0,0 +1,477 @@
+//! Opt-in traversal of zip (jar/war/ear), tar, tar.gz and gz files.
+//!
+//! Entries are scanned in memory and reported under a nested path such as
+//! `bundle.zip!/config/.env` (`!/` repeats for archives inside archives).
+//! Every top-level archive gets its own budget from `[archive]`: nesting
+//! depth, entry count, per-entry and total uncompressed bytes. When a budget
+//! trips, the affected entry (or the rest of the archive) is recorded as an
+//! [`ArchiveSkip`] instead of being silently dropped.
+
+use crate::model::Finding;
+use crate::scanner::matcher::RuleMatcher;
+use crate::scanner::utils::scan_data_with_matcher;
+use crate::scanner::{
+    DEFAULT_MAX_FILE_SIZE_BYTES, RULE_ID_BINARY_FILE, RULE_ID_MAX_FILE_SIZE, RULE_ID_READ_ERROR,
+};
+use flate2::read::MultiGzDecoder;
+use serde::Serialize;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+use veil_config::{ArchiveConfig, Config};
+
+/// Separator between an archive path and the entry path inside it.
+pub const ARCHIVE_PATH_SEPARATOR: &str = "!/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+    Gz,
+}
+
+/// Classifies `path` by file name. Archives are recognised by extension only.
+pub fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else if name.ends_with(".gz") {
+        Some(ArchiveKind::Gz)
+    } else if [".zip", ".jar", ".war", ".ear"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+    {
+        Some(ArchiveKind::Zip)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveSkipReason {
+    /// Nested archive below `archive.max_depth`.
+    MaxDepth,
+    /// `archive.max_entries` reached; the rest of the archive was not read.
+    MaxEntries,
+    /// Entry larger than `archive.max_entry_bytes`.
+    MaxEntryBytes,
+    /// `archive.max_total_bytes` reached; the rest of the archive was not read.
+    MaxTotalBytes,
+    /// Archive file larger than `archive.max_archive_bytes`.
+    MaxArchiveBytes,
+    /// Text entry larger than `core.max_file_size`.
+    MaxFileSize,
+    /// Corrupt, encrypted or otherwise unreadable archive or entry.
+    Unreadable,
+}
+
+impl ArchiveSkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArchiveSkipReason::MaxDepth => "max_depth",
+            ArchiveSkipReason::MaxEntries => "max_entries",
+            ArchiveSkipReason::MaxEntryBytes => "max_entry_bytes",
+            ArchiveSkipReason::MaxTotalBytes => "max_total_bytes",
+            ArchiveSkipReason::MaxArchiveBytes => "max_archive_bytes",
+            ArchiveSkipReason::MaxFileSize => "max_file_size",
+            ArchiveSkipReason::Unreadable => "unreadable",
+        }
+    }
+}
+
+impl fmt::Display for ArchiveSkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An archive or entry left (partly) unscanned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArchiveSkip {
+    pub path: PathBuf,
+    pub reason: ArchiveSkipReason,
+}
+
+#[derive(Debug, Default)]
+pub struct ArchiveScan {
+    pub findings: Vec<Finding>,
+    pub skips: Vec<ArchiveSkip>,
+    /// Entries read, including nested archives themselves.
+    pub entries: usize,
+}
+
+/// Scans every entry of the archive at `path`.
+pub fn scan_archive(
+    path: &Path,
+    kind: ArchiveKind,
+    matcher: &RuleMatcher,
+    config: &Config,
+) -> ArchiveScan {
+    let mut walker = Walker {
+        matcher,
+        config,
+        limits: &config.archive,
+        max_file_size: config
+            .core
+            .max_file_size
+            .unwrap_or(DEFAULT_MAX_FILE_SIZE_BYTES),
+        total_bytes: 0,
+        stopped: false,
+        scan: ArchiveScan::default(),
+    };
+    let display = path.to_string_lossy().into_owned();
+
+    let opened = std::fs::metadata(path).and_then(|metadata| {
+        if metadata.len() > walker.limits.max_archive_bytes {
+            Ok(None)
+        } else {
+            File::open(path).map(Some)
+        }
+    });
+    match opened {
+        Ok(Some(file)) => walker.visit_archive(&display, kind, BufReader::new(file), 1),
+        Ok(None) => walker.skip(&display, ArchiveSkipReason::MaxArchiveBytes),
+        Err(_) => walker.skip(&display, ArchiveSkipReason::Unreadable),
+    }
+    walker.scan
+}
+
+struct Walker<'a, 'r> {
+    matcher: &'a RuleMatcher<'r>,
+    config: &'a Config,
+    limits: &'a ArchiveConfig,
+    max_file_size: u64,
+    total_bytes: u64,
+    stopped: bool,
+    scan: ArchiveScan,
+}
+
+impl Walker<'_, '_> {
+    fn skip(&mut self, path: &str, reason: ArchiveSkipReason) {
+        self.scan.skips.push(ArchiveSkip {
+            path: PathBuf::from(path),
+            reason,
+        });
+    }
+
+    fn visit_archive<R: Read + Seek>(
+        &mut self,
+        path: &str,
+        kind: ArchiveKind,
+        reader: R,
+        depth: u32,
+    ) {
+        let complete = match kind {
+            ArchiveKind::Zip => self.visit_zip(path, reader, depth),
+            ArchiveKind::Tar => self.visit_tar(path, reader, depth),
+            ArchiveKind::TarGz => self.visit_tar(path, MultiGzDecoder::new(reader), depth),
+            ArchiveKind::Gz => {
+                let name = Path::new(path)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.visit_entry(path, &name, MultiGzDecoder::new(reader), None, depth);
+                true
+            }
+        };
+        if !complete && !self.stopped {
+            self.skip(path, ArchiveSkipReason::Unreadable);
+        }
+    }
+
+    /// Returns `false` when the archive itself could not be read.
+    fn visit_zip<R: Read + Seek>(&mut self, path: &str, reader: R, depth: u32) -> bool {
+        let Ok(mut archive) = zip::ZipArchive::new(reader) else {
+            return false;
+        };
+        for index in 0..archive.len() {
+            if self.stopped {
+                break;
+            }
+            let name = archive
+                .name_for_index(index)
+                .unwrap_or_default()
+                .to_string();
+            match archive.by_index(index) {
+                Ok(entry) if entry.is_dir() => {}
+                Ok(entry) => {
+                    let size = entry.size();
+                    self.visit_entry(path, &name, entry, Some(size), depth);
+                }
+                Err(_) => self.skip(&nested(path, &name), ArchiveSkipReason::Unreadable),
+            }
+        }
+        true
+    }
+
+    fn visit_tar<R: Read>(&mut self, path: &str, reader: R, depth: u32) -> bool {
+        let mut archive = tar::Archive::new(reader);
+        let Ok(entries) = archive.entries() else {
+            return false;
+        };
+        for entry in entries {
+            if self.stopped {
+                break;
+            }
+            let Ok(entry) = entry else {
+                return false;
+            };
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path().map(|name| name.to_string_lossy().into_owned());
+            let Ok(name) = name else {
+                continue;
+            };
+            let size = entry.header().size().ok();
+            self.visit_entry(path, &name, entry, size, depth);
+        }
+        true
+    }
+
+    fn visit_entry<R: Read>(
+        &mut self,
+        archive_path: &str,
+        name: &str,
+        reader: R,
+        declared_size: Option<u64>,
+        depth: u32,
+    ) {
+        let entry_path = nested(archive_path, name);
+
+        self.scan.entries += 1;
+        if self.scan.entries > self.limits.max_entries {
+            self.skip(archive_path, ArchiveSkipReason::MaxEntries);
+            self.stopped = true;
+            return;
+        }
+        let inner_kind = archive_kind(Path::new(name));
+        if inner_kind.is_some() && depth >= self.limits.max_depth {
+            self.skip(&entry_path, ArchiveSkipReason::MaxDepth);
+            return;
+        }
+        if declared_size.is_some_and(|size| size > self.limits.max_entry_bytes) {
+            self.skip(&entry_path, ArchiveSkipReason::MaxEntryBytes);
+            return;
+        }
+
+        // Text entries are only scanned up to `max_file_size`, so reading one
+        // byte past it is enough to classify them; archives need the whole entry.
+        let read_limit = match inner_kind {
+            Some(_) => self.limits.max_entry_bytes,
+            None => self.max_file_size.min(self.limits.max_entry_bytes),
+        };
+        let mut data = Vec::new();
+        if reader
+            .take(read_limit.saturating_add(1))
+            .read_to_end(&mut data)
+            .is_err()
+        {
+            self.skip(&entry_path, ArchiveSkipReason::Unreadable);
+            return;
+        }
+        let read = data.len() as u64;
+
+        self.total_bytes += read;
+        if self.total_bytes > self.limits.max_total_bytes {
+            self.skip(archive_path, ArchiveSkipReason::MaxTotalBytes);
+            self.stopped = true;
+            return;
+        }
+        if read > self.limits.max_entry_bytes {
+            self.skip(&entry_path, ArchiveSkipReason::MaxEntryBytes);
+            return;
+        }
+
+        if let Some(kind) = inner_kind {
+            self.visit_archive(&entry_path, kind, Cursor::new(data), depth + 1);
+            return;
+        }
+
+        let findings =
+            scan_data_with_matcher(Path::new(&entry_path), &data, self.matcher, self.config);
+        match findings.first().map(|first| first.rule_id.as_str()) {
+            // Binary members (classes, images) are expected in archives.
+            Some(RULE_ID_BINARY_FILE) => {}
+            Some(RULE_ID_MAX_FILE_SIZE) => self.skip(&entry_path, ArchiveSkipReason::MaxFileSize),
+            Some(RULE_ID_READ_ERROR) => self.skip(&entry_path, ArchiveSkipReason::Unreadable),
+            _ => self.scan.findings.extend(findings),
+        }
+    }
+}
+
+fn nested(archive_path: &str, name: &str) -> String {
+    format!(
+        "{archive_path}{ARCHIVE_PATH_SEPARATOR}{}",
+        name.trim_start_matches('/')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    const AWS_LINE: &str = "AWS_ACCESS_KEY_ID=AKIA1234567890ABCDEF\n";
+
+    fn zip_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn tar_gz_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn enabled_config() -> Config {
+        let mut config = Config::default();
+        config.archive.enabled = true;
+        config
+    }
+
+    fn scan(path: &Path, config: &Config) -> ArchiveScan {
+        let rules = crate::get_default_rules();
+        let matcher = RuleMatcher::new(&rules, config);
+        scan_archive(path, archive_kind(path).unwrap(), &matcher, config)
+    }
+
+    #[test]
+    fn classifies_archives_by_extension() {
+        assert_eq!(archive_kind(Path::new("a.JAR")), Some(ArchiveKind::Zip));
+        assert_eq!(
+            archive_kind(Path::new("a.tar.gz")),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(archive_kind(Path::new("a.tgz")), Some(ArchiveKind::TarGz));
+        assert_eq!(archive_kind(Path::new("a.tar")), Some(ArchiveKind::Tar));
+        assert_eq!(
+            archive_kind(Path::new("app.log.1.gz")),
+            Some(ArchiveKind::Gz)
+        );
+        assert_eq!(archive_kind(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn reports_nested_paths_inside_zip_and_tar_gz() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = tar_gz_bytes(&[("deploy/app.env", AWS_LINE.as_bytes())]);
+        let bundle = dir.path().join("bundle.zip");
+        std::fs::write(
+            &bundle,
+            zip_bytes(&[
+                ("config/.env", AWS_LINE.as_bytes()),
+                ("lib/Main.class", b"\xca\xfe\xba\xbe\0\0"),
+                ("release.tar.gz", &inner),
+            ]),
+        )
+        .unwrap();
+
+        let result = scan(&bundle, &enabled_config());
+
+        let paths: Vec<String> = result
+            .findings
+            .iter()
+            .map(|finding| finding.path.to_string_lossy().into_owned())
+            .collect();
+        let root = bundle.to_string_lossy();
+        assert!(paths.contains(&format!("{root}!/config/.env")));
+        assert!(paths.contains(&format!("{root}!/release.tar.gz!/deploy/app.env")));
+        assert!(result.skips.is_empty(), "{:?}", result.skips);
+        assert_eq!(result.entries, 4);
+    }
+
+    #[test]
+    fn gz_files_are_scanned_as_their_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log.1.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(AWS_LINE.as_bytes()).unwrap();
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let result = scan(&path, &enabled_config());
+
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(
+            result.findings[0].path,
+            PathBuf::from(format!("{}!/app.log.1", path.display()))
+        );
+    }
+
+    #[test]
+    fn limits_are_reported_as_skips() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = zip_bytes(&[("deep/.env", AWS_LINE.as_bytes())]);
+        let bundle = dir.path().join("bundle.zip");
+        std::fs::write(
+            &bundle,
+            zip_bytes(&[
+                ("a.env", AWS_LINE.as_bytes()),
+                ("big.txt", &[b'x'; 64]),
+                ("inner.zip", &inner),
+                ("z.env", AWS_LINE.as_bytes()),
+            ]),
+        )
+        .unwrap();
+
+        let mut config = enabled_config();
+        config.archive.max_depth = 1;
+        config.archive.max_entry_bytes = 48;
+        config.archive.max_entries = 3;
+        let result = scan(&bundle, &config);
+
+        let root = bundle.to_string_lossy();
+        let skips: Vec<(String, ArchiveSkipReason)> = result
+            .skips
+            .iter()
+            .map(|skip| (skip.path.to_string_lossy().into_owned(), skip.reason))
+            .collect();
+        assert_eq!(
+            skips,
+            [
+                (format!("{root}!/big.txt"), ArchiveSkipReason::MaxEntryBytes),
+                (format!("{root}!/inner.zip"), ArchiveSkipReason::MaxDepth),
+                (root.to_string(), ArchiveSkipReason::MaxEntries),
+            ]
+        );
+        assert_eq!(result.findings.len(), 1, "z.env is past the entry limit");
+    }
+
+    #[test]
+    fn oversized_and_corrupt_archives_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let corrupt = dir.path().join("broken.zip");
+        std::fs::write(&corrupt, b"not a zip").unwrap();
+        assert_eq!(
+            scan(&corrupt, &enabled_config()).skips[0].reason,
+            ArchiveSkipReason::Unreadable
+        );
+
+        let mut config = enabled_config();
+        config.archive.max_archive_bytes = 4;
+        assert_eq!(
+            scan(&corrupt, &config).skips[0].reason,
+            ArchiveSkipReason::MaxArchiveBytes
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Shannon-entropy heuristic for random tokens that no vendor regex covers.
+//!
+//! Candidates are maximal runs of base64/url-safe characters on a line. Each
+//! run is classified by charset (hex, alphanumeric, base64) and compared
+//! against that charset's threshold from `veil_config::EntropyConfig`.
+//! Matches are returned with a synthetic `Rule` so they flow through the
+//! normal scoring and masking path in `scan_line`.
+
+use crate::model::{FindingSpan, Rule, Severity};
+use regex::Regex;
+use std::path::Path;
+use veil_config::{Config, EntropyConfig};
+
+pub const RULE_ID_ENTROPY_BASE64: &str = "entropy.base64";
+pub const RULE_ID_ENTROPY_HEX: &str = "entropy.hex";
+pub const RULE_ID_ENTROPY_ALPHANUMERIC: &str = "entropy.alphanumeric";
+
+/// Lockfiles are full of integrity hashes and resolved checksums.
+const LOCKFILE_NAMES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "Cargo.lock",
+    "go.sum",
+    "poetry.lock",
+    "Pipfile.lock",
+    "composer.lock",
+    "Gemfile.lock",
+    "flake.lock",
+];
+
+/// Subresource-integrity style prefixes (`sha512-...`) used by npm/yarn/pnpm.
+const INTEGRITY_PREFIXES: &[&str] = &["sha1-", "sha256-", "sha384-", "sha512-"];
+
+/// Hex digests (md5, sha1, sha256, sha512) that are usually checksums or commit ids.
+const HASH_HEX_LENGTHS: &[usize] = &[32, 40, 64, 128];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Hex,
+    Alphanumeric,
+    Base64,
+}
+
+pub struct EntropyDetector {
+    settings: EntropyConfig,
+    allowlist: Vec<Regex>,
+    base64_rule: Rule,
+    hex_rule: Rule,
+    alphanumeric_rule: Rule,
+}
+
+impl EntropyDetector {
+    /// Builds a detector when `[entropy]` is enabled. Invalid allowlist
+    /// patterns are rejected by `veil_config::validate`, so they are skipped here.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let settings = &config.entropy;
+        if !settings.enabled {
+            return None;
+        }
+
+        let allowlist = settings
+            .allowlist
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect();
+
+        Some(Self {
+            settings: settings.clone(),
+            allowlist,
+            base64_rule: entropy_rule(
+                RULE_ID_ENTROPY_BASE64,
+                "High-entropy base64 token",
+                r"[A-Za-z0-9+/=_-]+",
+            ),
+            hex_rule: entropy_rule(
+                RULE_ID_ENTROPY_HEX,
+                "High-entropy hex token",
+                r"[0-9a-fA-F]+",
+            ),
+            alphanumeric_rule: entropy_rule(
+                RULE_ID_ENTROPY_ALPHANUMERIC,
+                "High-entropy alphanumeric token",
+                r"[A-Za-z0-9]+",
+            ),
+        })
+    }
+
+    /// Returns true for files whose content is dominated by legitimate hashes.
+    pub fn skips_path(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| LOCKFILE_NAMES.contains(&name))
+    }
+
+    pub fn find(&self, line: &str) -> Vec<(&Rule, FindingSpan)> {
+        let mut matches = Vec::new();
+        let lower = line.to_lowercase();
+        let has_keyword = self
+            .settings
+            .keywords
+            .iter()
+            .any(|keyword| !keyword.is_empty() && lower.contains(&keyword.to_lowercase()));
+        let boost = if has_keyword {
+            self.settings.keyword_boost
+        } else {
+            0.0
+        };
+
+        for (start, end) in candidate_tokens(line) {
+            let token = &line[start..end];
+            if token.len() < self.settings.min_length {
+                continue;
+            }
+            let charset = classify(token);
+            if self.is_allowlisted(token, charset, has_keyword) {
+                continue;
+            }
+
+            let (threshold, rule) = match charset {
+                Charset::Hex => (self.settings.hex_threshold, &self.hex_rule),
+                Charset::Alphanumeric => (
+                    self.settings.alphanumeric_threshold,
+                    &self.alphanumeric_rule,
+                ),
+                Charset::Base64 => (self.settings.base64_threshold, &self.base64_rule),
+            };
+            if shannon_entropy(token) >= threshold - boost {
+                matches.push((
+                    rule,
+                    FindingSpan {
+                        byte_start: start,
+                        byte_end: end,
+                    },
+                ));
+            }
+        }
+
+        matches
+    }
+
+    fn is_allowlisted(&self, token: &str, charset: Charset, has_keyword: bool) -> bool {
+        if is_uuid(token) {
+            return true;
+        }
+        if INTEGRITY_PREFIXES
+            .iter()
+            .any(|prefix| token.starts_with(prefix))
+        {
+            return true;
+        }
+        // A bare digest is a checksum unless the line names it as a credential.
+        if charset == Charset::Hex && !has_keyword && HASH_HEX_LENGTHS.contains(&token.len()) {
+            return true;
+        }
+        // Mixed-case words without digits are identifiers, not random tokens.
+        if charset == Charset::Alphanumeric && !token.bytes().any(|b| b.is_ascii_digit()) {
+            return true;
+        }
+        self.allowlist.iter().any(|re| re.is_match(token))
+    }
+}
+
+fn entropy_rule(id: &str, description: &str, pattern: &str) -> Rule {
+    Rule {
+        id: id.to_string(),
+        enabled: true,
+        pattern: Regex::new(pattern).expect("static entropy pattern"),
+        description: description.to_string(),
+        severity: Severity::Medium,
+        score: 55,
+        base_score: Some(55),
+        category: "secret".to_string(),
+        tags: vec!["entropy".to_string()],
+        context_lines_before: 0,
+        context_lines_after: 0,
+        validator_id: None,
+        validator: None,
+        placeholder: None,
+        multiline: false,
+        keywords: Vec::new(),
+        remediation: None,
+    }
+}
+
+/// Shannon entropy in bits per character.
+pub fn shannon_entropy(token: &str) -> f64 {
+    if token.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for byte in token.bytes() {
+        counts[byte as usize] += 1;
+    }
+    let len = token.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+pub fn classify(token: &str) -> Charset {
+    if token.bytes().all(|b| b.is_ascii_hexdigit()) {
+        Charset::Hex
+    } else if token.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        Charset::Alphanumeric
+    } else {
+        Charset::Base64
+    }
+}
+
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'/' | b'=' | b'_' | b'-')
+}
+
+/// Byte ranges of maximal base64/url-safe runs. Runs are ASCII-only, so the
+/// boundaries are always valid `str` indices.
+fn candidate_tokens(line: &str) -> Vec<(usize, usize)> {
+    let bytes = line.as_bytes();
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, &byte) in bytes.iter().enumerate() {
+        // `=` only belongs to a token as trailing base64 padding; elsewhere it
+        // separates `KEY=value` assignments.
+        let is_part = if byte == b'=' {
+            bytes
+                .get(index + 1)
+                .is_none_or(|&next| next == b'=' || !is_token_byte(next))
+        } else {
+            is_token_byte(byte)
+        };
+        match (is_part, start) {
+            (true, None) => start = Some(index),
+            (false, Some(s)) => {
+                tokens.push(trim_token(bytes, s, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(trim_token(bytes, s, bytes.len()));
+    }
+    tokens
+}
+
+/// Drops leading separators (`-`, `_`, `/`) so `--flag=VALUE` or `/path` do not
+/// swallow the value's neighbours.
+fn trim_token(bytes: &[u8], mut start: usize, end: usize) -> (usize, usize) {
+    while start < end && matches!(bytes[start], b'-' | b'_' | b'/' | b'=') {
+        start += 1;
+    }
+    (start, end)
+}
+
+fn is_uuid(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(index, &b)| match index {
+            8 | 13 | 18 | 23 => b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> EntropyDetector {
+        let mut config = Config::default();
+        config.entropy.enabled = true;
+        EntropyDetector::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(EntropyDetector::from_config(&Config::default()).is_none());
+    }
+
+    #[test]
+    fn shannon_entropy_matches_known_values() {
+        assert_eq!(shannon_entropy("aaaa"), 0.0);
+        assert!((shannon_entropy("abcd") - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn detects_random_base64_token() {
+        let line = "API_TOKEN = \"q8Zr+3kV/Lm9Tx2Wp7Yb4Nc6Hd1Fg0Js5Ue=\"";
+        let detector = detector();
+        let matches = detector.find(line);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0.id, RULE_ID_ENTROPY_BASE64);
+        let span = matches[0].1;
+        assert_eq!(
+            &line[span.byte_start..span.byte_end],
+            "q8Zr+3kV/Lm9Tx2Wp7Yb4Nc6Hd1Fg0Js5Ue="
+        );
+    }
+
+    #[test]
+    fn splits_assignments_but_keeps_padding() {
+        let line = "TOKEN=q8Zr+3kV/Lm9Tx2Wp7Yb4Nc6Hd1Fg0Js5Ue==";
+        let detector = detector();
+        let matches = detector.find(line);
+        assert_eq!(matches.len(), 1);
+        let span = matches[0].1;
+        assert_eq!(
+            &line[span.byte_start..span.byte_end],
+            "q8Zr+3kV/Lm9Tx2Wp7Yb4Nc6Hd1Fg0Js5Ue=="
+        );
+    }
+
+    #[test]
+    fn keyword_context_lowers_threshold() {
+        let token = "9f86d081a4b7c3e25d0f1e6a";
+        let mut config = Config::default();
+        config.entropy.enabled = true;
+        config.entropy.hex_threshold = shannon_entropy(token) + 0.2;
+        let strict = EntropyDetector::from_config(&config).unwrap();
+        assert!(strict.find(&format!("value {token}")).is_empty());
+        assert_eq!(strict.find(&format!("secret {token}")).len(), 1);
+    }
+
+    #[test]
+    fn allowlists_uuids_hashes_and_integrity_strings() {
+        let detector = detector();
+        assert!(detector
+            .find("id = 123e4567-e89b-12d3-a456-426614174000")
+            .is_empty());
+        assert!(detector
+            .find("commit e83c5163316f89bfbde7d9ab23ca2e25604af290")
+            .is_empty());
+        assert!(detector
+            .find("integrity sha512-q8Zr+3kV/Lm9Tx2Wp7Yb4Nc6Hd1Fg0Js5UeQ8Zr3kVLm9Tx2Wp7Yb4Nc6Hd1Fg0Js5Ue==")
+            .is_empty());
+        assert!(detector.skips_path(Path::new("web/package-lock.json")));
+    }
+
+    #[test]
+    fn ignores_identifiers_and_user_allowlist() {
+        let mut config = Config::default();
+        config.entropy.enabled = true;
+        config.entropy.allowlist = vec!["^q8Zr".to_string()];
+        let detector = EntropyDetector::from_config(&config).unwrap();
+
+        assert!(detector
+            .find("let handler = createAuthenticationMiddlewareFactory();")
+            .is_empty());
+        assert!(detector
+            .find("token = q8Zr+3kV/Lm9Tx2Wp7Yb4Nc6Hd1Fg0Js5Ue=")
+            .is_empty());
+    }
+}
@@ -0,0 +1,473 @@
+//! Candidate selection for line rules.
+//!
+//! Running every rule's regex on every line scales linearly with the rule
+//! count. [`RuleMatcher`] compiles the enabled rules once and narrows each
+//! line down to the rules that can possibly match:
+//!
+//! - rules declaring `keywords` run only when one of those literals occurs in
+//!   the line (ASCII case-insensitive, via Aho-Corasick);
+//! - rules without `keywords` get them derived from the pattern when every
+//!   match must contain one of a small set of literals (e.g. `ghp_`, or the
+//!   label alternation of a JP template);
+//! - the remaining rules are compiled into a single `RegexSet`, and only the
+//!   members the set reports run full `find_iter` matching.
+//!
+//! The matcher also carries the `[entropy]` detector, so both are built once
+//! per scan. Build one matcher per scan and share it across files;
+//! construction is the expensive part.
+
+use crate::model::Rule;
+use crate::scanner::entropy::EntropyDetector;
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use regex::{RegexSet, RegexSetBuilder};
+use regex_syntax::hir::{Class, Hir, HirKind};
+use std::path::Path;
+use veil_config::Config;
+
+/// Compiled size budget for the combined set. Larger rule packs fall back to
+/// running their regex-only rules unconditionally.
+const REGEX_SET_SIZE_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Derived keywords shorter than this filter too little to be worth it.
+const MIN_DERIVED_KEYWORD_LEN: usize = 3;
+
+/// Upper bound on alternatives tracked per literal set while deriving.
+const MAX_DERIVED_KEYWORDS: usize = 64;
+
+/// Larger classes are treated as "anything" while deriving.
+const MAX_CLASS_LITERALS: usize = 4;
+
+pub struct RuleMatcher<'a> {
+    line_rules: Vec<&'a Rule>,
+    multiline_rules: Vec<&'a Rule>,
+    keywords: Option<AhoCorasick>,
+    /// Aho-Corasick pattern id -> index into `line_rules`.
+    keyword_owners: Vec<usize>,
+    regex_set: Option<RegexSet>,
+    /// `regex_set` member -> index into `line_rules`.
+    set_owners: Vec<usize>,
+    /// Rules that always run (set compilation failed).
+    unfiltered: Vec<usize>,
+    /// `None` when `[entropy]` is disabled.
+    entropy: Option<EntropyDetector>,
+}
+
+impl<'a> RuleMatcher<'a> {
+    /// Compiles the rules enabled under `config`.
+    pub fn new(rules: &'a [Rule], config: &Config) -> Self {
+        let (multiline_rules, line_rules): (Vec<&Rule>, Vec<&Rule>) = rules
+            .iter()
+            .filter(|rule| super::is_rule_enabled(rule, config))
+            .partition(|rule| rule.multiline);
+
+        let mut keyword_patterns = Vec::new();
+        let mut keyword_owners = Vec::new();
+        let mut set_patterns = Vec::new();
+        let mut set_owners = Vec::new();
+        for (index, rule) in line_rules.iter().enumerate() {
+            let mut keywords: Vec<String> = rule
+                .keywords
+                .iter()
+                .filter(|keyword| !keyword.is_empty())
+                .cloned()
+                .collect();
+            if keywords.is_empty() {
+                keywords = derive_keywords(rule.pattern.as_str());
+            }
+            if keywords.is_empty() {
+                set_patterns.push(rule.pattern.as_str());
+                set_owners.push(index);
+            } else {
+                for keyword in keywords {
+                    keyword_patterns.push(keyword);
+                    keyword_owners.push(index);
+                }
+            }
+        }
+
+        let keywords = (!keyword_patterns.is_empty())
+            .then(|| {
+                AhoCorasickBuilder::new()
+                    .ascii_case_insensitive(true)
+                    .build(&keyword_patterns)
+                    .ok()
+            })
+            .flatten();
+        // Without an automaton, keyword rules can only be run unconditionally.
+        let mut unfiltered = Vec::new();
+        if keywords.is_none() {
+            unfiltered.extend(keyword_owners.iter().copied());
+            unfiltered.dedup();
+        }
+
+        let regex_set = (!set_patterns.is_empty())
+            .then(|| {
+                RegexSetBuilder::new(&set_patterns)
+                    .size_limit(REGEX_SET_SIZE_LIMIT)
+                    .build()
+                    .ok()
+            })
+            .flatten();
+        if regex_set.is_none() {
+            unfiltered.extend(set_owners.iter().copied());
+        }
+
+        Self {
+            line_rules,
+            multiline_rules,
+            keywords,
+            keyword_owners,
+            regex_set,
+            set_owners,
+            unfiltered,
+            entropy: EntropyDetector::from_config(config),
+        }
+    }
+
+    /// The entropy detector for `path`, unless `[entropy]` is disabled or the
+    /// file is exempt (lockfiles).
+    pub fn entropy_for(&self, path: &Path) -> Option<&EntropyDetector> {
+        self.entropy
+            .as_ref()
+            .filter(|detector| !detector.skips_path(path))
+    }
+
+    /// Enabled rules that run line by line, in rule order.
+    pub fn line_rules(&self) -> &[&'a Rule] {
+        &self.line_rules
+    }
+
+    /// Enabled rules that run over the whole buffer.
+    pub fn multiline_rules(&self) -> &[&'a Rule] {
+        &self.multiline_rules
+    }
+
+    /// Rules worth running on `line`, in rule order. `normalized` is the JP
+    /// normalized form of the line, when it differs; candidates from either
+    /// text are included.
+    pub fn candidates(&self, line: &str, normalized: Option<&str>) -> Vec<&'a Rule> {
+        let mut hits = self.unfiltered.clone();
+        for text in std::iter::once(line).chain(normalized) {
+            if let Some(keywords) = &self.keywords {
+                hits.extend(
+                    keywords
+                        .find_overlapping_iter(text)
+                        .map(|mat| self.keyword_owners[mat.pattern().as_usize()]),
+                );
+            }
+            if let Some(set) = &self.regex_set {
+                hits.extend(set.matches(text).iter().map(|i| self.set_owners[i]));
+            }
+        }
+
+        hits.sort_unstable();
+        hits.dedup();
+        hits.into_iter()
+            .map(|index| self.line_rules[index])
+            .collect()
+    }
+}
+
+/// Literals of which every match of `pattern` must contain at least one,
+/// ASCII-lowercased. Empty when no useful set exists.
+pub fn derive_keywords(pattern: &str) -> Vec<String> {
+    let Ok(hir) = regex_syntax::parse(pattern) else {
+        return Vec::new();
+    };
+    match required_literals(&hir).set {
+        Some(set) if set.iter().all(|lit| lit.len() >= MIN_DERIVED_KEYWORD_LEN) => set,
+        _ => Vec::new(),
+    }
+}
+
+/// `set`: every match contains one of these strings (`None` if unknown).
+/// `exact`: every match *is* one of these strings, so neighbours in a
+/// concatenation can be joined onto them.
+struct Literals {
+    set: Option<Vec<String>>,
+    exact: bool,
+}
+
+impl Literals {
+    fn unknown() -> Self {
+        Self {
+            set: None,
+            exact: false,
+        }
+    }
+
+    fn exact(set: Vec<String>) -> Self {
+        Self {
+            set: Some(set),
+            exact: true,
+        }
+    }
+}
+
+fn required_literals(hir: &Hir) -> Literals {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => Literals::exact(vec![String::new()]),
+        HirKind::Literal(literal) => match std::str::from_utf8(&literal.0) {
+            Ok(text) => Literals::exact(vec![text.to_ascii_lowercase()]),
+            Err(_) => Literals::unknown(),
+        },
+        HirKind::Class(Class::Unicode(class)) => {
+            let mut chars = Vec::new();
+            for range in class.ranges() {
+                for ch in range.start()..=range.end() {
+                    let ch = ch.to_ascii_lowercase().to_string();
+                    if !chars.contains(&ch) {
+                        chars.push(ch);
+                    }
+                    if chars.len() > MAX_CLASS_LITERALS {
+                        return Literals::unknown();
+                    }
+                }
+            }
+            Literals::exact(chars)
+        }
+        HirKind::Class(Class::Bytes(_)) => Literals::unknown(),
+        HirKind::Capture(capture) => required_literals(&capture.sub),
+        HirKind::Repetition(repetition) => {
+            if repetition.min == 0 {
+                return Literals::unknown();
+            }
+            let inner = required_literals(&repetition.sub);
+            Literals {
+                exact: inner.exact && repetition.max == Some(1) && repetition.min == 1,
+                set: inner.set,
+            }
+        }
+        HirKind::Alternation(branches) => {
+            let mut set = Vec::new();
+            let mut exact = true;
+            for branch in branches {
+                let literals = required_literals(branch);
+                let Some(branch_set) = literals.set else {
+                    return Literals::unknown();
+                };
+                exact &= literals.exact;
+                for lit in branch_set {
+                    if !set.contains(&lit) {
+                        set.push(lit);
+                    }
+                }
+                if set.len() > MAX_DERIVED_KEYWORDS {
+                    return Literals::unknown();
+                }
+            }
+            Literals {
+                set: Some(set),
+                exact,
+            }
+        }
+        HirKind::Concat(parts) => {
+            // Join runs of exact parts into longer literals and keep the most
+            // selective run (or inexact part) as the requirement.
+            let mut best: Option<Vec<String>> = None;
+            let mut run: Option<Vec<String>> = Some(vec![String::new()]);
+            let mut all_exact = true;
+            for part in parts {
+                let literals = required_literals(part);
+                match (literals.exact, literals.set) {
+                    (true, Some(set)) => {
+                        run = match run.take() {
+                            Some(prefixes)
+                                if prefixes.len() * set.len() <= MAX_DERIVED_KEYWORDS =>
+                            {
+                                Some(cross(&prefixes, &set))
+                            }
+                            Some(prefixes) => {
+                                all_exact = false;
+                                best = more_selective(best, prefixes);
+                                Some(set)
+                            }
+                            None => Some(set),
+                        };
+                    }
+                    (_, set) => {
+                        all_exact = false;
+                        if let Some(prefixes) = run.take() {
+                            best = more_selective(best, prefixes);
+                        }
+                        if let Some(set) = set {
+                            best = more_selective(best, set);
+                        }
+                    }
+                }
+            }
+            if all_exact {
+                return Literals {
+                    set: run,
+                    exact: true,
+                };
+            }
+            if let Some(prefixes) = run {
+                best = more_selective(best, prefixes);
+            }
+            Literals {
+                set: best,
+                exact: false,
+            }
+        }
+    }
+}
+
+fn cross(prefixes: &[String], suffixes: &[String]) -> Vec<String> {
+    let mut joined = Vec::with_capacity(prefixes.len() * suffixes.len());
+    for prefix in prefixes {
+        for suffix in suffixes {
+            let lit = format!("{prefix}{suffix}");
+            if !joined.contains(&lit) {
+                joined.push(lit);
+            }
+        }
+    }
+    joined
+}
+
+/// Prefers the set whose shortest literal is longest, then the smaller set.
+fn more_selective(current: Option<Vec<String>>, candidate: Vec<String>) -> Option<Vec<String>> {
+    let rank = |set: &[String]| {
+        let shortest = set.iter().map(String::len).min().unwrap_or(0);
+        (shortest, std::cmp::Reverse(set.len()))
+    };
+    match current {
+        Some(current) if rank(&current) >= rank(&candidate) => Some(current),
+        _ => Some(candidate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Severity;
+    use regex::Regex;
+
+    fn rule(id: &str, pattern: &str, keywords: &[&str]) -> Rule {
+        Rule {
+            id: id.to_string(),
+            enabled: true,
+            pattern: Regex::new(pattern).unwrap(),
+            description: String::new(),
+            severity: Severity::High,
+            score: 80,
+            base_score: None,
+            category: "test".to_string(),
+            tags: vec![],
+            context_lines_before: 0,
+            context_lines_after: 0,
+            validator_id: None,
+            validator: None,
+            placeholder: None,
+            multiline: false,
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            remediation: None,
+        }
+    }
+
+    fn ids(rules: Vec<&Rule>) -> Vec<&str> {
+        rules.into_iter().map(|rule| rule.id.as_str()).collect()
+    }
+
+    #[test]
+    fn keyword_rules_run_only_when_a_keyword_is_present() {
+        let rules = vec![
+            rule("token", r"tok_[a-z]{8}", &["tok_"]),
+            rule("aws", r"AKIA[0-9A-Z]{16}", &[]),
+        ];
+        let matcher = RuleMatcher::new(&rules, &Config::default());
+
+        assert!(matcher.candidates("nothing here", None).is_empty());
+        assert_eq!(ids(matcher.candidates("x = TOK_abc", None)), ["token"]);
+        assert_eq!(
+            ids(matcher.candidates("AKIA1234567890ABCDEF tok_abcdefgh", None)),
+            ["token", "aws"]
+        );
+    }
+
+    #[test]
+    fn entropy_detector_is_built_once_and_skips_lockfiles() {
+        let rules = vec![rule("aws", r"AKIA[0-9A-Z]{16}", &[])];
+        let disabled = RuleMatcher::new(&rules, &Config::default());
+        assert!(disabled.entropy_for(Path::new("app.env")).is_none());
+
+        let mut config = Config::default();
+        config.entropy.enabled = true;
+        let matcher = RuleMatcher::new(&rules, &config);
+        assert!(matcher.entropy_for(Path::new("app.env")).is_some());
+        assert!(matcher.entropy_for(Path::new("Cargo.lock")).is_none());
+    }
+
+    #[test]
+    fn regex_only_rules_are_gated_by_the_set() {
+        let rules = vec![rule("postal", r"[0-9]{3}-[0-9]{4}", &[])];
+        let matcher = RuleMatcher::new(&rules, &Config::default());
+
+        assert!(matcher.candidates("version 12-34", None).is_empty());
+        assert_eq!(ids(matcher.candidates("〒100-0001", None)), ["postal"]);
+    }
+
+    #[test]
+    fn normalized_text_also_selects_candidates() {
+        let rules = vec![rule("digits", r"[0-9]{4}-[0-9]{4}", &[])];
+        let matcher = RuleMatcher::new(&rules, &Config::default());
+
+        assert!(matcher.candidates("１２３４-５６７８", None).is_empty());
+        assert_eq!(
+            ids(matcher.candidates("１２３４-５６７８", Some("1234-5678"))),
+            ["digits"]
+        );
+    }
+
+    #[test]
+    fn disabled_and_multiline_rules_are_not_line_candidates() {
+        let mut disabled = rule("off", r"secret", &[]);
+        disabled.enabled = false;
+        let mut block = rule("block", r"BEGIN[\s\S]*END", &[]);
+        block.multiline = true;
+        let rules = vec![disabled, block];
+        let matcher = RuleMatcher::new(&rules, &Config::default());
+
+        assert!(matcher.line_rules().is_empty());
+        assert_eq!(matcher.multiline_rules().len(), 1);
+        assert!(matcher.candidates("secret BEGIN END", None).is_empty());
+    }
+
+    #[test]
+    fn derives_required_literals_from_patterns() {
+        assert_eq!(derive_keywords(r"ghp_[A-Za-z0-9]{36}"), ["ghp_"]);
+        assert_eq!(
+            derive_keywords(r"xox[abps]-[0-9A-Za-z-]{10,}"),
+            ["xoxa-", "xoxb-", "xoxp-", "xoxs-"]
+        );
+        // Unicode case folding keeps the long-s variant of `s`.
+        assert_eq!(
+            derive_keywords(r"(?i)(?:api[_-]?key|secret)\s*[:=]"),
+            ["api", "secret", "ſecret"]
+        );
+        assert_eq!(
+            derive_keywords(r#"(?i)["']?(?:Bearer|パスワード)["']?\s*[:=]"#),
+            ["bearer", "パスワード"]
+        );
+    }
+
+    #[test]
+    fn patterns_without_useful_literals_derive_nothing() {
+        assert!(derive_keywords(r"[0-9]{3}-?[0-9]{4}").is_empty());
+        assert!(derive_keywords(r"(?:ID|番号)\s*[:：]\s*\w+").is_empty());
+        assert!(derive_keywords(r"(").is_empty());
+    }
+
+    #[test]
+    fn explicit_keywords_take_precedence_over_derived_ones() {
+        let rules = vec![rule("labelled", r"secret=[a-z]+", &["vault"])];
+        let matcher = RuleMatcher::new(&rules, &Config::default());
+
+        assert!(matcher.candidates("secret=abc", None).is_empty());
+        assert_eq!(
+            ids(matcher.candidates("vault secret=abc", None)),
+            ["labelled"]
+        );
+    }
+}
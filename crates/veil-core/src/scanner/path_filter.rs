@@ -0,0 +1,336 @@
+//! gitignore-style matching for `[core] ignore` and `[core] include`.
+//!
+//! Both lists use `.gitignore` syntax: a bare name (`test`) matches a file or
+//! directory with that exact name at any depth, a leading `/` anchors the
+//! pattern to the directory of the repo config (`[core] patterns_root`, the
+//! working directory by default) whatever directory is scanned, `**` spans
+//! directories and `!` negates. A pattern that matches a directory applies to
+//! everything below it.
+//!
+//! `ignore` is evaluated first. `include` is a positive filter on top of it:
+//! once it holds any pattern other than `.`, only paths it matches are
+//! scanned.
+
+use crate::scanner::BUILTIN_IGNORES;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::path::{Component, Path, PathBuf};
+use veil_config::config::CoreConfig;
+
+/// Why a path is (or is not) scanned. Patterns are reported as written in
+/// the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathVerdict {
+    /// No ignore or include pattern applies.
+    Default,
+    /// Inside one of the built-in ignored directories (`node_modules`, ...).
+    Builtin(String),
+    /// Excluded by an `ignore` pattern.
+    Ignored(String),
+    /// An `ignore` negation re-included the path.
+    Unignored(String),
+    /// Selected by an `include` pattern.
+    Included(String),
+    /// `include` is active and the path is not selected. Carries the
+    /// negated include pattern that dropped it, if any.
+    NotIncluded(Option<String>),
+}
+
+impl PathVerdict {
+    pub fn is_excluded(&self) -> bool {
+        matches!(
+            self,
+            PathVerdict::Builtin(_) | PathVerdict::Ignored(_) | PathVerdict::NotIncluded(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    ignore: Gitignore,
+    include: Gitignore,
+    cwd: PathBuf,
+    root: PathBuf,
+}
+
+impl Default for PathFilter {
+    fn default() -> Self {
+        Self {
+            ignore: Gitignore::empty(),
+            include: Gitignore::empty(),
+            cwd: PathBuf::new(),
+            root: PathBuf::new(),
+        }
+    }
+}
+
+impl PathFilter {
+    pub fn new(core: &CoreConfig) -> Result<Self, ignore::Error> {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let root = match &core.patterns_root {
+            Some(root) => cwd.join(root),
+            None => cwd.clone(),
+        };
+        Ok(Self {
+            ignore: build_matcher(&core.ignore)?,
+            include: build_matcher(
+                core.include
+                    .iter()
+                    .filter(|pattern| !is_whole_tree(pattern)),
+            )?,
+            cwd,
+            root,
+        })
+    }
+
+    /// `path` (absolute, or relative to the working directory) relative to
+    /// the directory anchored patterns resolve against, ready for
+    /// [`PathFilter::verdict`].
+    pub fn relative(&self, path: &Path) -> PathBuf {
+        relative_to(&self.cwd.join(path), &self.root)
+    }
+
+    /// Evaluates `rel`, a path relative to the patterns root (see
+    /// [`PathFilter::relative`]).
+    pub fn verdict(&self, rel: &Path, is_dir: bool) -> PathVerdict {
+        let rel = normalize(rel);
+        if rel.as_os_str().is_empty() {
+            return PathVerdict::Default;
+        }
+
+        let parent_dirs = rel.parent().into_iter().flat_map(Path::components);
+        let file_dir = is_dir.then(|| rel.components().next_back()).flatten();
+        for component in parent_dirs.chain(file_dir) {
+            let name = component.as_os_str().to_string_lossy();
+            if BUILTIN_IGNORES.contains(&name.as_ref()) {
+                return PathVerdict::Builtin(name.into_owned());
+            }
+        }
+
+        self.pattern_verdict(&rel, is_dir)
+    }
+
+    /// [`PathFilter::verdict`] without the built-in ignored directories. A
+    /// walk prunes those below its root itself and still scans inside one
+    /// it was pointed at.
+    pub fn pattern_verdict(&self, rel: &Path, is_dir: bool) -> PathVerdict {
+        let rel = normalize(rel);
+        if rel.as_os_str().is_empty() {
+            return PathVerdict::Default;
+        }
+
+        // Like git, a file cannot be re-included once a parent directory is
+        // ignored: the walk never descends into it.
+        let mut ancestors: Vec<&Path> = rel.ancestors().skip(1).collect();
+        ancestors.reverse();
+        for dir in ancestors
+            .into_iter()
+            .filter(|dir| !dir.as_os_str().is_empty())
+        {
+            if let Match::Ignore(glob) = self.ignore.matched(dir, true) {
+                return PathVerdict::Ignored(glob.original().to_string());
+            }
+        }
+        let unignored = match self.ignore.matched(&rel, is_dir) {
+            Match::Ignore(glob) => return PathVerdict::Ignored(glob.original().to_string()),
+            Match::Whitelist(glob) => Some(glob.original().to_string()),
+            Match::None => None,
+        };
+
+        if !self.include.is_empty() {
+            match self.include.matched_path_or_any_parents(&rel, is_dir) {
+                Match::Ignore(glob) => return PathVerdict::Included(glob.original().to_string()),
+                Match::Whitelist(glob) => {
+                    return PathVerdict::NotIncluded(Some(glob.original().to_string()))
+                }
+                // Negations alone do not restrict the scan to anything.
+                Match::None if self.include.num_ignores() > 0 => {
+                    return PathVerdict::NotIncluded(None)
+                }
+                Match::None => {}
+            }
+        }
+
+        unignored.map_or(PathVerdict::Default, PathVerdict::Unignored)
+    }
+
+    pub fn is_excluded(&self, rel: &Path, is_dir: bool) -> bool {
+        self.verdict(rel, is_dir).is_excluded()
+    }
+
+    /// Whether a directory can be skipped without descending into it. Only
+    /// `ignore` prunes: an `include` pattern may still select something
+    /// deeper down.
+    pub fn prunes_dir(&self, rel: &Path) -> bool {
+        let rel = normalize(rel);
+        !rel.as_os_str().is_empty() && self.ignore.matched(&rel, true).is_ignore()
+    }
+}
+
+/// Whether the repository-relative `path` lies under one of `prefixes`.
+/// No prefixes means the whole repository.
+pub fn under_any(path: &Path, prefixes: &[PathBuf]) -> bool {
+    prefixes.is_empty() || prefixes.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// `path` relative to `root`. Paths outside `root` keep their normal
+/// components so unanchored patterns still apply to them.
+pub fn relative_to(path: &Path, root: &Path) -> PathBuf {
+    match path.strip_prefix(root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => normalize(rel),
+        _ => normalize(path),
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+/// `include = ["."]` is the default and means "everything".
+fn is_whole_tree(pattern: &str) -> bool {
+    matches!(pattern.trim(), "" | "." | "./")
+}
+
+fn build_matcher<'a>(
+    patterns: impl IntoIterator<Item = &'a String>,
+) -> Result<Gitignore, ignore::Error> {
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in patterns {
+        builder.add_line(None, pattern)?;
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(ignore: &[&str], include: &[&str]) -> PathFilter {
+        let core = CoreConfig {
+            ignore: ignore.iter().map(|s| s.to_string()).collect(),
+            include: include.iter().map(|s| s.to_string()).collect(),
+            ..CoreConfig::default()
+        };
+        PathFilter::new(&core).unwrap()
+    }
+
+    fn verdict(filter: &PathFilter, path: &str) -> PathVerdict {
+        filter.verdict(Path::new(path), false)
+    }
+
+    #[test]
+    fn bare_names_match_whole_components_only() {
+        let f = filter(&["test"], &["."]);
+        assert_eq!(verdict(&f, "src/latest.rs"), PathVerdict::Default);
+        assert_eq!(
+            verdict(&f, "test/fixtures/key.pem"),
+            PathVerdict::Ignored("test".into())
+        );
+        assert_eq!(
+            verdict(&f, "crates/a/test/x.rs"),
+            PathVerdict::Ignored("test".into())
+        );
+    }
+
+    #[test]
+    fn anchored_globstar_and_negation() {
+        let f = filter(
+            &[
+                "/out",
+                "**/*.log",
+                "!keep.log",
+                "secrets",
+                "!secrets/ok.txt",
+            ],
+            &[],
+        );
+        assert!(f.is_excluded(Path::new("out/app.txt"), false));
+        assert_eq!(verdict(&f, "src/out/app.txt"), PathVerdict::Default);
+        assert!(f.is_excluded(Path::new("a/b/debug.log"), false));
+        assert_eq!(
+            verdict(&f, "a/keep.log"),
+            PathVerdict::Unignored("!keep.log".into())
+        );
+        // A negation cannot reach into an ignored directory.
+        assert_eq!(
+            verdict(&f, "secrets/ok.txt"),
+            PathVerdict::Ignored("secrets".into())
+        );
+    }
+
+    #[test]
+    fn include_is_a_positive_filter() {
+        let f = filter(&["src/generated"], &[".", "src", "!src/vendored"]);
+        assert_eq!(
+            verdict(&f, "src/main.rs"),
+            PathVerdict::Included("src".into())
+        );
+        assert_eq!(
+            verdict(&f, "docs/readme.md"),
+            PathVerdict::NotIncluded(None)
+        );
+        assert_eq!(
+            verdict(&f, "src/vendored/lib.rs"),
+            PathVerdict::NotIncluded(Some("!src/vendored".into()))
+        );
+        assert_eq!(
+            verdict(&f, "src/generated/a.rs"),
+            PathVerdict::Ignored("src/generated".into())
+        );
+        assert!(!f.prunes_dir(Path::new("docs")));
+        assert!(f.prunes_dir(Path::new("src/generated")));
+    }
+
+    #[test]
+    fn anchored_patterns_resolve_against_the_patterns_root() {
+        let root = std::env::current_dir().unwrap().join("project");
+        let core = CoreConfig {
+            ignore: vec!["/src/generated".to_string()],
+            patterns_root: Some(root.clone()),
+            ..CoreConfig::default()
+        };
+        let f = PathFilter::new(&core).unwrap();
+
+        let generated = root.join("src/generated/a.txt");
+        assert_eq!(f.relative(&generated), Path::new("src/generated/a.txt"));
+        assert!(f.is_excluded(&f.relative(&generated), false));
+        assert!(!f.is_excluded(&f.relative(&root.join("lib/src/generated/a.txt")), false));
+        assert_eq!(
+            f.relative(Path::new("project/src/generated/a.txt")),
+            Path::new("src/generated/a.txt")
+        );
+    }
+
+    #[test]
+    fn default_include_and_builtin_dirs() {
+        let f = filter(&[], &["."]);
+        assert_eq!(verdict(&f, "anything/at/all.txt"), PathVerdict::Default);
+        assert_eq!(
+            verdict(&f, "web/node_modules/pkg/index.js"),
+            PathVerdict::Builtin("node_modules".into())
+        );
+        assert_eq!(verdict(&f, "target"), PathVerdict::Default);
+        assert_eq!(
+            f.verdict(Path::new("target"), true),
+            PathVerdict::Builtin("target".into())
+        );
+    }
+
+    #[test]
+    fn relative_to_strips_root_and_keeps_outside_paths() {
+        assert_eq!(
+            relative_to(Path::new("/repo/src/a.rs"), Path::new("/repo")),
+            PathBuf::from("src/a.rs")
+        );
+        assert_eq!(
+            relative_to(Path::new("./src/a.rs"), Path::new(".")),
+            PathBuf::from("src/a.rs")
+        );
+        assert_eq!(
+            relative_to(Path::new("/elsewhere/a.rs"), Path::new("/repo")),
+            PathBuf::from("elsewhere/a.rs")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::model::Finding;
+use crate::scanner::archive::ArchiveSkip;
 
 #[derive(Debug, Clone, Default)]
 pub struct ScanResult {
@@ -10,5 +11,13 @@ pub struct ScanResult {
     pub file_limit_reached: bool,
     pub max_file_size_reached: bool,
     pub read_error_reached: bool,
+    /// Files whose findings came from the scan cache instead of a rescan.
+    pub cache_hits: usize,
+    /// The scan was stopped through its cancellation flag; findings and
+    /// counters cover only the files scanned before that.
+    pub cancelled: bool,
+    /// Archives or archive entries left unscanned because an `[archive]`
+    /// limit tripped or they could not be read.
+    pub archive_skips: Vec<ArchiveSkip>,
     pub builtin_skips: std::collections::HashSet<String>,
 }
@@ -1,12 +1,25 @@
 use crate::model::{Finding, Rule, Severity};
 use crate::rules::grade::Grade;
+use crate::scanner::matcher::RuleMatcher;
 use crate::scanner::{
-    scan_content, DEFAULT_MAX_FILE_SIZE_BYTES, RULE_ID_BINARY_FILE, RULE_ID_MAX_FILE_SIZE,
+    scan_content_with_matcher, DEFAULT_MAX_FILE_SIZE_BYTES, RULE_ID_BINARY_FILE,
+    RULE_ID_MAX_FILE_SIZE,
 };
 use std::path::Path;
 use veil_config::Config;
 
 pub fn scan_data(path: &Path, data: &[u8], rules: &[Rule], config: &Config) -> Vec<Finding> {
+    scan_data_with_matcher(path, data, &RuleMatcher::new(rules, config), config)
+}
+
+/// [`scan_data`] with a prebuilt matcher, for callers scanning many blobs
+/// with the same rules.
+pub fn scan_data_with_matcher(
+    path: &Path,
+    data: &[u8],
+    matcher: &RuleMatcher,
+    config: &Config,
+) -> Vec<Finding> {
     // 1. Binary Check (Check first 8KB like git)
     let header_len = std::cmp::min(data.len(), 8192);
     if data[..header_len].contains(&0) {
@@ -38,7 +51,7 @@ pub fn scan_data(path: &Path, data: &[u8], rules: &[Rule], config: &Config) -> V
 
     // 3. UTF-8 Validation & Scan
     match std::str::from_utf8(data) {
-        Ok(content) => scan_content(content, path, rules, config),
+        Ok(content) => scan_content_with_matcher(content, path, matcher, config),
         Err(_) => vec![create_skipped_finding(
             path,
             RULE_ID_BINARY_FILE,
@@ -57,6 +70,7 @@ pub fn create_skipped_finding(
     Finding {
         path: path.to_path_buf(),
         line_number: 0,
+        end_line_number: None,
         line_content: msg,
         rule_id: rule_id.to_string(),
         matched_content: "".to_string(),
@@ -79,6 +93,7 @@ pub fn create_skipped_finding(
         commit_sha: None,
         author: None,
         date: None,
+        verification: None,
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::model::{Finding, Rule, Severity};
+use crate::model::{Finding, Rule, Severity, VerificationStatus};
 use crate::rules::grade::Grade;
 use std::collections::HashMap;
 
@@ -70,6 +70,13 @@ pub fn calculate_score(rule: &Rule, finding: &Finding, params: &ScoreParams) ->
         }
     }
 
+    // Live verification outweighs context heuristics
+    match finding.verification {
+        Some(VerificationStatus::Verified) => score += 20,
+        Some(VerificationStatus::Invalid) => score -= 30,
+        Some(VerificationStatus::Unverified) | None => {}
+    }
+
     score.clamp(0, 100) as u32
 }
 
@@ -124,10 +131,14 @@ mod tests {
             validator_id: None,
             validator: None,
             placeholder: None,
+            multiline: false,
+            keywords: Vec::new(),
+            remediation: None,
         };
         let finding = Finding {
             path: std::path::PathBuf::from("."),
             line_number: 1,
+            end_line_number: None,
             line_content: "".to_string(),
             rule_id: "test".to_string(),
             matched_content: "".to_string(),
@@ -142,6 +153,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         };
 
         let score = calculate_score(&rule, &finding, &ScoreParams::default());
@@ -165,10 +177,14 @@ mod tests {
             validator_id: None,
             validator: None,
             placeholder: None,
+            multiline: false,
+            keywords: Vec::new(),
+            remediation: None,
         };
         let mut finding = Finding {
             path: std::path::PathBuf::from("."),
             line_number: 1,
+            end_line_number: None,
             line_content: "some_var".to_string(),
             rule_id: "test".to_string(),
             matched_content: "".to_string(),
@@ -183,6 +199,7 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         };
 
         let params = ScoreParams::default();
@@ -211,6 +228,56 @@ mod tests {
         assert_eq!(calculate_score(&rule, &finding, &params), 70); // 60 + 10
     }
 
+    #[test]
+    fn test_verification_adjusts_score() {
+        let rule = Rule {
+            severity: Severity::Medium, // 60
+            base_score: None,
+            id: "test".to_string(),
+            enabled: true,
+            pattern: regex::Regex::new(".").unwrap(),
+            description: "".to_string(),
+            score: 0,
+            category: "".to_string(),
+            tags: vec![],
+            context_lines_before: 0,
+            context_lines_after: 0,
+            validator_id: None,
+            validator: None,
+            placeholder: None,
+            multiline: false,
+            keywords: Vec::new(),
+            remediation: None,
+        };
+        let mut finding = Finding {
+            path: std::path::PathBuf::from("."),
+            line_number: 1,
+            end_line_number: None,
+            line_content: "some_var".to_string(),
+            rule_id: "test".to_string(),
+            matched_content: "".to_string(),
+            masked_snippet: "".to_string(),
+            severity: Severity::Medium,
+            score: 0,
+            grade: Grade::Low,
+            span: Default::default(),
+            utf16_range: Default::default(),
+            context_before: vec![],
+            context_after: vec![],
+            commit_sha: None,
+            author: None,
+            date: None,
+            verification: Some(VerificationStatus::Verified),
+        };
+        let params = ScoreParams::default();
+
+        assert_eq!(calculate_score(&rule, &finding, &params), 80); // 60 + 20
+        finding.verification = Some(VerificationStatus::Invalid);
+        assert_eq!(calculate_score(&rule, &finding, &params), 30); // 60 - 30
+        finding.verification = Some(VerificationStatus::Unverified);
+        assert_eq!(calculate_score(&rule, &finding, &params), 60);
+    }
+
     #[test]
     fn severity_min_score_matches_contract_thresholds() {
         assert_eq!(severity_min_score(&Severity::Low), 20);
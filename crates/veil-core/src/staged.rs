@@ -0,0 +1,169 @@
+//! Scanning of the git index, as the pre-commit hook (`veil scan --staged`)
+//! sees it.
+//!
+//! Only paths the index adds or modifies relative to `HEAD` are scanned, and
+//! the staged blob is read instead of the working-tree file, so unstaged edits
+//! neither hide nor add findings.
+
+use crate::model::{Finding, Rule};
+use crate::scanner::matcher::RuleMatcher;
+use crate::scanner::path_filter::under_any;
+use crate::scanner::utils::scan_data_with_matcher;
+use crate::scanner::{RULE_ID_BINARY_FILE, RULE_ID_MAX_FILE_SIZE};
+use git2::{Delta, DiffOptions, Repository};
+use std::path::{Path, PathBuf};
+use veil_config::Config;
+
+#[derive(Debug, Default)]
+pub struct StagedScan {
+    /// Findings with paths relative to the repository root.
+    pub findings: Vec<Finding>,
+    pub scanned_files: usize,
+    /// Staged files skipped as binary or over `core.max_file_size`.
+    pub skipped_files: usize,
+    pub max_file_size_reached: bool,
+}
+
+/// Scans the staged files under `prefixes` (relative to the repository root;
+/// empty for all of them).
+pub fn scan_staged(
+    repo_path: &Path,
+    prefixes: &[PathBuf],
+    rules: &[Rule],
+    config: &Config,
+) -> Result<StagedScan, git2::Error> {
+    let repo = Repository::open(repo_path)?;
+    let index = repo.index()?;
+    // Before the first commit everything in the index is new.
+    let head_tree = match repo.head() {
+        Ok(head) => match head.peel_to_tree() {
+            Ok(tree) => Some(tree),
+            Err(_) => return Ok(StagedScan::default()),
+        },
+        Err(_) => None,
+    };
+    let mut diff_opts = DiffOptions::new();
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut diff_opts))?;
+
+    let matcher = RuleMatcher::new(rules, config);
+    let mut scan = StagedScan::default();
+    for delta in diff.deltas() {
+        if !matches!(delta.status(), Delta::Added | Delta::Modified) {
+            continue;
+        }
+        let Some(path) = delta.new_file().path() else {
+            continue;
+        };
+        if !under_any(path, prefixes) {
+            continue;
+        }
+        if let Ok(true) = repo.is_path_ignored(path) {
+            continue;
+        }
+        let Some(blob) = index
+            .get_path(path, 0)
+            .and_then(|entry| repo.find_blob(entry.id).ok())
+        else {
+            continue;
+        };
+
+        let file_findings = scan_data_with_matcher(path, blob.content(), &matcher, config);
+        match file_findings.first().map(|first| first.rule_id.as_str()) {
+            Some(RULE_ID_MAX_FILE_SIZE) => {
+                scan.max_file_size_reached = true;
+                scan.skipped_files += 1;
+            }
+            Some(RULE_ID_BINARY_FILE) => scan.skipped_files += 1,
+            _ => {
+                scan.scanned_files += 1;
+                scan.findings.extend(file_findings);
+            }
+        }
+    }
+
+    Ok(scan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::builtin::get_all_rules;
+    use git2::Signature;
+    use std::fs;
+
+    fn stage(repo: &Repository, path: &str) {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+
+    fn commit_index(repo: &Repository) {
+        let mut index = repo.index().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Dev", "dev@example.com").unwrap();
+        let parents: Vec<_> = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<_> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, "c", &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn scans_staged_blobs_instead_of_working_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let config = Config::default();
+        let rules = get_all_rules(&config, vec![]);
+
+        fs::write(
+            dir.path().join("committed.txt"),
+            "contact test@example.com\n",
+        )
+        .unwrap();
+        stage(&repo, "committed.txt");
+        commit_index(&repo);
+
+        fs::write(
+            dir.path().join("staged.txt"),
+            "contact staged@example.com\n",
+        )
+        .unwrap();
+        stage(&repo, "staged.txt");
+        // Unstaged edits are invisible to the hook.
+        fs::write(dir.path().join("staged.txt"), "clean now\n").unwrap();
+        fs::write(
+            dir.path().join("untracked.txt"),
+            "contact other@example.com\n",
+        )
+        .unwrap();
+
+        let scan = scan_staged(dir.path(), &[], &rules, &config).unwrap();
+
+        assert_eq!(scan.scanned_files, 1);
+        assert_eq!(scan.findings.len(), 1, "{:?}", scan.findings);
+        assert_eq!(scan.findings[0].path, Path::new("staged.txt"));
+        assert_eq!(scan.findings[0].rule_id, "pii.net.email");
+    }
+
+    #[test]
+    fn scans_whole_index_before_first_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let config = Config::default();
+        let rules = get_all_rules(&config, vec![]);
+        fs::write(dir.path().join("a.txt"), "contact test@example.com\n").unwrap();
+        stage(&repo, "a.txt");
+
+        let scan = scan_staged(dir.path(), &[], &rules, &config).unwrap();
+
+        assert_eq!(scan.findings.len(), 1);
+        let outside = scan_staged(dir.path(), &[PathBuf::from("src")], &rules, &config).unwrap();
+        assert_eq!(outside.scanned_files, 0);
+        assert!(outside.findings.is_empty());
+        assert!(scan_staged(&dir.path().join("missing"), &[], &rules, &config).is_err());
+    }
+}
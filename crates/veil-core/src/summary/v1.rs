@@ -1,5 +1,8 @@
+use crate::model::Rule;
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use veil_config::Config;
 
 /// Veil Summary Schema v1
 // NOTE: Enums must use #[derive(Default)] + #[default] variant.
@@ -53,6 +56,47 @@ pub struct ToolInfo {
     pub fingerprint_key_digest: Option<String>,
 }
 
+/// Value for [`ToolInfo::ruleset_digest`]: every field of every rule that can
+/// change what a scan reports. Entries are sorted by rule id, because
+/// `try_get_all_rules` returns rules in `HashMap` order.
+pub fn ruleset_digest(rules: &[Rule]) -> String {
+    let mut sorted: Vec<&Rule> = rules.iter().collect();
+    sorted.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut hasher = Sha256::new();
+    for rule in sorted {
+        let entry = json!([
+            rule.id,
+            rule.enabled,
+            rule.pattern.as_str(),
+            rule.description,
+            rule.severity,
+            rule.score,
+            rule.base_score,
+            rule.category,
+            rule.tags,
+            rule.context_lines_before,
+            rule.context_lines_after,
+            rule.validator_id,
+            rule.placeholder,
+            rule.multiline,
+            rule.keywords,
+        ]);
+        hasher.update(entry.to_string().as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("sha256:{:x}", hasher.finalize())
+}
+
+/// Value for [`ToolInfo::config_digest`]. Object keys are sorted, so the
+/// digest does not depend on `HashMap` iteration order.
+pub fn config_digest(config: &Config) -> String {
+    let canonical = serde_json::to_value(config)
+        .map(|value| value.to_string())
+        .unwrap_or_default();
+    format!("sha256:{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RunInfo {
@@ -100,6 +144,9 @@ pub struct ScanInfo {
     pub duration_ms: u64,
     pub files_scanned: u64,
     pub bytes_scanned: u64,
+    /// Files whose findings were served from the scan cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_hits: Option<u64>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -248,6 +295,7 @@ mod tests {
                 duration_ms: 150,
                 files_scanned: 120,
                 bytes_scanned: 1024000,
+                cache_hits: Some(100),
                 baseline: Some(BaselineInfo {
                     path: "veil.baseline.json".to_string(),
                     digest:
@@ -333,4 +381,24 @@ mod tests {
         // for now just ensuring it serializes is a good first step.
         assert!(json_output.contains("veil.summary.v1"));
     }
+
+    #[test]
+    fn digests_are_stable_and_sensitive_to_changes() {
+        let rules = crate::get_default_rules();
+        assert_eq!(ruleset_digest(&rules), ruleset_digest(&rules));
+        assert!(ruleset_digest(&rules).starts_with("sha256:"));
+        assert_ne!(ruleset_digest(&rules), ruleset_digest(&rules[1..]));
+        let mut reversed = rules.clone();
+        reversed.reverse();
+        assert_eq!(ruleset_digest(&rules), ruleset_digest(&reversed));
+        let mut with_keyword = rules.clone();
+        with_keyword[0].keywords.push("token".to_string());
+        assert_ne!(ruleset_digest(&rules), ruleset_digest(&with_keyword));
+
+        let mut config = Config::default();
+        let before = config_digest(&config);
+        assert_eq!(before, config_digest(&config.clone()));
+        config.entropy.enabled = true;
+        assert_ne!(before, config_digest(&config));
+    }
 }
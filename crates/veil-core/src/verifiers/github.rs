@@ -0,0 +1,66 @@
+use super::Verifier;
+use crate::model::VerificationStatus;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+
+/// Checks GitHub personal access tokens with `GET /user`.
+pub struct GitHubVerifier;
+
+impl Verifier for GitHubVerifier {
+    fn id(&self) -> &'static str {
+        "github"
+    }
+
+    fn default_endpoint(&self) -> &'static str {
+        "https://api.github.com"
+    }
+
+    fn verify(
+        &self,
+        client: &Client,
+        endpoint: &str,
+        secret: &str,
+        _paired: Option<&str>,
+    ) -> VerificationStatus {
+        let response = client
+            .get(format!("{endpoint}/user"))
+            .bearer_auth(secret)
+            .header("Accept", "application/vnd.github+json")
+            .send();
+
+        match response.map(|r| r.status()) {
+            Ok(status) if status.is_success() => VerificationStatus::Verified,
+            Ok(StatusCode::UNAUTHORIZED) => VerificationStatus::Invalid,
+            _ => VerificationStatus::Unverified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifiers::mock;
+
+    #[test]
+    fn sends_token_as_bearer_and_maps_401_to_invalid() {
+        let (url, server) = mock::serve(1, |_| (401, r#"{"message":"Bad credentials"}"#));
+
+        let status = GitHubVerifier.verify(&Client::new(), &url, "ghp_revoked", None);
+
+        assert_eq!(status, VerificationStatus::Invalid);
+        let heads = server.join().unwrap();
+        assert!(heads[0]
+            .to_lowercase()
+            .contains("authorization: bearer ghp_revoked"));
+    }
+
+    #[test]
+    fn rate_limit_is_unverified() {
+        let (url, server) = mock::serve(1, |_| (403, r#"{"message":"rate limited"}"#));
+
+        let status = GitHubVerifier.verify(&Client::new(), &url, "ghp_any", None);
+
+        assert_eq!(status, VerificationStatus::Unverified);
+        server.join().unwrap();
+    }
+}
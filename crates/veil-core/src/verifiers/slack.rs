@@ -0,0 +1,97 @@
+use super::Verifier;
+use crate::model::VerificationStatus;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+/// Slack `auth.test` error codes that mean the token itself is dead.
+const INVALID_TOKEN_ERRORS: &[&str] = &[
+    "invalid_auth",
+    "not_authed",
+    "account_inactive",
+    "token_revoked",
+    "token_expired",
+];
+
+/// Checks Slack tokens with `POST /api/auth.test`.
+pub struct SlackVerifier;
+
+#[derive(Deserialize)]
+struct AuthTestResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl Verifier for SlackVerifier {
+    fn id(&self) -> &'static str {
+        "slack"
+    }
+
+    fn default_endpoint(&self) -> &'static str {
+        "https://slack.com"
+    }
+
+    fn verify(
+        &self,
+        client: &Client,
+        endpoint: &str,
+        secret: &str,
+        _paired: Option<&str>,
+    ) -> VerificationStatus {
+        let response = client
+            .post(format!("{endpoint}/api/auth.test"))
+            .bearer_auth(secret)
+            .send()
+            .and_then(|r| r.json::<AuthTestResponse>());
+
+        match response {
+            Ok(body) if body.ok => VerificationStatus::Verified,
+            Ok(body)
+                if body
+                    .error
+                    .as_deref()
+                    .is_some_and(|error| INVALID_TOKEN_ERRORS.contains(&error)) =>
+            {
+                VerificationStatus::Invalid
+            }
+            _ => VerificationStatus::Unverified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifiers::mock;
+
+    #[test]
+    fn maps_auth_test_responses() {
+        let (url, server) = mock::serve(3, |head| {
+            if head.contains("xoxb-live") {
+                (200, r#"{"ok":true,"team":"veil"}"#)
+            } else if head.contains("xoxb-dead") {
+                (200, r#"{"ok":false,"error":"invalid_auth"}"#)
+            } else {
+                (200, r#"{"ok":false,"error":"ratelimited"}"#)
+            }
+        });
+        let client = Client::new();
+
+        assert_eq!(
+            SlackVerifier.verify(&client, &url, "xoxb-live", None),
+            VerificationStatus::Verified
+        );
+        assert_eq!(
+            SlackVerifier.verify(&client, &url, "xoxb-dead", None),
+            VerificationStatus::Invalid
+        );
+        assert_eq!(
+            SlackVerifier.verify(&client, &url, "xoxb-other", None),
+            VerificationStatus::Unverified
+        );
+        let heads = server.join().unwrap();
+        assert!(heads
+            .iter()
+            .all(|head| head.starts_with("POST /api/auth.test ")));
+    }
+}
@@ -27,6 +27,9 @@ fn test_large_input_performance() {
         validator_id: None,
         validator: None,
         placeholder: None,
+        multiline: false,
+        keywords: Vec::new(),
+        remediation: None,
     };
     let rules = vec![rule];
     let config = Config::default();
@@ -68,6 +71,9 @@ fn test_many_matches_dos() {
         validator_id: None,
         validator: None,
         placeholder: None,
+        multiline: false,
+        keywords: Vec::new(),
+        remediation: None,
     };
     let rules = vec![rule];
     let config = Config::default();
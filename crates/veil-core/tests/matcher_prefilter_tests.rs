@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use veil_config::Config;
+use veil_core::{get_default_rules, RuleMatcher};
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn collect_text_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_text_files(&path, files);
+        } else if let Ok(content) = fs::read_to_string(&path) {
+            if !content.is_empty() {
+                files.push(path);
+            }
+        }
+    }
+}
+
+/// Every rule whose regex matches a fixture line must be among the
+/// prefilter's candidates for that line, otherwise findings are lost.
+#[test]
+fn prefilter_never_drops_a_matching_rule() {
+    let root = workspace_root();
+    let mut rules = get_default_rules();
+    rules.extend(
+        veil_core::rules::pack::load_rule_templates_parallel(
+            &root.join("crates/veil/rules_ja/templates/jp_security_templates_1000"),
+        )
+        .unwrap(),
+    );
+    let config = Config::default();
+    let matcher = RuleMatcher::new(&rules, &config);
+
+    let mut files = Vec::new();
+    collect_text_files(&root.join("tests/data"), &mut files);
+    collect_text_files(&root.join("tests/fixtures"), &mut files);
+    assert!(!files.is_empty());
+
+    let mut checked_matches = 0usize;
+    for path in files {
+        let content = fs::read_to_string(&path).unwrap();
+        for line in content.lines() {
+            let candidates = matcher.candidates(line, None);
+            for rule in matcher.line_rules() {
+                if rule.pattern.is_match(line) {
+                    checked_matches += 1;
+                    assert!(
+                        candidates.iter().any(|candidate| candidate.id == rule.id),
+                        "rule {} matches {:?} in {} but was filtered out",
+                        rule.id,
+                        line,
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+    assert!(checked_matches > 50);
+}
@@ -0,0 +1,545 @@
+//! CVSS base scores from OSV `severity[].score` vector strings.
+//!
+//! CVSS v3.0/v3.1 vectors are scored with the specification's base score
+//! equations. CVSS v4.0 vectors are scored like the FIRST reference
+//! calculator: the macro vector's score from the specification's lookup
+//! table, lowered by the vector's distance from the macro vector's most
+//! severe vectors.
+
+use std::collections::HashMap;
+
+/// Score of a `CVSS:3.0/...`, `CVSS:3.1/...` or `CVSS:4.0/...` vector. `None`
+/// for other versions or if a mandatory base metric is missing or invalid.
+/// v4.0 threat and environmental metrics are applied when present.
+pub fn base_score(vector: &str) -> Option<f64> {
+    let vector = vector.trim();
+    let (prefix, metrics) = vector.split_once('/')?;
+    let metrics = parse_metrics(metrics)?;
+    match prefix {
+        "CVSS:3.0" | "CVSS:3.1" => Some(v3_score(&V3Metrics::from_v3(&metrics)?)),
+        "CVSS:4.0" => v4_score(&V4Metrics::from_v4(&metrics)?),
+        _ => None,
+    }
+}
+
+fn parse_metrics(metrics: &str) -> Option<HashMap<&str, &str>> {
+    metrics
+        .split('/')
+        .map(|pair| pair.split_once(':'))
+        .collect::<Option<HashMap<_, _>>>()
+}
+
+struct V3Metrics {
+    av: f64,
+    ac: f64,
+    /// Privileges required, as (scope unchanged, scope changed) weights.
+    pr: (f64, f64),
+    ui: f64,
+    changed: bool,
+    c: f64,
+    i: f64,
+    a: f64,
+}
+
+impl V3Metrics {
+    fn from_v3(m: &HashMap<&str, &str>) -> Option<Self> {
+        Some(Self {
+            av: attack_vector(m.get("AV")?)?,
+            ac: attack_complexity(m.get("AC")?)?,
+            pr: privileges(m.get("PR")?)?,
+            ui: match *m.get("UI")? {
+                "N" => 0.85,
+                "R" => 0.62,
+                _ => return None,
+            },
+            changed: match *m.get("S")? {
+                "U" => false,
+                "C" => true,
+                _ => return None,
+            },
+            c: impact(m.get("C")?)?,
+            i: impact(m.get("I")?)?,
+            a: impact(m.get("A")?)?,
+        })
+    }
+}
+
+fn attack_vector(value: &str) -> Option<f64> {
+    Some(match value {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    })
+}
+
+fn attack_complexity(value: &str) -> Option<f64> {
+    Some(match value {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    })
+}
+
+fn privileges(value: &str) -> Option<(f64, f64)> {
+    Some(match value {
+        "N" => (0.85, 0.85),
+        "L" => (0.62, 0.68),
+        "H" => (0.27, 0.5),
+        _ => return None,
+    })
+}
+
+fn impact(value: &str) -> Option<f64> {
+    Some(match value {
+        "H" => 0.56,
+        "L" => 0.22,
+        "N" => 0.0,
+        _ => return None,
+    })
+}
+
+/// CVSS v3.1 base score equation.
+fn v3_score(m: &V3Metrics) -> f64 {
+    let iss = 1.0 - (1.0 - m.c) * (1.0 - m.i) * (1.0 - m.a);
+    let impact = if m.changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return 0.0;
+    }
+    let pr = if m.changed { m.pr.1 } else { m.pr.0 };
+    let exploitability = 8.22 * m.av * m.ac * pr * m.ui;
+    if m.changed {
+        roundup((1.08 * (impact + exploitability)).min(10.0))
+    } else {
+        roundup((impact + exploitability).min(10.0))
+    }
+}
+
+/// The specification's `Roundup`: smallest one-decimal number >= `value`,
+/// computed on integers to avoid floating point artefacts.
+fn roundup(value: f64) -> f64 {
+    let int_input = (value * 100_000.0).round() as i64;
+    if int_input % 10_000 == 0 {
+        int_input as f64 / 100_000.0
+    } else {
+        ((int_input / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+/// Severity level of each v4.0 metric value, in the specification's 0.1
+/// steps; 0 is the most severe. `S` (safety) only appears as MSI/MSA.
+const V4_LEVELS: &[(&str, &[(&str, u8)])] = &[
+    ("AV", &[("N", 0), ("A", 1), ("L", 2), ("P", 3)]),
+    ("AC", &[("L", 0), ("H", 1)]),
+    ("AT", &[("N", 0), ("P", 1)]),
+    ("PR", &[("N", 0), ("L", 1), ("H", 2)]),
+    ("UI", &[("N", 0), ("P", 1), ("A", 2)]),
+    ("VC", &[("H", 0), ("L", 1), ("N", 2)]),
+    ("VI", &[("H", 0), ("L", 1), ("N", 2)]),
+    ("VA", &[("H", 0), ("L", 1), ("N", 2)]),
+    ("SC", &[("H", 1), ("L", 2), ("N", 3)]),
+    ("SI", &[("S", 0), ("H", 1), ("L", 2), ("N", 3)]),
+    ("SA", &[("S", 0), ("H", 1), ("L", 2), ("N", 3)]),
+    ("E", &[("A", 0), ("P", 1), ("U", 2)]),
+    ("CR", &[("H", 0), ("M", 1), ("L", 2)]),
+    ("IR", &[("H", 0), ("M", 1), ("L", 2)]),
+    ("AR", &[("H", 0), ("M", 1), ("L", 2)]),
+];
+
+/// The most severe vectors of each equivalence class, per level
+/// (`maxComposed` in the specification). EQ3 and EQ6 are indexed together.
+const V4_EQ1_MAXES: [&[&str]; 3] = [
+    &["AV:N/PR:N/UI:N"],
+    &["AV:A/PR:N/UI:N", "AV:N/PR:L/UI:N", "AV:N/PR:N/UI:P"],
+    &["AV:P/PR:N/UI:N", "AV:A/PR:L/UI:P"],
+];
+const V4_EQ2_MAXES: [&[&str]; 2] = [&["AC:L/AT:N"], &["AC:H/AT:N", "AC:L/AT:P"]];
+const V4_EQ3_EQ6_MAXES: &[((u8, u8), &[&str])] = &[
+    ((0, 0), &["VC:H/VI:H/VA:H/CR:H/IR:H/AR:H"]),
+    (
+        (0, 1),
+        &[
+            "VC:H/VI:H/VA:L/CR:M/IR:M/AR:H",
+            "VC:H/VI:H/VA:H/CR:M/IR:M/AR:M",
+        ],
+    ),
+    (
+        (1, 0),
+        &[
+            "VC:L/VI:H/VA:H/CR:H/IR:H/AR:H",
+            "VC:H/VI:L/VA:H/CR:H/IR:H/AR:H",
+        ],
+    ),
+    (
+        (1, 1),
+        &[
+            "VC:L/VI:H/VA:L/CR:H/IR:M/AR:H",
+            "VC:L/VI:H/VA:H/CR:H/IR:M/AR:M",
+            "VC:H/VI:L/VA:H/CR:M/IR:H/AR:M",
+            "VC:H/VI:L/VA:L/CR:M/IR:H/AR:H",
+            "VC:L/VI:L/VA:H/CR:H/IR:H/AR:M",
+        ],
+    ),
+    ((2, 1), &["VC:L/VI:L/VA:L/CR:H/IR:H/AR:H"]),
+];
+const V4_EQ4_MAXES: [&[&str]; 3] = [
+    &["SC:H/SI:S/SA:S"],
+    &["SC:H/SI:H/SA:H"],
+    &["SC:L/SI:L/SA:L"],
+];
+
+/// Depth of each equivalence class level, in 0.1 steps (`maxSeverity`).
+const V4_EQ1_DEPTH: [u8; 3] = [1, 4, 5];
+const V4_EQ2_DEPTH: [u8; 2] = [1, 2];
+const V4_EQ3_EQ6_DEPTH: &[((u8, u8), u8)] = &[
+    ((0, 0), 7),
+    ((0, 1), 6),
+    ((1, 0), 8),
+    ((1, 1), 8),
+    ((2, 1), 10),
+];
+const V4_EQ4_DEPTH: [u8; 3] = [6, 5, 4];
+
+/// Score of every macro vector (EQ1..EQ6 levels), from the specification.
+#[rustfmt::skip]
+const V4_MACRO_SCORES: &[(&str, f64)] = &[
+    ("000000", 10.0), ("000001", 9.9), ("000010", 9.8), ("000011", 9.5), ("000020", 9.5), ("000021", 9.2),
+    ("000100", 10.0), ("000101", 9.6), ("000110", 9.3), ("000111", 8.7), ("000120", 9.1), ("000121", 8.1),
+    ("000200", 9.3), ("000201", 9.0), ("000210", 8.9), ("000211", 8.0), ("000220", 8.1), ("000221", 6.8),
+    ("001000", 9.8), ("001001", 9.5), ("001010", 9.5), ("001011", 9.2), ("001020", 9.0), ("001021", 8.4),
+    ("001100", 9.3), ("001101", 9.2), ("001110", 8.9), ("001111", 8.1), ("001120", 8.1), ("001121", 6.5),
+    ("001200", 8.8), ("001201", 8.0), ("001210", 7.8), ("001211", 7.0), ("001220", 6.9), ("001221", 4.8),
+    ("002001", 9.2), ("002011", 8.2), ("002021", 7.2), ("002101", 7.9), ("002111", 6.9), ("002121", 5.0),
+    ("002201", 6.9), ("002211", 5.5), ("002221", 2.7),
+    ("010000", 9.9), ("010001", 9.7), ("010010", 9.5), ("010011", 9.2), ("010020", 9.2), ("010021", 8.5),
+    ("010100", 9.5), ("010101", 9.1), ("010110", 9.0), ("010111", 8.3), ("010120", 8.4), ("010121", 7.1),
+    ("010200", 9.2), ("010201", 8.1), ("010210", 8.2), ("010211", 7.1), ("010220", 7.2), ("010221", 5.3),
+    ("011000", 9.5), ("011001", 9.3), ("011010", 9.2), ("011011", 8.5), ("011020", 8.5), ("011021", 7.3),
+    ("011100", 9.2), ("011101", 8.2), ("011110", 8.0), ("011111", 7.2), ("011120", 7.0), ("011121", 5.9),
+    ("011200", 8.4), ("011201", 7.0), ("011210", 7.1), ("011211", 5.2), ("011220", 5.0), ("011221", 3.0),
+    ("012001", 8.6), ("012011", 7.5), ("012021", 5.2), ("012101", 7.1), ("012111", 5.2), ("012121", 2.9),
+    ("012201", 6.3), ("012211", 2.9), ("012221", 1.7),
+    ("100000", 9.8), ("100001", 9.5), ("100010", 9.4), ("100011", 8.7), ("100020", 9.1), ("100021", 8.1),
+    ("100100", 9.4), ("100101", 8.9), ("100110", 8.6), ("100111", 7.4), ("100120", 7.7), ("100121", 6.4),
+    ("100200", 8.7), ("100201", 7.5), ("100210", 7.4), ("100211", 6.3), ("100220", 6.3), ("100221", 4.9),
+    ("101000", 9.4), ("101001", 8.9), ("101010", 8.8), ("101011", 7.7), ("101020", 7.6), ("101021", 6.7),
+    ("101100", 8.6), ("101101", 7.6), ("101110", 7.4), ("101111", 5.8), ("101120", 5.9), ("101121", 5.0),
+    ("101200", 7.2), ("101201", 5.7), ("101210", 5.7), ("101211", 5.2), ("101220", 5.2), ("101221", 2.5),
+    ("102001", 8.3), ("102011", 7.0), ("102021", 5.4), ("102101", 6.5), ("102111", 5.8), ("102121", 2.6),
+    ("102201", 5.3), ("102211", 2.1), ("102221", 1.3),
+    ("110000", 9.5), ("110001", 9.0), ("110010", 8.8), ("110011", 7.6), ("110020", 7.6), ("110021", 7.0),
+    ("110100", 9.0), ("110101", 7.7), ("110110", 7.5), ("110111", 6.2), ("110120", 6.1), ("110121", 5.3),
+    ("110200", 7.7), ("110201", 6.6), ("110210", 6.8), ("110211", 5.9), ("110220", 5.2), ("110221", 3.0),
+    ("111000", 8.9), ("111001", 7.8), ("111010", 7.6), ("111011", 6.7), ("111020", 6.2), ("111021", 5.8),
+    ("111100", 7.4), ("111101", 5.9), ("111110", 5.7), ("111111", 5.7), ("111120", 4.7), ("111121", 2.3),
+    ("111200", 6.1), ("111201", 5.2), ("111210", 5.7), ("111211", 2.9), ("111220", 2.4), ("111221", 1.6),
+    ("112001", 7.1), ("112011", 5.9), ("112021", 3.0), ("112101", 5.8), ("112111", 2.6), ("112121", 1.5),
+    ("112201", 2.3), ("112211", 1.3), ("112221", 0.6),
+    ("200000", 9.3), ("200001", 8.7), ("200010", 8.6), ("200011", 7.2), ("200020", 7.5), ("200021", 5.8),
+    ("200100", 8.6), ("200101", 7.4), ("200110", 7.4), ("200111", 6.1), ("200120", 5.6), ("200121", 3.4),
+    ("200200", 7.0), ("200201", 5.4), ("200210", 5.2), ("200211", 4.0), ("200220", 4.0), ("200221", 2.2),
+    ("201000", 8.5), ("201001", 7.5), ("201010", 7.4), ("201011", 5.5), ("201020", 6.2), ("201021", 5.1),
+    ("201100", 7.2), ("201101", 5.7), ("201110", 5.5), ("201111", 4.1), ("201120", 4.6), ("201121", 1.9),
+    ("201200", 5.3), ("201201", 3.6), ("201210", 3.4), ("201211", 1.9), ("201220", 1.9), ("201221", 0.8),
+    ("202001", 6.4), ("202011", 5.1), ("202021", 2.0), ("202101", 4.7), ("202111", 2.1), ("202121", 1.1),
+    ("202201", 2.4), ("202211", 0.9), ("202221", 0.4),
+    ("210000", 8.8), ("210001", 7.5), ("210010", 7.3), ("210011", 5.3), ("210020", 6.0), ("210021", 5.0),
+    ("210100", 7.3), ("210101", 5.5), ("210110", 5.9), ("210111", 4.0), ("210120", 4.1), ("210121", 2.0),
+    ("210200", 5.4), ("210201", 4.3), ("210210", 4.5), ("210211", 2.2), ("210220", 2.0), ("210221", 1.1),
+    ("211000", 7.5), ("211001", 5.5), ("211010", 5.8), ("211011", 4.5), ("211020", 4.0), ("211021", 2.1),
+    ("211100", 6.1), ("211101", 5.1), ("211110", 4.8), ("211111", 1.8), ("211120", 2.0), ("211121", 0.9),
+    ("211200", 4.6), ("211201", 1.8), ("211210", 1.7), ("211211", 0.7), ("211220", 0.8), ("211221", 0.2),
+    ("212001", 5.3), ("212011", 2.4), ("212021", 1.4), ("212101", 2.4), ("212111", 1.2), ("212121", 0.5),
+    ("212201", 1.0), ("212211", 0.3), ("212221", 0.1),
+];
+
+/// Effective v4.0 metric values: modified environmental metrics replace
+/// their base metric, and unset E/CR/IR/AR count as their worst case.
+struct V4Metrics<'a> {
+    values: HashMap<&'static str, &'a str>,
+}
+
+impl<'a> V4Metrics<'a> {
+    fn from_v4(m: &HashMap<&str, &'a str>) -> Option<Self> {
+        let set = |key: &str| m.get(key).copied().filter(|value| *value != "X");
+        let mut values = HashMap::new();
+        for (metric, levels) in V4_LEVELS {
+            let value = match *metric {
+                "E" => set("E").unwrap_or("A"),
+                "CR" | "IR" | "AR" => set(metric).unwrap_or("H"),
+                _ => {
+                    let base = *m.get(metric)?;
+                    if base == "S" || !levels.iter().any(|(value, _)| *value == base) {
+                        return None;
+                    }
+                    set(&format!("M{metric}")).unwrap_or(base)
+                }
+            };
+            levels.iter().find(|(level, _)| *level == value)?;
+            values.insert(*metric, value);
+        }
+        Some(Self { values })
+    }
+
+    fn is(&self, metric: &str, value: &str) -> bool {
+        self.values.get(metric) == Some(&value)
+    }
+
+    fn level(&self, metric: &str) -> u8 {
+        v4_level(metric, self.values[metric])
+    }
+
+    /// EQ1..EQ6 levels.
+    fn macro_vector(&self) -> [u8; 6] {
+        let eq1 = if self.is("AV", "N") && self.is("PR", "N") && self.is("UI", "N") {
+            0
+        } else if (self.is("AV", "N") || self.is("PR", "N") || self.is("UI", "N"))
+            && !self.is("AV", "P")
+        {
+            1
+        } else {
+            2
+        };
+        let eq2 = if self.is("AC", "L") && self.is("AT", "N") {
+            0
+        } else {
+            1
+        };
+        let eq3 = if self.is("VC", "H") && self.is("VI", "H") {
+            0
+        } else if self.is("VC", "H") || self.is("VI", "H") || self.is("VA", "H") {
+            1
+        } else {
+            2
+        };
+        let eq4 = if self.is("SI", "S") || self.is("SA", "S") {
+            0
+        } else if self.is("SC", "H") || self.is("SI", "H") || self.is("SA", "H") {
+            1
+        } else {
+            2
+        };
+        let eq5 = self.level("E");
+        let eq6 = if (self.is("CR", "H") && self.is("VC", "H"))
+            || (self.is("IR", "H") && self.is("VI", "H"))
+            || (self.is("AR", "H") && self.is("VA", "H"))
+        {
+            0
+        } else {
+            1
+        };
+        [eq1, eq2, eq3, eq4, eq5, eq6]
+    }
+
+    /// Per-metric distance from `max`, or `None` if this vector is more
+    /// severe than `max` in any metric.
+    fn distances_to(&self, max: &HashMap<&str, &str>) -> Option<HashMap<&'static str, u8>> {
+        V4_LEVELS
+            .iter()
+            .filter(|(metric, _)| *metric != "E")
+            .map(|(metric, _)| {
+                let max_level = v4_level(metric, max.get(metric)?);
+                Some((*metric, self.level(metric).checked_sub(max_level)?))
+            })
+            .collect()
+    }
+}
+
+fn v4_level(metric: &str, value: &str) -> u8 {
+    V4_LEVELS
+        .iter()
+        .find(|(name, _)| *name == metric)
+        .and_then(|(_, levels)| levels.iter().find(|(level, _)| *level == value))
+        .map_or(0, |(_, level)| *level)
+}
+
+fn v4_macro_score(macro_vector: [u8; 6]) -> Option<f64> {
+    let key: String = macro_vector.iter().map(u8::to_string).collect();
+    V4_MACRO_SCORES
+        .iter()
+        .find(|(macro_key, _)| *macro_key == key)
+        .map(|(_, score)| *score)
+}
+
+fn v4_eq3_eq6<T: Copy>(table: &[((u8, u8), T)], eq3: u8, eq6: u8) -> Option<T> {
+    table
+        .iter()
+        .find(|(levels, _)| *levels == (eq3, eq6))
+        .map(|(_, value)| *value)
+}
+
+/// CVSS v4.0 score: the macro vector's score minus the mean of the
+/// proportional distances to the next lower macro vector of each
+/// equivalence class.
+fn v4_score(m: &V4Metrics) -> Option<f64> {
+    if ["VC", "VI", "VA", "SC", "SI", "SA"]
+        .iter()
+        .all(|metric| m.is(metric, "N"))
+    {
+        return Some(0.0);
+    }
+
+    let mv = m.macro_vector();
+    let [eq1, eq2, eq3, eq4, _, eq6] = mv.map(usize::from);
+    let value = v4_macro_score(mv)?;
+    let lower = |steps: &[usize]| {
+        let mut next = mv;
+        for &eq in steps {
+            next[eq] += 1;
+        }
+        v4_macro_score(next)
+    };
+    let eq3_eq6_lower = match (eq3, eq6) {
+        // 00 can go down to 01 or 10; take the higher of the two.
+        (0, 0) => lower(&[5]).zip(lower(&[2])).map(|(a, b)| a.max(b)),
+        (0, 1) | (1, 1) => lower(&[2]),
+        (1, 0) => lower(&[5]),
+        _ => lower(&[2, 5]),
+    };
+
+    let eq3_eq6_maxes = v4_eq3_eq6(V4_EQ3_EQ6_MAXES, mv[2], mv[5])?;
+    let distances = V4_EQ1_MAXES[eq1]
+        .iter()
+        .flat_map(|a| V4_EQ2_MAXES[eq2].iter().map(move |b| [*a, *b]))
+        .flat_map(|[a, b]| eq3_eq6_maxes.iter().map(move |c| [a, b, *c]))
+        .flat_map(|[a, b, c]| V4_EQ4_MAXES[eq4].iter().map(move |d| [a, b, c, *d]))
+        .find_map(|parts| {
+            let joined = parts.join("/");
+            let max = parse_metrics(&joined)?;
+            m.distances_to(&max)
+        })?;
+    let sum = |metrics: &[&str]| -> u8 { metrics.iter().map(|metric| distances[metric]).sum() };
+
+    let classes = [
+        (lower(&[0]), sum(&["AV", "PR", "UI"]), V4_EQ1_DEPTH[eq1]),
+        (lower(&[1]), sum(&["AC", "AT"]), V4_EQ2_DEPTH[eq2]),
+        (
+            eq3_eq6_lower,
+            sum(&["VC", "VI", "VA", "CR", "IR", "AR"]),
+            v4_eq3_eq6(V4_EQ3_EQ6_DEPTH, mv[2], mv[5])?,
+        ),
+        (lower(&[3]), sum(&["SC", "SI", "SA"]), V4_EQ4_DEPTH[eq4]),
+        // The threat class has no depth: its distance is always 0.
+        (lower(&[4]), 0, 1),
+    ];
+    let proportional: Vec<f64> = classes
+        .iter()
+        .filter_map(|(lower, distance, depth)| {
+            Some((value - (*lower)?) * f64::from(*distance) / f64::from(*depth))
+        })
+        .collect();
+    let mean = if proportional.is_empty() {
+        0.0
+    } else {
+        proportional.iter().sum::<f64>() / proportional.len() as f64
+    };
+
+    let score = (value - mean).clamp(0.0, 10.0);
+    Some(((score + 1e-6) * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(vector: &str) -> f64 {
+        base_score(vector).unwrap()
+    }
+
+    #[test]
+    fn scores_v3_vectors_like_the_specification() {
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), 9.8);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"), 10.0);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), 6.1);
+        assert_eq!(score("CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H"), 7.5);
+        assert_eq!(score("CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N"), 1.8);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N"), 0.0);
+    }
+
+    #[test]
+    fn scores_v4_vectors_like_the_reference_calculator() {
+        let cases = [
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                9.3,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:H/SI:H/SA:H",
+                10.0,
+            ),
+            (
+                "AV:N/AC:H/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                9.2,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.7,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:N/VA:N/SC:N/SI:N/SA:N",
+                8.7,
+            ),
+            (
+                "AV:L/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.5,
+            ),
+            (
+                "AV:L/AC:L/AT:N/PR:N/UI:P/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.5,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:L/VI:L/VA:L/SC:N/SI:N/SA:N",
+                6.9,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:L/VI:N/VA:N/SC:N/SI:N/SA:N",
+                6.9,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:A/VC:N/VI:N/VA:N/SC:L/SI:L/SA:N",
+                5.1,
+            ),
+            (
+                "AV:L/AC:H/AT:P/PR:H/UI:A/VC:L/VI:N/VA:N/SC:N/SI:N/SA:N",
+                1.0,
+            ),
+            (
+                "AV:N/AC:L/AT:N/PR:N/UI:N/VC:N/VI:N/VA:N/SC:N/SI:N/SA:N",
+                0.0,
+            ),
+        ];
+        for (metrics, expected) in cases {
+            assert_eq!(score(&format!("CVSS:4.0/{metrics}")), expected, "{metrics}");
+        }
+    }
+
+    #[test]
+    fn v4_applies_threat_and_environmental_metrics() {
+        let base = "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N";
+        assert_eq!(score(&format!("{base}/E:X/CR:X/MAV:X")), 9.3);
+        assert!(score(&format!("{base}/E:U")) < 9.3);
+        assert_eq!(
+            score(&format!("{base}/MAV:L/MPR:L")),
+            score("CVSS:4.0/AV:L/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N")
+        );
+        // Supplemental metrics do not change the score.
+        assert_eq!(score(&format!("{base}/S:P/R:U")), 9.3);
+    }
+
+    #[test]
+    fn rejects_incomplete_or_unknown_vectors() {
+        assert!(base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H").is_none());
+        assert!(base_score("CVSS:2.0/AV:N/AC:L/Au:N/C:P/I:P/A:P").is_none());
+        assert!(base_score("AV:N/AC:L/Au:N/C:P/I:P/A:P").is_none());
+        assert!(base_score("CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").is_none());
+        assert!(base_score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N").is_none());
+        assert!(
+            base_score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:S/SA:N").is_none()
+        );
+    }
+}
@@ -15,6 +15,8 @@ pub enum GuardianError {
     LockfileParseError(String),
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("OSV database error: {0}")]
+    DatabaseError(String),
 }
 
 pub struct GuardianDb {
@@ -0,0 +1,388 @@
+//! Accepted advisories.
+//!
+//! The guardian counterpart of `veil_core::registry::Registry`: each entry
+//! accepts one advisory for one package, optionally narrowed to a version
+//! range, with a mandatory reason and an optional expiry. Active entries
+//! move the advisory out of `ScanResult::vulnerabilities` into
+//! `ScanResult::suppressed`; expired entries stop suppressing and are listed
+//! in `ScanResult::expired_exceptions` so the check fails again.
+
+use crate::models::{Advisory, Ecosystem};
+use crate::report::{ScanResult, SuppressedAdvisory, Vulnerability};
+use crate::util::atomic_write::atomic_write_bytes;
+use crate::util::file_lock::with_file_lock;
+use crate::version;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Repository-relative default location, next to `ops/exceptions.toml`.
+pub const DEFAULT_REGISTRY_PATH: &str = "ops/guardian-exceptions.toml";
+
+const CURRENT_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ExceptionRegistryError {
+    #[error("guardian exception registry not found at {0}")]
+    NotFound(PathBuf),
+
+    #[error("failed to parse guardian exception registry at {0}: {1}")]
+    ParseError(PathBuf, String),
+
+    #[error("failed to serialize guardian exception registry: {0}")]
+    SerializationError(String),
+
+    #[error("version mismatch: expected {expected}, found {found}")]
+    VersionMismatch { found: u32, expected: u32 },
+
+    #[error("invalid version range '{0}' (use comparators like '>= 1.0, < 1.4')")]
+    InvalidRange(String),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdvisoryException {
+    /// Advisory id or any of its aliases (`GHSA-...`, `CVE-...`, `RUSTSEC-...`).
+    pub advisory: String,
+    pub package: String,
+    /// Comma-separated comparators (`>= 1.0, < 1.4`); a bare version means
+    /// exactly that version. Absent means every version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<String>,
+    pub reason: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AdvisoryException {
+    /// Entries with the same key replace each other on `add`.
+    pub fn same_key(&self, other: &AdvisoryException) -> bool {
+        self.advisory == other.advisory
+            && self.package == other.package
+            && self.versions == other.versions
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at < now)
+    }
+
+    fn matches(&self, vuln: &Vulnerability, advisory: &Advisory) -> bool {
+        let names_advisory = advisory.id == self.advisory
+            || advisory.aliases.iter().any(|alias| alias == &self.advisory);
+        names_advisory
+            && vuln.package_name == self.package
+            && self
+                .versions
+                .as_deref()
+                .is_none_or(|range| range_matches(vuln.ecosystem, range, &vuln.version))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExceptionRegistry {
+    #[serde(default = "default_version")]
+    pub version: u32,
+
+    #[serde(default)]
+    pub exceptions: Vec<AdvisoryException>,
+}
+
+fn default_version() -> u32 {
+    CURRENT_VERSION
+}
+
+impl Default for ExceptionRegistry {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            exceptions: Vec::new(),
+        }
+    }
+}
+
+impl ExceptionRegistry {
+    pub fn load(path: &Path) -> Result<Self, ExceptionRegistryError> {
+        if !path.exists() {
+            return Err(ExceptionRegistryError::NotFound(path.to_path_buf()));
+        }
+
+        let content = with_file_lock(path, || std::fs::read_to_string(path))?;
+        let registry: ExceptionRegistry = toml::from_str(&content)
+            .map_err(|e| ExceptionRegistryError::ParseError(path.to_path_buf(), e.to_string()))?;
+
+        if registry.version != CURRENT_VERSION {
+            return Err(ExceptionRegistryError::VersionMismatch {
+                found: registry.version,
+                expected: CURRENT_VERSION,
+            });
+        }
+        for entry in &registry.exceptions {
+            if let Some(range) = &entry.versions {
+                validate_range(range)?;
+            }
+        }
+
+        Ok(registry)
+    }
+
+    pub fn save(&mut self, path: &Path) -> Result<(), ExceptionRegistryError> {
+        self.exceptions.sort_by(|a, b| {
+            (&a.advisory, &a.package, &a.versions).cmp(&(&b.advisory, &b.package, &b.versions))
+        });
+
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| ExceptionRegistryError::SerializationError(e.to_string()))?;
+        with_file_lock(path, || atomic_write_bytes(path, content.as_bytes()))?;
+        Ok(())
+    }
+
+    /// Adds `entry`, replacing an existing entry with the same key but
+    /// keeping its creation metadata.
+    pub fn upsert(&mut self, mut entry: AdvisoryException) -> Result<(), ExceptionRegistryError> {
+        if let Some(range) = &entry.versions {
+            validate_range(range)?;
+        }
+        if let Some(existing) = self.exceptions.iter().find(|e| e.same_key(&entry)) {
+            entry.created_at = existing.created_at.or(entry.created_at);
+            entry.created_by = existing.created_by.clone().or(entry.created_by);
+        }
+        self.exceptions.retain(|e| !e.same_key(&entry));
+        self.exceptions.push(entry);
+        Ok(())
+    }
+
+    /// Removes expired entries and returns how many were dropped.
+    pub fn cleanup(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.exceptions.len();
+        self.exceptions.retain(|e| !e.is_expired(now));
+        before - self.exceptions.len()
+    }
+
+    pub fn find(&self, vuln: &Vulnerability, advisory: &Advisory) -> Option<&AdvisoryException> {
+        self.exceptions.iter().find(|e| e.matches(vuln, advisory))
+    }
+
+    /// Moves advisories covered by an active exception out of `result`.
+    /// Packages left without advisories are dropped from the findings.
+    pub fn apply(&self, result: &mut ScanResult, now: DateTime<Utc>) {
+        for vuln in &mut result.vulnerabilities {
+            let mut kept = Vec::new();
+            for advisory in std::mem::take(&mut vuln.advisories) {
+                let Some(entry) = self.find(vuln, &advisory) else {
+                    kept.push(advisory);
+                    continue;
+                };
+                let record = SuppressedAdvisory {
+                    ecosystem: vuln.ecosystem,
+                    package_name: vuln.package_name.clone(),
+                    version: vuln.version.clone(),
+                    advisory_id: advisory.id.clone(),
+                    reason: entry.reason.clone(),
+                    expires_at: entry.expires_at,
+                };
+                if entry.is_expired(now) {
+                    result.expired_exceptions.push(record);
+                    kept.push(advisory);
+                } else {
+                    result.suppressed.push(record);
+                }
+            }
+            vuln.advisories = kept;
+        }
+        result
+            .vulnerabilities
+            .retain(|vuln| !vuln.advisories.is_empty());
+    }
+}
+
+/// Checks the syntax of a `versions` range. Versions themselves are only
+/// parsed against the package's ecosystem when matching.
+pub fn validate_range(range: &str) -> Result<(), ExceptionRegistryError> {
+    let valid = range.split(',').all(|part| !comparator(part).1.is_empty());
+    if valid {
+        Ok(())
+    } else {
+        Err(ExceptionRegistryError::InvalidRange(range.to_string()))
+    }
+}
+
+fn comparator(part: &str) -> (&str, &str) {
+    let part = part.trim();
+    for op in [">=", "<=", ">", "<", "="] {
+        if let Some(rest) = part.strip_prefix(op) {
+            return (op, rest.trim());
+        }
+    }
+    ("=", part)
+}
+
+/// Whether `version` satisfies every comparator in `range`. A version the
+/// ecosystem cannot order never matches, so nothing is suppressed by accident.
+fn range_matches(ecosystem: Ecosystem, range: &str, version: &str) -> bool {
+    range.split(',').all(|part| {
+        let (op, bound) = comparator(part);
+        let Some(order) = version::compare(ecosystem, version, bound) else {
+            return false;
+        };
+        match op {
+            ">=" => order != Ordering::Less,
+            "<=" => order != Ordering::Greater,
+            ">" => order == Ordering::Greater,
+            "<" => order == Ordering::Less,
+            _ => order == Ordering::Equal,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::VersionReq;
+
+    fn advisory(id: &str, aliases: &[&str]) -> Advisory {
+        Advisory {
+            id: id.to_string(),
+            crate_name: "lib".to_string(),
+            vulnerable_versions: VersionReq::STAR,
+            description: String::new(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            details: None,
+            cache_status: None,
+            last_fetched_at: None,
+        }
+    }
+
+    fn exception(
+        advisory: &str,
+        versions: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AdvisoryException {
+        AdvisoryException {
+            advisory: advisory.to_string(),
+            package: "lib".to_string(),
+            versions: versions.map(str::to_string),
+            reason: "not reachable".to_string(),
+            created_at: None,
+            created_by: None,
+            expires_at,
+        }
+    }
+
+    fn result(version: &str) -> ScanResult {
+        ScanResult {
+            vulnerabilities: vec![Vulnerability {
+                ecosystem: Ecosystem::Npm,
+                package_name: "lib".to_string(),
+                version: version.to_string(),
+                advisories: vec![advisory("GHSA-1", &["CVE-2024-1"]), advisory("GHSA-2", &[])],
+                locations: Vec::new(),
+                fixed_version: None,
+                severity: None,
+                cvss_score: None,
+                dependency_paths: Vec::new(),
+            }],
+            scanned_crates: 1,
+            ..ScanResult::default()
+        }
+    }
+
+    #[test]
+    fn active_exception_suppresses_by_alias_and_range() {
+        let registry = ExceptionRegistry {
+            exceptions: vec![exception("CVE-2024-1", Some(">= 1.0.0, < 2.0.0"), None)],
+            ..ExceptionRegistry::default()
+        };
+
+        let mut in_range = result("1.4.0");
+        registry.apply(&mut in_range, Utc::now());
+        assert_eq!(in_range.suppressed.len(), 1);
+        assert_eq!(in_range.suppressed[0].advisory_id, "GHSA-1");
+        let ids: Vec<&str> = in_range.vulnerabilities[0]
+            .advisories
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["GHSA-2"]);
+
+        let mut out_of_range = result("2.0.0");
+        registry.apply(&mut out_of_range, Utc::now());
+        assert!(out_of_range.suppressed.is_empty());
+        assert_eq!(out_of_range.vulnerabilities[0].advisories.len(), 2);
+    }
+
+    #[test]
+    fn fully_suppressed_package_is_clean_until_expiry() {
+        let now = Utc::now();
+        let registry = ExceptionRegistry {
+            exceptions: vec![
+                exception("GHSA-1", None, Some(now + chrono::Duration::days(1))),
+                exception("GHSA-2", Some("1.4.0"), None),
+            ],
+            ..ExceptionRegistry::default()
+        };
+        let mut scan = result("1.4.0");
+        registry.apply(&mut scan, now);
+        assert!(scan.is_clean());
+        assert_eq!(scan.suppressed.len(), 2);
+
+        let mut later = result("1.4.0");
+        registry.apply(&mut later, now + chrono::Duration::days(2));
+        assert!(!later.is_clean());
+        assert_eq!(later.expired_exceptions.len(), 1);
+        assert_eq!(later.expired_exceptions[0].advisory_id, "GHSA-1");
+    }
+
+    #[test]
+    fn upsert_replaces_same_key_and_rejects_bad_ranges() {
+        let mut registry = ExceptionRegistry::default();
+        let mut first = exception("GHSA-1", None, None);
+        first.created_by = Some("alice".to_string());
+        registry.upsert(first).unwrap();
+        registry.upsert(exception("GHSA-1", None, None)).unwrap();
+        assert_eq!(registry.exceptions.len(), 1);
+        assert_eq!(registry.exceptions[0].created_by.as_deref(), Some("alice"));
+
+        assert!(registry
+            .upsert(exception("GHSA-1", Some(">= 1.0, <"), None))
+            .is_err());
+    }
+
+    #[test]
+    fn save_load_roundtrip_and_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ops/guardian-exceptions.toml");
+        let now = Utc::now();
+
+        let mut registry = ExceptionRegistry::default();
+        registry
+            .upsert(exception(
+                "GHSA-2",
+                None,
+                Some(now - chrono::Duration::hours(1)),
+            ))
+            .unwrap();
+        registry.upsert(exception("GHSA-1", None, None)).unwrap();
+        registry.save(&path).unwrap();
+
+        let mut loaded = ExceptionRegistry::load(&path).unwrap();
+        assert_eq!(loaded.exceptions[0].advisory, "GHSA-1");
+        assert_eq!(loaded.cleanup(now), 1);
+        assert_eq!(loaded.exceptions.len(), 1);
+
+        assert!(matches!(
+            ExceptionRegistry::load(&dir.path().join("missing.toml")),
+            Err(ExceptionRegistryError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+//! Dependency graphs recovered from lockfiles, used to explain which direct
+//! dependency pulled a vulnerable transitive package into the project.
+
+use crate::models::PackageRef;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Most chains reported per package. Diamond-shaped graphs can have an
+/// exponential number of equally short chains.
+pub const MAX_PATHS: usize = 3;
+
+/// Packages of one lockfile and the "depends on" edges between them.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// Direct dependencies of the project (or of any workspace member).
+    roots: BTreeSet<PackageRef>,
+    edges: HashMap<PackageRef, BTreeSet<PackageRef>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty() && self.edges.is_empty()
+    }
+
+    pub fn add_root(&mut self, package: PackageRef) {
+        self.roots.insert(package);
+    }
+
+    pub fn add_edge(&mut self, from: PackageRef, to: PackageRef) {
+        if from != to {
+            self.edges.entry(from).or_default().insert(to);
+        }
+    }
+
+    /// The shortest chains from a direct dependency down to `target`, at most
+    /// `limit` of them, each starting at the direct dependency and ending at
+    /// `target`. A direct dependency yields the single chain `[target]`.
+    ///
+    /// Lockfiles that do not record the project's direct dependencies (npm v1,
+    /// yarn classic) fall back to the packages nothing else depends on.
+    pub fn shortest_paths(&self, target: &PackageRef, limit: usize) -> Vec<Vec<PackageRef>> {
+        let roots = self.effective_roots(target);
+        if roots.contains(target) {
+            return vec![vec![target.clone()]];
+        }
+
+        // Breadth-first search from all roots at once, remembering every
+        // predecessor that reaches a node at its shortest distance.
+        let mut depth: HashMap<&PackageRef, usize> = HashMap::new();
+        let mut parents: HashMap<&PackageRef, Vec<&PackageRef>> = HashMap::new();
+        let mut queue: VecDeque<&PackageRef> = VecDeque::new();
+        for root in &roots {
+            depth.insert(root, 0);
+            queue.push_back(root);
+        }
+        while let Some(node) = queue.pop_front() {
+            let next_depth = depth[node] + 1;
+            if depth.get(target).is_some_and(|&d| next_depth > d) {
+                break;
+            }
+            for child in self.edges.get(node).into_iter().flatten() {
+                match depth.get(child) {
+                    None => {
+                        depth.insert(child, next_depth);
+                        parents.entry(child).or_default().push(node);
+                        queue.push_back(child);
+                    }
+                    Some(&d) if d == next_depth => parents.entry(child).or_default().push(node),
+                    Some(_) => {}
+                }
+            }
+        }
+        if !depth.contains_key(target) {
+            return Vec::new();
+        }
+
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![target]];
+        while let Some(partial) = stack.pop() {
+            if paths.len() >= limit {
+                break;
+            }
+            let head = partial[partial.len() - 1];
+            match parents.get(head) {
+                Some(preds) => {
+                    let mut preds = preds.clone();
+                    preds.sort();
+                    for pred in preds.into_iter().rev() {
+                        let mut extended = partial.clone();
+                        extended.push(pred);
+                        stack.push(extended);
+                    }
+                }
+                None => paths.push(partial.into_iter().rev().cloned().collect::<Vec<_>>()),
+            }
+        }
+        paths.sort();
+        paths
+    }
+
+    fn effective_roots<'a>(&'a self, target: &'a PackageRef) -> BTreeSet<&'a PackageRef> {
+        if !self.roots.is_empty() {
+            return self.roots.iter().collect();
+        }
+        let depended_on: HashSet<&PackageRef> = self.edges.values().flatten().collect();
+        self.edges
+            .keys()
+            .chain(std::iter::once(target))
+            .filter(|package| !depended_on.contains(package))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Ecosystem;
+
+    fn pkg(name: &str) -> PackageRef {
+        PackageRef {
+            ecosystem: Ecosystem::Npm,
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn names(paths: Vec<Vec<PackageRef>>) -> Vec<Vec<String>> {
+        paths
+            .into_iter()
+            .map(|path| path.into_iter().map(|p| p.name).collect())
+            .collect()
+    }
+
+    #[test]
+    fn shortest_paths_prefers_the_shortest_chains() {
+        let mut graph = DependencyGraph::new();
+        graph.add_root(pkg("app-a"));
+        graph.add_root(pkg("app-b"));
+        graph.add_edge(pkg("app-a"), pkg("mid"));
+        graph.add_edge(pkg("mid"), pkg("deep"));
+        graph.add_edge(pkg("deep"), pkg("vuln"));
+        graph.add_edge(pkg("app-b"), pkg("other"));
+        graph.add_edge(pkg("other"), pkg("vuln"));
+        graph.add_edge(pkg("mid"), pkg("vuln"));
+
+        assert_eq!(
+            names(graph.shortest_paths(&pkg("vuln"), MAX_PATHS)),
+            vec![vec!["app-a", "mid", "vuln"], vec!["app-b", "other", "vuln"]]
+        );
+        assert_eq!(
+            names(graph.shortest_paths(&pkg("vuln"), 1)),
+            vec![vec!["app-a", "mid", "vuln"]]
+        );
+        assert_eq!(
+            names(graph.shortest_paths(&pkg("app-b"), MAX_PATHS)),
+            vec![vec!["app-b"]]
+        );
+        assert!(graph.shortest_paths(&pkg("missing"), MAX_PATHS).is_empty());
+    }
+
+    #[test]
+    fn shortest_paths_without_roots_starts_at_unreferenced_packages() {
+        let mut graph = DependencyGraph::new();
+        graph.add_edge(pkg("top"), pkg("mid"));
+        graph.add_edge(pkg("mid"), pkg("vuln"));
+        // Cycles must not hide the entry point.
+        graph.add_edge(pkg("vuln"), pkg("mid"));
+
+        assert_eq!(
+            names(graph.shortest_paths(&pkg("vuln"), MAX_PATHS)),
+            vec![vec!["top", "mid", "vuln"]]
+        );
+    }
+}
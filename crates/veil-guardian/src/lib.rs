@@ -1,14 +1,22 @@
 // Re-export public API
+pub mod cvss;
 pub mod db;
+pub mod exceptions;
+pub mod graph;
 pub mod guardian_next;
+pub mod license;
 pub mod metrics;
 pub mod models;
 pub mod providers;
+pub mod purl;
+pub mod remediation;
 pub mod report;
+pub mod sbom;
 pub mod scanner;
 pub mod util;
+pub mod version;
 
 pub use db::GuardianDb;
 pub use db::GuardianError;
 pub use metrics::Metrics;
-pub use scanner::{scan_lockfile, ScanOptions};
+pub use scanner::{is_supported_lockfile, scan_lockfile, scan_lockfiles, ScanOptions};
@@ -0,0 +1,361 @@
+//! License compliance: SPDX license expressions, where a package's declared
+//! license can be found on disk, and the allow/deny policy checked against it.
+
+use std::path::{Path, PathBuf};
+
+/// A parsed SPDX license expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseExpr {
+    /// A license identifier, including any `WITH` exception
+    /// (`Apache-2.0 WITH LLVM-exception`).
+    License(String),
+    And(Vec<LicenseExpr>),
+    Or(Vec<LicenseExpr>),
+}
+
+impl LicenseExpr {
+    /// Parses an SPDX expression. Operators are matched case-insensitively
+    /// and the legacy `MIT/Apache-2.0` form used by old crates reads as `OR`.
+    pub fn parse(expression: &str) -> Option<Self> {
+        let normalized = expression
+            .replace('/', " OR ")
+            .replace('(', " ( ")
+            .replace(')', " ) ");
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        let mut pos = 0;
+        let expr = parse_or(&tokens, &mut pos)?;
+        (pos == tokens.len()).then_some(expr)
+    }
+
+    /// Whether some choice of licenses satisfies the expression using only
+    /// licenses `acceptable` accepts.
+    fn satisfied_by(&self, acceptable: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            LicenseExpr::License(id) => acceptable(id),
+            LicenseExpr::And(terms) => terms.iter().all(|t| t.satisfied_by(acceptable)),
+            LicenseExpr::Or(terms) => terms.iter().any(|t| t.satisfied_by(acceptable)),
+        }
+    }
+
+    fn licenses<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            LicenseExpr::License(id) => {
+                if !out.contains(&id.as_str()) {
+                    out.push(id);
+                }
+            }
+            LicenseExpr::And(terms) | LicenseExpr::Or(terms) => {
+                for term in terms {
+                    term.licenses(out);
+                }
+            }
+        }
+    }
+}
+
+fn parse_or(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let mut terms = vec![parse_and(tokens, pos)?];
+    while tokens
+        .get(*pos)
+        .is_some_and(|t| t.eq_ignore_ascii_case("OR"))
+    {
+        *pos += 1;
+        terms.push(parse_and(tokens, pos)?);
+    }
+    Some(if terms.len() == 1 {
+        terms.remove(0)
+    } else {
+        LicenseExpr::Or(terms)
+    })
+}
+
+fn parse_and(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let mut terms = vec![parse_with(tokens, pos)?];
+    while tokens
+        .get(*pos)
+        .is_some_and(|t| t.eq_ignore_ascii_case("AND"))
+    {
+        *pos += 1;
+        terms.push(parse_with(tokens, pos)?);
+    }
+    Some(if terms.len() == 1 {
+        terms.remove(0)
+    } else {
+        LicenseExpr::And(terms)
+    })
+}
+
+fn parse_with(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    if token == "(" {
+        let inner = parse_or(tokens, pos)?;
+        if tokens.get(*pos) != Some(&")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(inner);
+    }
+    if is_operator(token) {
+        return None;
+    }
+    if tokens
+        .get(*pos)
+        .is_some_and(|t| t.eq_ignore_ascii_case("WITH"))
+    {
+        let exception = tokens.get(*pos + 1).filter(|t| !is_operator(t))?;
+        *pos += 2;
+        return Some(LicenseExpr::License(format!(
+            "{} WITH {}",
+            token, exception
+        )));
+    }
+    Some(LicenseExpr::License(token.to_string()))
+}
+
+fn is_operator(token: &str) -> bool {
+    token == "("
+        || token == ")"
+        || ["AND", "OR", "WITH"]
+            .iter()
+            .any(|op| token.eq_ignore_ascii_case(op))
+}
+
+/// Allowed and denied SPDX license identifiers (`[guardian.licenses]` in
+/// `veil.toml`).
+#[derive(Debug, Clone, Default)]
+pub struct LicensePolicy {
+    /// When non-empty, only these licenses are acceptable.
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    /// Report packages whose license could not be determined.
+    pub fail_on_unknown: bool,
+}
+
+impl LicensePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty() && !self.fail_on_unknown
+    }
+
+    /// Why a package declaring `license` breaks the policy, or `None` when it
+    /// complies. An expression complies when one of its alternatives uses
+    /// acceptable licenses only.
+    pub fn check(&self, license: Option<&str>) -> Option<String> {
+        let Some(license) = license else {
+            return self
+                .fail_on_unknown
+                .then(|| "license could not be determined".to_string());
+        };
+        let expr = LicenseExpr::parse(license)
+            .unwrap_or_else(|| LicenseExpr::License(license.trim().to_string()));
+        if expr.satisfied_by(&|id| self.accepts(id)) {
+            return None;
+        }
+
+        let mut ids = Vec::new();
+        expr.licenses(&mut ids);
+        let denied: Vec<&str> = ids
+            .iter()
+            .copied()
+            .filter(|id| listed(&self.deny, id))
+            .collect();
+        if !denied.is_empty() {
+            return Some(format!("denied: {}", denied.join(", ")));
+        }
+        let unlisted: Vec<&str> = ids
+            .into_iter()
+            .filter(|id| !listed(&self.allow, id))
+            .collect();
+        Some(format!("not allowed: {}", unlisted.join(", ")))
+    }
+
+    fn accepts(&self, id: &str) -> bool {
+        !listed(&self.deny, id) && (self.allow.is_empty() || listed(&self.allow, id))
+    }
+}
+
+/// Matches `id` against a list, where listing a license also covers it
+/// with an exception attached.
+fn listed(list: &[String], id: &str) -> bool {
+    let base = id.split(" WITH ").next().unwrap_or(id);
+    list.iter()
+        .any(|entry| entry.eq_ignore_ascii_case(id) || entry.eq_ignore_ascii_case(base))
+}
+
+/// The `license` of a crate, read from its manifest in a `vendor/`
+/// directory next to the lockfile (`cargo vendor`, with or without
+/// `--versioned-dirs`) or in the local Cargo registry source cache.
+pub fn cargo_license(lockfile_dir: &Path, name: &str, version: &str) -> Option<String> {
+    let versioned = format!("{}-{}", name, version);
+    let vendor = lockfile_dir.join("vendor");
+    let mut candidates = vec![vendor.join(name), vendor.join(&versioned)];
+    if let Some(cargo_home) = cargo_home() {
+        if let Ok(registries) = std::fs::read_dir(cargo_home.join("registry").join("src")) {
+            candidates.extend(
+                registries
+                    .flatten()
+                    .map(|entry| entry.path().join(&versioned)),
+            );
+        }
+    }
+
+    candidates.into_iter().find_map(|dir| {
+        let content = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        let manifest: toml::Value = toml::from_str(&content).ok()?;
+        let package = manifest.get("package")?;
+        if package.get("version").and_then(|v| v.as_str()) != Some(version) {
+            return None;
+        }
+        package
+            .get("license")
+            .and_then(|l| l.as_str())
+            .map(str::to_string)
+    })
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".cargo")))
+}
+
+/// The license of an installed npm package, from
+/// `node_modules/<name>/package.json` next to the lockfile.
+pub fn node_modules_license(lockfile_dir: &Path, name: &str, version: &str) -> Option<String> {
+    let manifest = lockfile_dir
+        .join("node_modules")
+        .join(name)
+        .join("package.json");
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    if package.get("version").and_then(|v| v.as_str()) != Some(version) {
+        return None;
+    }
+    npm_license(&package)
+}
+
+/// Reads an npm `license` field (`"MIT"` or the deprecated `{ "type": "MIT" }`)
+/// or the deprecated `licenses` array, whose entries are alternatives.
+pub fn npm_license(package: &serde_json::Value) -> Option<String> {
+    fn license_type(value: &serde_json::Value) -> Option<&str> {
+        value
+            .as_str()
+            .or_else(|| value.get("type").and_then(|t| t.as_str()))
+    }
+
+    if let Some(license) = package.get("license").and_then(license_type) {
+        return Some(license.to_string());
+    }
+    let alternatives: Vec<&str> = package
+        .get("licenses")?
+        .as_array()?
+        .iter()
+        .filter_map(license_type)
+        .collect();
+    match alternatives.len() {
+        0 => None,
+        1 => Some(alternatives[0].to_string()),
+        _ => Some(format!("({})", alternatives.join(" OR "))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> LicensePolicy {
+        LicensePolicy {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+            fail_on_unknown: false,
+        }
+    }
+
+    #[test]
+    fn parses_spdx_expressions() {
+        let license = |id: &str| LicenseExpr::License(id.to_string());
+        assert_eq!(
+            LicenseExpr::parse("MIT OR (Apache-2.0 AND BSD-3-Clause)"),
+            Some(LicenseExpr::Or(vec![
+                license("MIT"),
+                LicenseExpr::And(vec![license("Apache-2.0"), license("BSD-3-Clause")]),
+            ]))
+        );
+        assert_eq!(
+            LicenseExpr::parse("MIT/Apache-2.0"),
+            Some(LicenseExpr::Or(vec![license("MIT"), license("Apache-2.0")]))
+        );
+        assert_eq!(
+            LicenseExpr::parse("Apache-2.0 WITH LLVM-exception"),
+            Some(license("Apache-2.0 WITH LLVM-exception"))
+        );
+        assert_eq!(LicenseExpr::parse("MIT AND"), None);
+        assert_eq!(LicenseExpr::parse("(MIT"), None);
+    }
+
+    #[test]
+    fn policy_accepts_any_satisfiable_alternative() {
+        let policy = policy(&["MIT", "Apache-2.0"], &["GPL-3.0-only"]);
+        assert_eq!(policy.check(Some("MIT")), None);
+        assert_eq!(policy.check(Some("GPL-3.0-only OR MIT")), None);
+        assert_eq!(policy.check(Some("Apache-2.0 WITH LLVM-exception")), None);
+        assert_eq!(
+            policy.check(Some("MIT AND GPL-3.0-only")),
+            Some("denied: GPL-3.0-only".to_string())
+        );
+        assert_eq!(
+            policy.check(Some("ISC OR BSD-2-Clause")),
+            Some("not allowed: ISC, BSD-2-Clause".to_string())
+        );
+        assert_eq!(policy.check(None), None);
+    }
+
+    #[test]
+    fn deny_only_policy_allows_everything_else() {
+        let mut policy = policy(&[], &["AGPL-3.0-or-later"]);
+        assert_eq!(policy.check(Some("LicenseRef-Proprietary")), None);
+        assert_eq!(
+            policy.check(Some("agpl-3.0-or-later")),
+            Some("denied: agpl-3.0-or-later".to_string())
+        );
+
+        policy.fail_on_unknown = true;
+        assert_eq!(
+            policy.check(None),
+            Some("license could not be determined".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_npm_license_fields() {
+        let read = |json: &str| npm_license(&serde_json::from_str(json).unwrap());
+        assert_eq!(read(r#"{ "license": "MIT" }"#), Some("MIT".to_string()));
+        assert_eq!(
+            read(r#"{ "license": { "type": "ISC" } }"#),
+            Some("ISC".to_string())
+        );
+        assert_eq!(
+            read(r#"{ "licenses": [ { "type": "MIT" }, { "type": "Apache-2.0" } ] }"#),
+            Some("(MIT OR Apache-2.0)".to_string())
+        );
+        assert_eq!(read(r#"{}"#), None);
+    }
+
+    #[test]
+    fn reads_vendored_cargo_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let crate_dir = dir.path().join("vendor").join("demo");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"1.2.3\"\nlicense = \"MIT OR Apache-2.0\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            cargo_license(dir.path(), "demo", "1.2.3"),
+            Some("MIT OR Apache-2.0".to_string())
+        );
+        assert_eq!(cargo_license(dir.path(), "demo", "2.0.0"), None);
+    }
+}
@@ -26,6 +26,9 @@ pub struct Metrics {
     pub cache_quarantine_corrupt: AtomicU64,
     pub cache_quarantine_unsupported: AtomicU64,
     pub cache_quarantine_conflict: AtomicU64,
+    // counts - imported offline database
+    pub offline_db_hits: AtomicU64,
+    pub offline_db_age_secs: AtomicU64,
 
     // counts - net
     pub net_fetched: AtomicU64,
@@ -67,6 +70,8 @@ impl Metrics {
             cache_quarantine_corrupt: self.cache_quarantine_corrupt.load(Ordering::Relaxed),
             cache_quarantine_unsupported: self.cache_quarantine_unsupported.load(Ordering::Relaxed),
             cache_quarantine_conflict: self.cache_quarantine_conflict.load(Ordering::Relaxed),
+            offline_db_hits: self.offline_db_hits.load(Ordering::Relaxed),
+            offline_db_age_secs: self.offline_db_age_secs.load(Ordering::Relaxed),
 
             net_fetched: self.net_fetched.load(Ordering::Relaxed),
             net_not_modified: self.net_not_modified.load(Ordering::Relaxed),
@@ -118,6 +123,8 @@ pub struct MetricsSnapshot {
     pub cache_quarantine_corrupt: u64,
     pub cache_quarantine_unsupported: u64,
     pub cache_quarantine_conflict: u64,
+    pub offline_db_hits: u64,
+    pub offline_db_age_secs: u64,
 
     pub net_fetched: u64,
     pub net_not_modified: u64,
@@ -174,6 +181,14 @@ impl std::fmt::Display for Metrics {
                 snap.cache_quarantine_conflict
             )?;
         }
+        if snap.offline_db_hits > 0 {
+            writeln!(
+                f,
+                "    Offline DB:  {} hits, {}d old",
+                snap.offline_db_hits,
+                snap.offline_db_age_secs / 86_400
+            )?;
+        }
         writeln!(
             f,
             "    Network:     {} fetch, {} 304, {} retry, {} 429",
@@ -3,22 +3,52 @@ use serde::{Deserialize, Serialize};
 
 use std::fmt;
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Ecosystem {
     Rust,
     Npm,
+    PyPI,
+    Go,
+    Maven,
 }
 
-impl fmt::Display for Ecosystem {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Ecosystem {
+    pub const ALL: [Ecosystem; 5] = [
+        Ecosystem::Rust,
+        Ecosystem::Npm,
+        Ecosystem::PyPI,
+        Ecosystem::Go,
+        Ecosystem::Maven,
+    ];
+
+    /// The ecosystem name OSV expects in queries.
+    pub fn osv_name(&self) -> &'static str {
         match self {
-            Ecosystem::Rust => write!(f, "crates.io"),
-            Ecosystem::Npm => write!(f, "npm"),
+            Ecosystem::Rust => "crates.io",
+            Ecosystem::Npm => "npm",
+            Ecosystem::PyPI => "PyPI",
+            Ecosystem::Go => "Go",
+            Ecosystem::Maven => "Maven",
         }
     }
+
+    /// The ecosystem of an OSV `affected[].package.ecosystem` value. Release
+    /// suffixes (`Debian:11`) are ignored.
+    pub fn from_osv_name(name: &str) -> Option<Self> {
+        let base = name.split(':').next().unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|ecosystem| ecosystem.osv_name() == base)
+    }
+}
+
+impl fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.osv_name())
+    }
 }
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
 pub struct PackageRef {
     pub ecosystem: Ecosystem,
     pub name: String,
@@ -32,6 +62,9 @@ pub struct Advisory {
     #[serde(with = "serde_version_req")]
     pub vulnerable_versions: VersionReq,
     pub description: String,
+    /// Other identifiers for the same advisory (CVE, GHSA, RUSTSEC, ...).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -40,6 +73,35 @@ pub struct Advisory {
     pub last_fetched_at: Option<u64>,
 }
 
+impl Advisory {
+    /// Whether `other` describes the same advisory, matching ids and aliases
+    /// in either direction.
+    pub fn is_same_advisory(&self, other: &Advisory) -> bool {
+        let ids = |a: &'_ Advisory| {
+            std::iter::once(a.id.clone())
+                .chain(a.aliases.iter().cloned())
+                .collect::<Vec<_>>()
+        };
+        let mine = ids(self);
+        ids(other).iter().any(|id| mine.contains(id))
+    }
+}
+
+/// Appends `advisory` unless `list` already holds the same advisory under any
+/// of its ids; in that case only the aliases are folded into the kept entry.
+pub fn push_unique_advisory(list: &mut Vec<Advisory>, advisory: Advisory) {
+    match list.iter_mut().find(|a| a.is_same_advisory(&advisory)) {
+        Some(existing) => {
+            for alias in std::iter::once(advisory.id).chain(advisory.aliases) {
+                if alias != existing.id && !existing.aliases.contains(&alias) {
+                    existing.aliases.push(alias);
+                }
+            }
+        }
+        None => list.push(advisory),
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DatabaseSchema {
     pub schema_version: u32,
@@ -0,0 +1,163 @@
+use crate::models::{Ecosystem, PackageRef};
+use crate::GuardianError;
+use std::collections::{HashMap, HashSet};
+
+/// Parses a `go.sum` file content and extracts module references.
+///
+/// Lines for `<version>/go.mod` only record the module graph; the module's
+/// code is not part of the build unless a line without the suffix exists.
+pub fn parse_go_sum(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let mut modules = HashSet::new();
+
+    for (lineno, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(module), Some(version), Some(_hash)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(GuardianError::LockfileParseError(format!(
+                "go.sum line {}: expected `<module> <version> <hash>`",
+                lineno + 1
+            )));
+        };
+        if version.ends_with("/go.mod") {
+            continue;
+        }
+        modules.insert((module.to_string(), version.to_string()));
+    }
+
+    Ok(to_refs(modules))
+}
+
+/// Parses a `go.mod` file content: `require` directives, with `replace`
+/// directives applied. Modules replaced by a local path are skipped.
+pub fn parse_go_mod(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let mut requires = Vec::new();
+    let mut replaces: HashMap<String, Option<(String, String)>> = HashMap::new();
+    let mut block: Option<&str> = None;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (directive, spec) = match block {
+            Some(_) if line == ")" => {
+                block = None;
+                continue;
+            }
+            Some(directive) => (directive, line),
+            None => {
+                let (directive, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+                let rest = rest.trim();
+                if rest == "(" {
+                    block = Some(directive);
+                    continue;
+                }
+                (directive, rest)
+            }
+        };
+
+        match directive {
+            "require" => {
+                let mut fields = spec.split_whitespace();
+                if let (Some(module), Some(version)) = (fields.next(), fields.next()) {
+                    requires.push((module.to_string(), version.to_string()));
+                }
+            }
+            "replace" => {
+                let Some((old, new)) = spec.split_once("=>") else {
+                    continue;
+                };
+                let old_module = old.split_whitespace().next().unwrap_or_default();
+                let mut new_fields = new.split_whitespace();
+                let target = match (new_fields.next(), new_fields.next()) {
+                    (Some(module), Some(version)) => {
+                        Some((module.to_string(), version.to_string()))
+                    }
+                    _ => None,
+                };
+                replaces.insert(old_module.to_string(), target);
+            }
+            _ => {}
+        }
+    }
+
+    let modules = requires
+        .into_iter()
+        .filter_map(|(module, version)| match replaces.get(&module) {
+            Some(target) => target.clone(),
+            None => Some((module, version)),
+        })
+        .collect();
+    Ok(to_refs(modules))
+}
+
+/// OSV lists Go versions without the `v` prefix.
+fn to_refs(modules: HashSet<(String, String)>) -> Vec<PackageRef> {
+    let mut refs: Vec<PackageRef> = modules
+        .into_iter()
+        .map(|(name, version)| PackageRef {
+            ecosystem: Ecosystem::Go,
+            name,
+            version: version
+                .strip_prefix('v')
+                .unwrap_or(&version)
+                .trim_end_matches("+incompatible")
+                .to_string(),
+        })
+        .collect();
+    refs.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(refs: &[PackageRef]) -> Vec<(&str, &str)> {
+        refs.iter()
+            .map(|r| (r.name.as_str(), r.version.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_go_sum_fixture() {
+        let content = include_str!("../../../../tests/fixtures/go/go.sum");
+        let result = parse_go_sum(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("github.com/docker/docker", "20.10.0"),
+                ("github.com/gin-gonic/gin", "1.6.3"),
+                ("golang.org/x/crypto", "0.0.0-20200622213623-75b288015ac9"),
+            ]
+        );
+        assert!(result.iter().all(|r| r.ecosystem == Ecosystem::Go));
+    }
+
+    #[test]
+    fn test_go_mod_fixture_applies_replaces() {
+        let content = include_str!("../../../../tests/fixtures/go/go.mod");
+        let result = parse_go_mod(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("github.com/gin-gonic/gin", "1.6.3"),
+                ("github.com/sirupsen/logrus", "1.8.1"),
+                ("golang.org/x/crypto", "0.0.0-20200622213623-75b288015ac9"),
+                ("golang.org/x/net", "0.7.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_sum_rejects_malformed_lines() {
+        assert!(parse_go_sum("github.com/a/b v1.0.0\n").is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use crate::models::{Ecosystem, PackageRef};
+use crate::GuardianError;
+use std::collections::{HashMap, HashSet};
+
+/// Parses a `gradle.lockfile` content (`group:artifact:version=configurations`).
+pub fn parse_gradle_lockfile(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let mut packages = HashSet::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("empty=") {
+            continue;
+        }
+        let coordinates = line.split('=').next().unwrap_or_default();
+        let mut parts = coordinates.split(':');
+        if let (Some(group), Some(artifact), Some(version)) =
+            (parts.next(), parts.next(), parts.next())
+        {
+            packages.insert((format!("{}:{}", group, artifact), version.to_string()));
+        }
+    }
+
+    Ok(to_refs(packages))
+}
+
+/// Parses a `pom.xml` content and extracts `<dependency>` coordinates.
+///
+/// `${...}` placeholders are resolved from `<properties>` and the project's
+/// own version. Dependencies without a concrete version (managed by a parent
+/// or BOM, or given as a range) are skipped since there is nothing to check.
+pub fn parse_pom_xml(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let xml = strip_comments(content);
+    if !xml.contains("<project") {
+        return Err(GuardianError::LockfileParseError(
+            "pom.xml has no <project> element".to_string(),
+        ));
+    }
+
+    let mut properties = HashMap::new();
+    if let Some(block) = elements(&xml, "properties").first() {
+        properties.extend(child_elements(block));
+    }
+    let parent = elements(&xml, "parent").first().map(|s| s.to_string());
+    let mut project = xml.clone();
+    for tag in [
+        "parent",
+        "dependencies",
+        "dependencyManagement",
+        "build",
+        "profiles",
+        "reporting",
+        "properties",
+    ] {
+        project = remove_elements(&project, tag);
+    }
+    let parent_version = parent.as_deref().and_then(|p| child_text(p, "version"));
+    if let Some(version) = child_text(&project, "version").or(parent_version.clone()) {
+        properties.insert("project.version".to_string(), version);
+    }
+    if let Some(version) = parent_version {
+        properties.insert("project.parent.version".to_string(), version);
+    }
+
+    let mut packages = HashSet::new();
+    for dependency in elements(&xml, "dependency") {
+        let dependency = remove_elements(dependency, "exclusions");
+        let group = child_text(&dependency, "groupId").map(|v| resolve(&v, &properties));
+        let artifact = child_text(&dependency, "artifactId").map(|v| resolve(&v, &properties));
+        let version = child_text(&dependency, "version").map(|v| resolve(&v, &properties));
+        let (Some(group), Some(artifact), Some(version)) = (group, artifact, version) else {
+            continue;
+        };
+        let concrete = |v: &str| !v.is_empty() && !v.contains("${") && !v.contains(['[', '(']);
+        if concrete(&group) && concrete(&artifact) && concrete(&version) {
+            packages.insert((format!("{}:{}", group, artifact), version));
+        }
+    }
+
+    Ok(to_refs(packages))
+}
+
+fn resolve(value: &str, properties: &HashMap<String, String>) -> String {
+    let mut value = value.to_string();
+    // Properties may refer to other properties; bound the expansion.
+    for _ in 0..8 {
+        let Some(start) = value.find("${") else {
+            break;
+        };
+        let Some(len) = value[start..].find('}') else {
+            break;
+        };
+        let key = &value[start + 2..start + len];
+        let Some(replacement) = properties.get(key) else {
+            break;
+        };
+        value.replace_range(start..start + len + 1, &replacement.clone());
+    }
+    value
+}
+
+fn strip_comments(xml: &str) -> String {
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        rest = match rest[start..].find("-->") {
+            Some(end) => &rest[start + end + 3..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Inner text of every `<tag>...</tag>` element. POM elements of interest
+/// never nest inside themselves, so the first closing tag ends each one.
+fn elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let body = &rest[start + open.len()..];
+        let Some(end) = body.find(&close) else {
+            break;
+        };
+        found.push(&body[..end]);
+        rest = &body[end + close.len()..];
+    }
+    found
+}
+
+fn remove_elements(xml: &str, tag: &str) -> String {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        out.push_str(&rest[..start]);
+        rest = match rest[start..].find(&close) {
+            Some(end) => &rest[start + end + close.len()..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+fn child_text(xml: &str, tag: &str) -> Option<String> {
+    elements(xml, tag)
+        .first()
+        .map(|text| text.trim().to_string())
+}
+
+/// `<name>value</name>` pairs directly inside a block such as `<properties>`.
+fn child_elements(xml: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        let Some(name_end) = after.find('>') else {
+            break;
+        };
+        let name = &after[..name_end];
+        let body = &after[name_end + 1..];
+        let close = format!("</{}>", name);
+        match body.find(&close) {
+            Some(end) if !name.starts_with(['/', '?', '!']) && !name.ends_with('/') => {
+                pairs.push((name.to_string(), body[..end].trim().to_string()));
+                rest = &body[end + close.len()..];
+            }
+            _ => rest = body,
+        }
+    }
+    pairs
+}
+
+fn to_refs(packages: HashSet<(String, String)>) -> Vec<PackageRef> {
+    let mut refs: Vec<PackageRef> = packages
+        .into_iter()
+        .map(|(name, version)| PackageRef {
+            ecosystem: Ecosystem::Maven,
+            name,
+            version,
+        })
+        .collect();
+    refs.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(refs: &[PackageRef]) -> Vec<(&str, &str)> {
+        refs.iter()
+            .map(|r| (r.name.as_str(), r.version.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_pom_fixture_resolves_properties() {
+        let content = include_str!("../../../../tests/fixtures/maven/pom.xml");
+        let result = parse_pom_xml(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("com.example:service-common", "1.4.0"),
+                ("com.fasterxml.jackson.core:jackson-databind", "2.9.10"),
+                ("junit:junit", "4.12"),
+                ("org.apache.logging.log4j:log4j-core", "2.14.1"),
+            ]
+        );
+        assert!(result.iter().all(|r| r.ecosystem == Ecosystem::Maven));
+    }
+
+    #[test]
+    fn test_gradle_lockfile_fixture() {
+        let content = include_str!("../../../../tests/fixtures/maven/gradle.lockfile");
+        let result = parse_gradle_lockfile(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("com.fasterxml.jackson.core:jackson-databind", "2.9.10"),
+                ("org.apache.logging.log4j:log4j-api", "2.14.1"),
+                ("org.apache.logging.log4j:log4j-core", "2.14.1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pom_requires_project_element() {
+        assert!(parse_pom_xml("<settings></settings>").is_err());
+    }
+}
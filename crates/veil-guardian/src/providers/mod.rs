@@ -1,4 +1,8 @@
+pub mod go;
+pub mod maven;
 pub mod npm;
 pub mod osv;
 pub mod pnpm;
+pub mod python;
+pub mod sbom;
 pub mod yarn;
@@ -1,3 +1,5 @@
+use crate::graph::DependencyGraph;
+use crate::license;
 use crate::models::{Ecosystem, PackageRef};
 use crate::GuardianError;
 use serde::Deserialize;
@@ -21,20 +23,41 @@ struct PackageLock {
 #[derive(Debug, Deserialize)]
 struct DependencyV1 {
     version: String,
+    /// Names this package depends on, resolved like `require()` would.
+    #[serde(default)]
+    requires: HashMap<String, String>,
     #[serde(default)]
     dependencies: HashMap<String, DependencyV1>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct PackageV2 {
+    // Absent on workspace links and on roots without a version.
+    #[serde(default)]
     version: String,
     #[serde(default)]
     link: bool,
     #[allow(dead_code)]
     name: Option<String>, // Sometimes present, but usually inferred from key
+    #[serde(default)]
+    dependencies: HashMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: HashMap<String, String>,
+    #[serde(default)]
+    dev_dependencies: HashMap<String, String>,
 }
 
 pub fn parse_package_lock(path: &Path) -> Result<Vec<PackageRef>, GuardianError> {
+    parse_package_lock_with_graph(path).map(|(refs, _)| refs)
+}
+
+/// Like [`parse_package_lock`], also returning which package depends on
+/// which. The project root and workspace members are not packages; their
+/// dependencies become the graph roots.
+pub fn parse_package_lock_with_graph(
+    path: &Path,
+) -> Result<(Vec<PackageRef>, DependencyGraph), GuardianError> {
     let content = std::fs::read_to_string(path)
         .map_err(|e| GuardianError::LockfileParseError(format!("Failed to read file: {}", e)))?;
 
@@ -42,11 +65,13 @@ pub fn parse_package_lock(path: &Path) -> Result<Vec<PackageRef>, GuardianError>
         .map_err(|e| GuardianError::LockfileParseError(format!("Invalid JSON: {}", e)))?;
 
     let mut refs = Vec::new();
+    let mut graph = DependencyGraph::new();
 
     // Strategy 1: "packages" (npm v2/v3) - Preferred if present and non-empty
     // We check for > 1 because "packages" usually contains "" (root) even if empty otherwise?
     // Actually npm v7+ always puts packages.
     if !lock.packages.is_empty() {
+        graph = packages_v2_graph(&lock.packages);
         for (key, pkg) in lock.packages {
             if key.is_empty() {
                 continue;
@@ -75,13 +100,153 @@ pub fn parse_package_lock(path: &Path) -> Result<Vec<PackageRef>, GuardianError>
     } else {
         // Strategy 2: "dependencies" (npm v1) - Recursive
         parse_deps_v1(&lock.dependencies, &mut refs);
+        deps_v1_graph(&lock.dependencies, &mut Vec::new(), &mut graph);
     }
 
     refs.sort_by(|a, b| {
         (a.name.as_str(), a.version.as_str()).cmp(&(b.name.as_str(), b.version.as_str()))
     });
     refs.dedup_by(|a, b| a.name == b.name && a.version == b.version);
-    Ok(refs)
+    Ok((refs, graph))
+}
+
+/// Declared licenses of the packages in an npm v2/v3 lockfile, which npm
+/// copies from each package's `package.json`.
+pub fn package_lock_licenses(path: &Path) -> Result<HashMap<PackageRef, String>, GuardianError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| GuardianError::LockfileParseError(format!("Failed to read file: {}", e)))?;
+    let lock: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| GuardianError::LockfileParseError(format!("Invalid JSON: {}", e)))?;
+
+    let mut licenses = HashMap::new();
+    let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) else {
+        return Ok(licenses);
+    };
+    for (key, entry) in packages {
+        let version = entry.get("version").and_then(|v| v.as_str());
+        let (Some(version), Some(license)) = (version, license::npm_license(entry)) else {
+            continue;
+        };
+        if key.is_empty() || !is_valid_version(version) {
+            continue;
+        }
+        licenses.insert(
+            PackageRef {
+                ecosystem: Ecosystem::Npm,
+                name: extract_name_from_path(key).to_string(),
+                version: version.to_string(),
+            },
+            license,
+        );
+    }
+    Ok(licenses)
+}
+
+fn packages_v2_graph(packages: &HashMap<String, PackageV2>) -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+    for (key, pkg) in packages {
+        if pkg.link {
+            continue;
+        }
+        // "" is the root and other keys outside node_modules are workspace
+        // members; only they install their devDependencies.
+        let is_project = !key.starts_with("node_modules/") && !key.contains("/node_modules/");
+        let from = package_v2_ref(key, pkg);
+        let mut names: Vec<&String> = pkg
+            .dependencies
+            .keys()
+            .chain(pkg.optional_dependencies.keys())
+            .collect();
+        if is_project {
+            names.extend(pkg.dev_dependencies.keys());
+        }
+
+        for name in names {
+            let Some(to) = resolve_v2(packages, key, name)
+                .and_then(|dep_key| package_v2_ref(dep_key, &packages[dep_key]))
+            else {
+                continue;
+            };
+            if is_project {
+                graph.add_root(to);
+            } else if let Some(from) = &from {
+                graph.add_edge(from.clone(), to);
+            }
+        }
+    }
+    graph
+}
+
+/// Finds the install path `name` resolves to from the package at `from`:
+/// its own node_modules first, then each enclosing node_modules up to the
+/// root, as Node's module resolution does.
+fn resolve_v2<'a>(
+    packages: &'a HashMap<String, PackageV2>,
+    from: &str,
+    name: &str,
+) -> Option<&'a str> {
+    let mut base = from;
+    loop {
+        let candidate = if base.is_empty() {
+            format!("node_modules/{}", name)
+        } else {
+            format!("{}/node_modules/{}", base, name)
+        };
+        if let Some((key, _)) = packages.get_key_value(&candidate) {
+            return Some(key.as_str());
+        }
+        if base.is_empty() {
+            return None;
+        }
+        base = match base.rfind("/node_modules/") {
+            Some(idx) => &base[..idx],
+            None => "",
+        };
+    }
+}
+
+fn package_v2_ref(key: &str, pkg: &PackageV2) -> Option<PackageRef> {
+    if pkg.link || !is_valid_version(&pkg.version) {
+        return None;
+    }
+    Some(PackageRef {
+        ecosystem: Ecosystem::Npm,
+        name: extract_name_from_path(key).to_string(),
+        version: pkg.version.clone(),
+    })
+}
+
+/// npm v1 lockfiles nest packages where they are installed; `requires`
+/// names resolve against the package's own nested dependencies first, then
+/// each enclosing level. They do not say which packages are direct.
+fn deps_v1_graph<'a>(
+    deps: &'a HashMap<String, DependencyV1>,
+    scopes: &mut Vec<&'a HashMap<String, DependencyV1>>,
+    graph: &mut DependencyGraph,
+) {
+    scopes.push(deps);
+    for (name, dep) in deps {
+        if let Some(from) = dep_v1_ref(name, dep) {
+            for required in dep.requires.keys() {
+                let resolved = std::iter::once(&dep.dependencies)
+                    .chain(scopes.iter().rev().copied())
+                    .find_map(|scope| scope.get(required));
+                if let Some(to) = resolved.and_then(|d| dep_v1_ref(required, d)) {
+                    graph.add_edge(from.clone(), to);
+                }
+            }
+        }
+        deps_v1_graph(&dep.dependencies, scopes, graph);
+    }
+    scopes.pop();
+}
+
+fn dep_v1_ref(name: &str, dep: &DependencyV1) -> Option<PackageRef> {
+    is_valid_version(&dep.version).then(|| PackageRef {
+        ecosystem: Ecosystem::Npm,
+        name: name.to_string(),
+        version: dep.version.clone(),
+    })
 }
 
 fn parse_deps_v1(deps: &HashMap<String, DependencyV1>, refs: &mut Vec<PackageRef>) {
@@ -131,4 +296,85 @@ mod tests {
         assert_eq!(extract_name_from_path("node_modules/a/node_modules/b"), "b");
         assert_eq!(extract_name_from_path("foo"), "foo");
     }
+
+    fn chains(graph: &DependencyGraph, name: &str, version: &str) -> Vec<Vec<String>> {
+        let target = PackageRef {
+            ecosystem: Ecosystem::Npm,
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        graph
+            .shortest_paths(&target, crate::graph::MAX_PATHS)
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|p| format!("{}@{}", p.name, p.version))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_graph_v3_resolves_nested_installs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package-lock.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "lockfileVersion": 3,
+                "packages": {
+                    "": { "name": "app", "dependencies": { "express": "^4" },
+                          "devDependencies": { "jest": "^29" } },
+                    "node_modules/express": { "version": "4.17.1",
+                        "dependencies": { "body-parser": "1.19.0", "qs": "6.7.0" } },
+                    "node_modules/body-parser": { "version": "1.19.0",
+                        "dependencies": { "qs": "6.7.0" } },
+                    "node_modules/qs": { "version": "6.7.0" },
+                    "node_modules/jest": { "version": "29.0.0",
+                        "dependencies": { "qs": "^6.11.0" } },
+                    "node_modules/jest/node_modules/qs": { "version": "6.11.0" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let (refs, graph) = parse_package_lock_with_graph(&path).unwrap();
+        assert_eq!(refs.len(), 5);
+        assert_eq!(
+            chains(&graph, "qs", "6.7.0"),
+            vec![vec!["express@4.17.1", "qs@6.7.0"]]
+        );
+        assert_eq!(
+            chains(&graph, "qs", "6.11.0"),
+            vec![vec!["jest@29.0.0", "qs@6.11.0"]]
+        );
+        assert_eq!(chains(&graph, "jest", "29.0.0"), vec![vec!["jest@29.0.0"]]);
+    }
+
+    #[test]
+    fn test_graph_v1_requires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package-lock.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "lockfileVersion": 1,
+                "dependencies": {
+                    "express": { "version": "4.17.1",
+                        "requires": { "body-parser": "1.19.0" } },
+                    "body-parser": { "version": "1.19.0",
+                        "requires": { "qs": "6.7.0" },
+                        "dependencies": { "qs": { "version": "6.7.0" } } },
+                    "qs": { "version": "6.11.0" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let (_, graph) = parse_package_lock_with_graph(&path).unwrap();
+        assert_eq!(
+            chains(&graph, "qs", "6.7.0"),
+            vec![vec!["express@4.17.1", "body-parser@1.19.0", "qs@6.7.0"]]
+        );
+    }
 }
@@ -1,12 +1,13 @@
 use super::cache::Cache;
 use super::details::{CachePolicy, CacheStatus, CachedVuln, FetchOutcome};
 use super::details_store::{DetailsStore, StoreLoad};
+use super::mirror::OsvMirror;
 use super::net::{
     backoff_delay, clamp_timeout, classify_error, classify_response, ConcurrencyGate, NetConfig,
     RetryClass, Sleeper, TimeBudget, TokioSleeper,
 };
-use crate::models::{Advisory, Ecosystem, PackageRef};
-use crate::report::Vulnerability;
+use crate::models::{Advisory, PackageRef};
+use crate::report::{OfflineDatabase, Vulnerability};
 use crate::GuardianError;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -73,12 +74,16 @@ pub struct OsvVuln {
     // Actually standard OSV response includes 'summary' or 'details'.
     pub summary: Option<String>,
     pub details: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
 
 pub struct OsvClient {
     client: Client, // Async Client
     cache: Option<Cache>,
     details_store: Option<DetailsStore>,
+    /// Imported OSV database, answering queries when offline.
+    mirror: Option<OsvMirror>,
     offline: bool,
     api_url: String,
     metrics: Option<Arc<Metrics>>,
@@ -131,6 +136,12 @@ impl OsvClient {
         // DetailsStore now expects <root>/osv and manages `vulns` inside.
         // So we pass `base.join("osv")` to DetailsStore.
 
+        let mirror = OsvMirror::new(
+            cache_dir
+                .as_ref()
+                .map(|base| base.join("osv").join("mirror")),
+        );
+
         let (query_cache, details_cache) = if let Some(base) = cache_dir {
             // If base provided (e.g. /tmp/test), we want /tmp/test/osv as root.
             let osv_root = base.join("osv");
@@ -151,6 +162,7 @@ impl OsvClient {
                 None
             },
             details_store: DetailsStore::new(details_cache),
+            mirror,
             offline,
             api_url,
             metrics,
@@ -185,6 +197,7 @@ impl OsvClient {
             client,
             cache: if !offline { Cache::new(None) } else { None },
             details_store: store,
+            mirror: None,
             offline,
             api_url,
             metrics: None,
@@ -225,6 +238,7 @@ impl OsvClient {
             client,
             cache: if !offline { Cache::new(None) } else { None },
             details_store: store,
+            mirror: None,
             offline,
             api_url,
             metrics,
@@ -248,6 +262,46 @@ impl OsvClient {
         Self::new_custom_with_net_and_metrics(offline, store, api_url, net, sleeper, None)
     }
 
+    /// Answers offline queries from `mirror` instead of the default one.
+    pub fn with_mirror(mut self, mirror: OsvMirror) -> Self {
+        self.mirror = Some(mirror);
+        self
+    }
+
+    /// The imported databases offline queries for `packages` are answered
+    /// from, with their age. Empty when online.
+    pub fn offline_databases(&self, packages: &[PackageRef]) -> Vec<OfflineDatabase> {
+        let Some(mirror) = self.mirror.as_ref().filter(|_| self.offline) else {
+            return Vec::new();
+        };
+        let mut ecosystems: Vec<_> = packages.iter().map(|p| p.ecosystem).collect();
+        ecosystems.sort();
+        ecosystems.dedup();
+
+        let now = chrono::Utc::now();
+        let databases: Vec<OfflineDatabase> = ecosystems
+            .into_iter()
+            .filter_map(|ecosystem| mirror.meta(ecosystem))
+            .map(|meta| OfflineDatabase {
+                ecosystem: meta.ecosystem,
+                source: meta.source,
+                advisories: meta.advisories,
+                imported_at: meta.imported_at,
+                updated_at: meta.updated_at,
+                age_days: (now - meta.updated_at).num_days(),
+            })
+            .collect();
+        if let Some(m) = &self.metrics {
+            let oldest = databases
+                .iter()
+                .map(|db| (now - db.updated_at).num_seconds().max(0) as u64)
+                .max()
+                .unwrap_or(0);
+            m.offline_db_age_secs.fetch_max(oldest, Ordering::Relaxed);
+        }
+        databases
+    }
+
     fn env_flag(name: &str) -> bool {
         matches!(
             std::env::var(name).as_deref(),
@@ -281,6 +335,7 @@ impl OsvClient {
             client: self.client.clone(),
             cache: self.cache.clone(),
             details_store: self.details_store.clone(),
+            mirror: self.mirror.clone(),
             offline: self.offline,
             api_url: self.api_url.clone(),
             metrics: self.metrics.clone(),
@@ -343,6 +398,7 @@ struct OsvClientInternal {
     client: Client,
     cache: Option<Cache>,
     details_store: Option<DetailsStore>,
+    mirror: Option<OsvMirror>,
     offline: bool,
     api_url: String,
     metrics: Option<Arc<Metrics>>,
@@ -591,6 +647,20 @@ impl OsvClientInternal {
         let policy = CachePolicy::default();
         let force_refresh = OsvClient::env_flag("VEIL_OSV_FORCE_REFRESH");
 
+        // 0. Offline runs with an imported database read the record from it.
+        if self.offline {
+            if let Some((vuln, imported_at)) = self.mirror.as_ref().and_then(|m| m.load(id)) {
+                if let Some(m) = &self.metrics {
+                    m.offline_db_hits.fetch_add(1, Ordering::Relaxed);
+                }
+                return Ok((
+                    vuln,
+                    FetchOutcome::OfflineDatabase.label().to_string(),
+                    imported_at.into(),
+                ));
+            }
+        }
+
         // 1. Check Cache
         // Using StoreLoad to detect migrations
         let cache_result = if let Some(store) = &self.details_store {
@@ -932,6 +1002,7 @@ impl OsvClientInternal {
                                     .unwrap_or_else(|| "No description".to_string()),
                                 vulnerable_versions: semver::VersionReq::STAR,
                                 crate_name: pkg.name.clone(),
+                                aliases: v.aliases,
                                 details: None,
                                 cache_status: None,
                                 last_fetched_at: None,
@@ -942,6 +1013,15 @@ impl OsvClientInternal {
                                 // Async Fetch Details with Coalescing
                                 match self.fetch_vuln_details_with_coalescing(&advisory.id).await {
                                     Ok((json, status, fetched_at)) => {
+                                        if let Some(aliases) =
+                                            json.get("aliases").and_then(|a| a.as_array())
+                                        {
+                                            for alias in aliases.iter().filter_map(|a| a.as_str()) {
+                                                if !advisory.aliases.iter().any(|a| a == alias) {
+                                                    advisory.aliases.push(alias.to_string());
+                                                }
+                                            }
+                                        }
                                         advisory.details = Some(json);
                                         advisory.cache_status = Some(status);
                                         advisory.last_fetched_at = Some(
@@ -965,6 +1045,10 @@ impl OsvClientInternal {
                             version: pkg.version.clone(),
                             advisories,
                             locations: Vec::new(),
+                            fixed_version: None,
+                            severity: None,
+                            cvss_score: None,
+                            dependency_paths: Vec::new(),
                         });
                     }
                 }
@@ -990,9 +1074,7 @@ impl OsvClientInternal {
         }
 
         if self.offline {
-            return Err(Arc::new(GuardianError::NetworkError(
-                "Offline mode: OSV cache miss.".to_string(),
-            )));
+            return self.query_offline_database(packages).map_err(Arc::new);
         }
 
         // Prepare Owned Data for Future (Optimistic serialization)
@@ -1001,10 +1083,7 @@ impl OsvClientInternal {
             .map(|p| Query {
                 package: OsvPackage {
                     name: &p.name,
-                    ecosystem: match p.ecosystem {
-                        Ecosystem::Npm => "npm",
-                        Ecosystem::Rust => "Crates.io",
-                    },
+                    ecosystem: p.ecosystem.osv_name(),
                 },
                 version: &p.version,
             })
@@ -1093,6 +1172,43 @@ impl OsvClientInternal {
         result
     }
 
+    /// Offline answer for a chunk from the imported database. Every package's
+    /// ecosystem must have been imported.
+    fn query_offline_database(
+        &self,
+        packages: &[PackageRef],
+    ) -> Result<Vec<Option<Vec<OsvVuln>>>, GuardianError> {
+        let mut missing: Vec<&str> = Vec::new();
+        let mut results = Vec::with_capacity(packages.len());
+        for package in packages {
+            match self.mirror.as_ref().and_then(|m| m.query(package)) {
+                Some(records) => results.push(Some(
+                    records
+                        .into_iter()
+                        .filter_map(|record| serde_json::from_value::<OsvVuln>(record).ok())
+                        .collect(),
+                )),
+                None => {
+                    let name = package.ecosystem.osv_name();
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+            }
+        }
+        if !missing.is_empty() {
+            return Err(GuardianError::NetworkError(format!(
+                "Offline mode: OSV cache miss. No OSV database imported for {} (see `veil guardian db import`).",
+                missing.join(", ")
+            )));
+        }
+        if let Some(m) = &self.metrics {
+            m.offline_db_hits
+                .fetch_add(packages.len() as u64, Ordering::Relaxed);
+        }
+        Ok(results)
+    }
+
     fn compute_chunk_key(&self, packages: &[PackageRef]) -> String {
         let mut s = String::new();
         for p in packages {
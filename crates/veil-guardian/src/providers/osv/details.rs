@@ -14,6 +14,7 @@ pub enum FetchOutcome {
     OfflineUsedFreshCache,
     OfflineFallbackUsedStale,
     HitLegacyMigrated, // legacy satisfied request and migrated to v1
+    OfflineDatabase,   // offline, served from an imported OSV export
     FailedNoUsableCache,
 }
 
@@ -29,6 +30,7 @@ impl FetchOutcome {
             FetchOutcome::CacheHitStaleFallback => "Hit (Stale) [Fallback]",
             FetchOutcome::OfflineFallbackUsedStale => "Hit (Stale) [Offline Fallback]",
             FetchOutcome::HitLegacyMigrated => "Hit (Migrated)",
+            FetchOutcome::OfflineDatabase => "Hit (Offline DB)",
 
             FetchOutcome::NetworkFetched => "Fetched",
             FetchOutcome::NetworkNotModified => "Hit (Fresh) [304]",
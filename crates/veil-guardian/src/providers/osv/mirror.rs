@@ -0,0 +1,465 @@
+//! Local OSV database imported from the bulk exports
+//! (`https://osv-vulnerabilities.storage.googleapis.com/<ecosystem>/all.zip`),
+//! so offline scans do not depend on what the query cache happened to see.
+//!
+//! Layout under `<osv root>/mirror/<ecosystem>/`:
+//! - `meta.json`: import time, newest `modified` timestamp, advisory count
+//! - `index.json`: normalized package name -> advisory ids
+//! - `vulns/<blake3(id)>.json`: the OSV records as exported
+//!
+//! An import replaces the data of every ecosystem found in the archive.
+
+use crate::models::{Advisory, Ecosystem, PackageRef};
+use crate::remediation::{self, normalize_name};
+use crate::util::atomic_write::atomic_write_bytes;
+use crate::GuardianError;
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const SCHEMA_VERSION: u32 = 1;
+
+/// Export entries are single advisories; anything larger is not one.
+const MAX_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+
+type Index = BTreeMap<String, BTreeSet<String>>;
+
+/// What one import left behind for an ecosystem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorMeta {
+    pub schema_version: u32,
+    pub ecosystem: Ecosystem,
+    /// File name of the imported archive.
+    pub source: String,
+    pub imported_at: DateTime<Utc>,
+    /// Newest `modified` timestamp among the advisories; the database is as
+    /// old as this.
+    pub updated_at: DateTime<Utc>,
+    pub advisories: usize,
+}
+
+#[derive(Clone)]
+pub struct OsvMirror {
+    dir: PathBuf,
+    indexes: Arc<Mutex<HashMap<Ecosystem, Arc<Index>>>>,
+}
+
+impl OsvMirror {
+    /// Recommended: <cache_dir>/osv/mirror, next to the details store.
+    pub fn new(custom_path: Option<PathBuf>) -> Option<Self> {
+        let dir = match custom_path {
+            Some(p) => p,
+            None => ProjectDirs::from("com", "veil-rs", "veil")?
+                .cache_dir()
+                .join("guardian")
+                .join("osv")
+                .join("mirror"),
+        };
+        Some(Self::with_dir(dir))
+    }
+
+    pub fn with_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            indexes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Imports an OSV export zip. Every ecosystem with at least one advisory
+    /// in the archive is replaced.
+    pub fn import_zip(
+        &self,
+        path: &Path,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MirrorMeta>, GuardianError> {
+        let file = fs::File::open(path).map_err(|e| import_error(path, e))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| import_error(path, e))?;
+        let source = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut staged: BTreeMap<Ecosystem, Staged> = BTreeMap::new();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).map_err(|e| import_error(path, e))?;
+            if !entry.is_file()
+                || !entry.name().ends_with(".json")
+                || entry.size() > MAX_ENTRY_BYTES
+            {
+                continue;
+            }
+            let mut bytes = Vec::new();
+            (&mut entry)
+                .take(MAX_ENTRY_BYTES)
+                .read_to_end(&mut bytes)
+                .map_err(|e| import_error(path, e))?;
+            let Ok(vuln) = serde_json::from_slice::<Value>(&bytes) else {
+                continue;
+            };
+            let Some(id) = vuln.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            let modified = vuln
+                .get("modified")
+                .and_then(Value::as_str)
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|at| at.with_timezone(&Utc));
+
+            for (ecosystem, name) in affected_packages(&vuln) {
+                let stage = match staged.entry(ecosystem) {
+                    std::collections::btree_map::Entry::Occupied(e) => e.into_mut(),
+                    std::collections::btree_map::Entry::Vacant(e) => {
+                        e.insert(Staged::create(&self.staging_dir(ecosystem))?)
+                    }
+                };
+                stage.add(id, &name, &bytes, modified)?;
+            }
+        }
+
+        if staged.is_empty() {
+            return Err(GuardianError::DatabaseError(format!(
+                "{} holds no advisories for a supported ecosystem",
+                path.display()
+            )));
+        }
+
+        let mut imported = Vec::new();
+        for (ecosystem, stage) in staged {
+            let meta = MirrorMeta {
+                schema_version: SCHEMA_VERSION,
+                ecosystem,
+                source: source.clone(),
+                imported_at: now,
+                updated_at: stage.updated_at.unwrap_or(now),
+                advisories: stage.written.len(),
+            };
+            stage.finish(&meta, &self.ecosystem_dir(ecosystem))?;
+            self.indexes.lock().unwrap().remove(&ecosystem);
+            imported.push(meta);
+        }
+        Ok(imported)
+    }
+
+    /// The import that serves `ecosystem`, if any.
+    pub fn meta(&self, ecosystem: Ecosystem) -> Option<MirrorMeta> {
+        let bytes = fs::read(self.ecosystem_dir(ecosystem).join("meta.json")).ok()?;
+        serde_json::from_slice::<MirrorMeta>(&bytes)
+            .ok()
+            .filter(|meta| meta.schema_version == SCHEMA_VERSION)
+    }
+
+    /// Every imported ecosystem.
+    pub fn status(&self) -> Vec<MirrorMeta> {
+        Ecosystem::ALL
+            .into_iter()
+            .filter_map(|ecosystem| self.meta(ecosystem))
+            .collect()
+    }
+
+    /// Advisories affecting `package`. `None` when its ecosystem was never
+    /// imported, as opposed to an empty list for a package without findings.
+    pub fn query(&self, package: &PackageRef) -> Option<Vec<Value>> {
+        let index = self.index(package.ecosystem)?;
+        let ids = index.get(&normalize_name(&package.name));
+        Some(
+            ids.into_iter()
+                .flatten()
+                .filter_map(|id| self.load_from(package.ecosystem, id))
+                .filter(|vuln| vuln.get("withdrawn").is_none())
+                .filter(|vuln| affects(vuln, package))
+                .collect(),
+        )
+    }
+
+    /// The stored OSV record for `id`, with the import time of the ecosystem
+    /// it was found under.
+    pub fn load(&self, id: &str) -> Option<(Value, DateTime<Utc>)> {
+        Ecosystem::ALL.into_iter().find_map(|ecosystem| {
+            let vuln = self.load_from(ecosystem, id)?;
+            Some((vuln, self.meta(ecosystem)?.imported_at))
+        })
+    }
+
+    fn index(&self, ecosystem: Ecosystem) -> Option<Arc<Index>> {
+        if let Some(index) = self.indexes.lock().unwrap().get(&ecosystem) {
+            return Some(index.clone());
+        }
+        self.meta(ecosystem)?;
+        let bytes = fs::read(self.ecosystem_dir(ecosystem).join("index.json")).ok()?;
+        let index: Arc<Index> = Arc::new(serde_json::from_slice(&bytes).ok()?);
+        self.indexes
+            .lock()
+            .unwrap()
+            .insert(ecosystem, index.clone());
+        Some(index)
+    }
+
+    fn load_from(&self, ecosystem: Ecosystem, id: &str) -> Option<Value> {
+        let bytes = fs::read(vuln_path(&self.ecosystem_dir(ecosystem), id)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    fn ecosystem_dir(&self, ecosystem: Ecosystem) -> PathBuf {
+        self.dir.join(ecosystem.osv_name())
+    }
+
+    fn staging_dir(&self, ecosystem: Ecosystem) -> PathBuf {
+        self.dir.join(format!(".import-{}", ecosystem.osv_name()))
+    }
+}
+
+/// An ecosystem being imported into its staging directory.
+struct Staged {
+    dir: PathBuf,
+    index: Index,
+    written: HashSet<String>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl Staged {
+    fn create(dir: &Path) -> Result<Self, GuardianError> {
+        // Leftovers of an interrupted import.
+        let _ = fs::remove_dir_all(dir);
+        fs::create_dir_all(dir.join("vulns")).map_err(|e| store_error(dir, e))?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            index: Index::new(),
+            written: HashSet::new(),
+            updated_at: None,
+        })
+    }
+
+    fn add(
+        &mut self,
+        id: &str,
+        name: &str,
+        bytes: &[u8],
+        modified: Option<DateTime<Utc>>,
+    ) -> Result<(), GuardianError> {
+        if self.written.insert(id.to_string()) {
+            let path = vuln_path(&self.dir, id);
+            fs::write(&path, bytes).map_err(|e| store_error(&path, e))?;
+            self.updated_at = self.updated_at.max(modified);
+        }
+        self.index
+            .entry(normalize_name(name))
+            .or_default()
+            .insert(id.to_string());
+        Ok(())
+    }
+
+    fn finish(self, meta: &MirrorMeta, target: &Path) -> Result<(), GuardianError> {
+        let index = serde_json::to_vec(&self.index)
+            .map_err(|e| GuardianError::DatabaseError(e.to_string()))?;
+        let meta = serde_json::to_vec_pretty(meta)
+            .map_err(|e| GuardianError::DatabaseError(e.to_string()))?;
+        atomic_write_bytes(&self.dir.join("index.json"), &index)
+            .map_err(|e| store_error(&self.dir, e))?;
+        atomic_write_bytes(&self.dir.join("meta.json"), &meta)
+            .map_err(|e| store_error(&self.dir, e))?;
+
+        if target.exists() {
+            fs::remove_dir_all(target).map_err(|e| store_error(target, e))?;
+        }
+        fs::rename(&self.dir, target).map_err(|e| store_error(target, e))
+    }
+}
+
+/// `(ecosystem, package name)` pairs from `affected[]`, for the ecosystems
+/// guardian scans.
+fn affected_packages(vuln: &Value) -> Vec<(Ecosystem, String)> {
+    vuln.get("affected")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let package = entry.get("package")?;
+            let ecosystem = Ecosystem::from_osv_name(package.get("ecosystem")?.as_str()?)?;
+            Some((ecosystem, package.get("name")?.as_str()?.to_string()))
+        })
+        .collect()
+}
+
+/// Whether the record's `affected[]` entries for the package's ecosystem
+/// cover its version, evaluated like OSV does.
+fn affects(vuln: &Value, package: &PackageRef) -> bool {
+    let affected: Vec<&Value> = vuln
+        .get("affected")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .get("package")
+                .and_then(|p| p.get("ecosystem"))
+                .and_then(Value::as_str)
+                .and_then(Ecosystem::from_osv_name)
+                == Some(package.ecosystem)
+        })
+        .collect();
+    let advisory = Advisory {
+        id: vuln
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        crate_name: package.name.clone(),
+        vulnerable_versions: semver::VersionReq::STAR,
+        description: String::new(),
+        aliases: Vec::new(),
+        details: Some(json!({ "affected": affected })),
+        cache_status: None,
+        last_fetched_at: None,
+    };
+    remediation::affects(
+        &advisory,
+        package.ecosystem,
+        &package.name,
+        &package.version,
+    ) == Some(true)
+}
+
+fn vuln_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join("vulns")
+        .join(format!("{}.json", blake3::hash(id.as_bytes()).to_hex()))
+}
+
+fn import_error(path: &Path, e: impl std::fmt::Display) -> GuardianError {
+    GuardianError::DatabaseError(format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn store_error(path: &Path, e: std::io::Error) -> GuardianError {
+    GuardianError::DatabaseError(format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn export(dir: &Path, records: &[Value]) -> PathBuf {
+        let path = dir.join("npm.zip");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for record in records {
+            writer
+                .start_file(
+                    format!("{}.json", record["id"].as_str().unwrap()),
+                    zip::write::SimpleFileOptions::default(),
+                )
+                .unwrap();
+            writer
+                .write_all(serde_json::to_string(record).unwrap().as_bytes())
+                .unwrap();
+        }
+        writer.finish().unwrap();
+        path
+    }
+
+    fn npm(name: &str, version: &str) -> PackageRef {
+        PackageRef {
+            ecosystem: Ecosystem::Npm,
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn imports_and_answers_queries_by_range() {
+        let temp = tempfile::tempdir().unwrap();
+        let zip = export(
+            temp.path(),
+            &[
+                json!({ "id": "GHSA-1", "modified": "2026-10-01T00:00:00Z",
+                    "affected": [{ "package": { "ecosystem": "npm", "name": "left-pad" },
+                        "ranges": [{ "type": "SEMVER",
+                            "events": [{ "introduced": "0" }, { "fixed": "1.3.0" }] }] }] }),
+                json!({ "id": "GHSA-2", "modified": "2026-09-01T00:00:00Z", "withdrawn": "2026-09-02T00:00:00Z",
+                    "affected": [{ "package": { "ecosystem": "npm", "name": "left-pad" },
+                        "versions": ["1.0.0"] }] }),
+                json!({ "id": "PYSEC-1",
+                    "affected": [{ "package": { "ecosystem": "Debian:12", "name": "left-pad" } }] }),
+            ],
+        );
+        let mirror = OsvMirror::with_dir(temp.path().join("mirror"));
+        let now = DateTime::parse_from_rfc3339("2026-10-18T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let imported = mirror.import_zip(&zip, now).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].ecosystem, Ecosystem::Npm);
+        assert_eq!(imported[0].advisories, 2);
+        assert_eq!(
+            imported[0].updated_at.to_rfc3339(),
+            "2026-10-01T00:00:00+00:00"
+        );
+
+        let ids = |found: Vec<Value>| -> Vec<String> {
+            found
+                .iter()
+                .map(|v| v["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(
+            ids(mirror.query(&npm("left-pad", "1.0.0")).unwrap()),
+            vec!["GHSA-1"]
+        );
+        assert!(mirror.query(&npm("left-pad", "1.3.0")).unwrap().is_empty());
+        assert!(mirror.query(&npm("right-pad", "1.0.0")).unwrap().is_empty());
+        assert!(mirror
+            .query(&PackageRef {
+                ecosystem: Ecosystem::PyPI,
+                name: "left-pad".to_string(),
+                version: "1.0".to_string(),
+            })
+            .is_none());
+        assert_eq!(mirror.load("GHSA-1").unwrap().1, now);
+    }
+
+    #[test]
+    fn reimport_replaces_the_ecosystem() {
+        let temp = tempfile::tempdir().unwrap();
+        let mirror = OsvMirror::with_dir(temp.path().join("mirror"));
+        let record = |id: &str| {
+            json!({ "id": id, "affected": [{ "package": { "ecosystem": "npm", "name": "pkg" },
+                "versions": ["1.0.0"] }] })
+        };
+
+        mirror
+            .import_zip(&export(temp.path(), &[record("GHSA-old")]), Utc::now())
+            .unwrap();
+        assert_eq!(mirror.query(&npm("pkg", "1.0.0")).unwrap().len(), 1);
+
+        mirror
+            .import_zip(&export(temp.path(), &[record("GHSA-new")]), Utc::now())
+            .unwrap();
+        let found = mirror.query(&npm("pkg", "1.0.0")).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["id"], "GHSA-new");
+        assert!(mirror.load("GHSA-old").is_none());
+    }
+
+    #[test]
+    fn rejects_archives_without_supported_advisories() {
+        let temp = tempfile::tempdir().unwrap();
+        let zip = export(
+            temp.path(),
+            &[
+                json!({ "id": "DSA-1", "affected": [{ "package": { "ecosystem": "Debian", "name": "x" } }] }),
+            ],
+        );
+        let mirror = OsvMirror::with_dir(temp.path().join("mirror"));
+        assert!(mirror.import_zip(&zip, Utc::now()).is_err());
+        assert!(mirror.meta(Ecosystem::Npm).is_none());
+    }
+}
@@ -2,6 +2,8 @@ pub mod cache;
 pub mod client;
 pub mod details;
 pub mod details_store;
+pub mod mirror;
 pub mod net;
 
 pub use client::OsvClient;
+pub use mirror::OsvMirror;
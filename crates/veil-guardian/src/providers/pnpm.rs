@@ -1,38 +1,125 @@
+use crate::graph::DependencyGraph;
 use crate::models::{Ecosystem, PackageRef};
 use crate::GuardianError;
 use serde::Deserialize;
+use serde_yaml::Value;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct PnpmLock {
+    /// Workspace projects (v5.3+ with workspaces, always in v9).
     #[serde(default)]
-    packages: HashMap<String, serde::de::IgnoredAny>,
+    importers: HashMap<String, PnpmDependencies>,
+    /// Single-project lockfiles before v9 list direct dependencies at the top.
+    #[serde(flatten)]
+    root: PnpmDependencies,
     #[serde(default)]
-    snapshots: HashMap<String, serde::de::IgnoredAny>,
+    packages: HashMap<String, Option<PnpmDependencies>>,
+    #[serde(default)]
+    snapshots: HashMap<String, Option<PnpmDependencies>>,
+}
+
+/// Dependency maps of an importer or a package. Importers map names to a
+/// `{specifier, version}` mapping (or a bare version before v6), packages to
+/// a bare version.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct PnpmDependencies {
+    #[serde(default)]
+    dependencies: HashMap<String, Value>,
+    #[serde(default)]
+    dev_dependencies: HashMap<String, Value>,
+    #[serde(default)]
+    optional_dependencies: HashMap<String, Value>,
+}
+
+impl PnpmDependencies {
+    fn all(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.dependencies
+            .iter()
+            .chain(&self.dev_dependencies)
+            .chain(&self.optional_dependencies)
+    }
 }
 
 pub fn parse_pnpm_lock(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    parse_pnpm_lock_with_graph(content).map(|(refs, _)| refs)
+}
+
+/// Like [`parse_pnpm_lock`], also returning which package depends on which.
+/// Dependencies of the importers (workspace projects) are the graph roots.
+pub fn parse_pnpm_lock_with_graph(
+    content: &str,
+) -> Result<(Vec<PackageRef>, DependencyGraph), GuardianError> {
     let lock: PnpmLock = serde_yaml::from_str(content)
         .map_err(|e| GuardianError::LockfileParseError(format!("Failed to parse YAML: {}", e)))?;
 
     // Use HashSet of (name, version) for deduplication
     let mut refs = HashSet::new();
+    let mut graph = DependencyGraph::new();
 
-    // Helper to process a key
-    let mut process_key = |key: &str| {
-        // Skip non-semver protocols
-        if key.starts_with("file:") || key.starts_with("link:") || key.starts_with("workspace:") {
-            return;
+    for importer in lock.importers.values().chain(std::iter::once(&lock.root)) {
+        for (name, value) in importer.all() {
+            if let Some(to) = dependency_ref(name, value) {
+                graph.add_root(to);
+            }
         }
+    }
 
-        let clean_key = if let Some(idx) = key.find('(') {
-            &key[..idx]
-        } else {
-            key
+    // 'packages' is common in v5/v6/v9; 'snapshots' is new in v9 for
+    // describing the graph, but keys are essentially packages.
+    for (key, deps) in lock.packages.iter().chain(&lock.snapshots) {
+        let Some((name, version)) = parse_key(key) else {
+            continue;
         };
+        let from = npm_ref(name.clone(), version.clone());
+        for (dep_name, value) in deps.iter().flat_map(PnpmDependencies::all) {
+            if let Some(to) = dependency_ref(dep_name, value) {
+                graph.add_edge(from.clone(), to);
+            }
+        }
+        refs.insert((name, version));
+    }
+
+    let mut refs_vec: Vec<_> = refs.into_iter().collect();
+    refs_vec.sort_by(|a, b| match a.0.cmp(&b.0) {
+        std::cmp::Ordering::Equal => a.1.cmp(&b.1),
+        other => other,
+    });
+
+    let refs = refs_vec
+        .into_iter()
+        .map(|(name, version)| npm_ref(name, version))
+        .collect();
+    Ok((refs, graph))
+}
+
+fn npm_ref(name: String, version: String) -> PackageRef {
+    PackageRef {
+        ecosystem: Ecosystem::Npm,
+        name,
+        version,
+    }
+}
+
+/// Splits a `packages`/`snapshots` key into name and version, dropping peer
+/// dependency suffixes.
+fn parse_key(key: &str) -> Option<(String, String)> {
+    // Skip non-semver protocols
+    if key.starts_with("file:") || key.starts_with("link:") || key.starts_with("workspace:") {
+        return None;
+    }
+
+    let clean_key = if let Some(idx) = key.find('(') {
+        &key[..idx]
+    } else {
+        key
+    };
 
-        // v5/v6 Slash-style: /@scope/pkg/1.2.3 or /pkg/1.2.3
-        if let Some(path) = clean_key.strip_prefix('/') {
+    // v5/v6 Slash-style: /@scope/pkg/1.2.3 or /pkg/1.2.3
+    let clean_key = match clean_key.strip_prefix('/') {
+        Some(path) => {
             // Strip leading slash
 
             // Strip peer/hash part (everything after first _, if any)
@@ -47,57 +134,49 @@ pub fn parse_pnpm_lock(content: &str) -> Result<Vec<PackageRef>, GuardianError>
             // e.g. @scope/pkg/1.2.3 -> ["@scope", "pkg", "1.2.3"]
             // e.g. lodash/4.17.15 -> ["lodash", "4.17.15"]
             let parts: Vec<&str> = path.split('/').collect();
-            if parts.len() >= 2 {
-                // If the first part starts with @, we need at least 3 parts (@scope, pkg, ver)
-                // Otherwise we need 2 parts (pkg, ver)
-                if parts[0].starts_with('@') {
-                    if parts.len() >= 3 {
-                        let name = format!("{}/{}", parts[0], parts[1]);
-                        let version = parts[2].to_string();
-                        refs.insert((name, version));
-                    }
-                } else {
-                    let name = parts[0].to_string();
-                    let version = parts[1].to_string();
-                    refs.insert((name, version));
+            // If the first part starts with @, we need at least 3 parts (@scope, pkg, ver)
+            // Otherwise we need 2 parts (pkg, ver)
+            if parts[0].starts_with('@') {
+                if parts.len() >= 3 {
+                    return Some((format!("{}/{}", parts[0], parts[1]), parts[2].to_string()));
                 }
+            } else if parts.len() >= 2 {
+                return Some((parts[0].to_string(), parts[1].to_string()));
             }
+            // v6 writes /pkg@1.2.3
+            path
         }
-        // v9 name@version style: lodash@4.17.15 or @scope/pkg@1.2.3
-        else if let Some(idx) = clean_key.rfind('@') {
-            // Determine if this @ is a separator or part of scope
-            if idx > 0 {
-                let name = clean_key[..idx].to_string();
-                let version = clean_key[idx + 1..].to_string();
-                refs.insert((name, version));
-            }
-        }
+        None => clean_key,
     };
 
-    // Process 'packages' (common in v5/v6/v9)
-    for key in lock.packages.keys() {
-        process_key(key);
+    // v9 name@version style: lodash@4.17.15 or @scope/pkg@1.2.3
+    match clean_key.rfind('@') {
+        // Determine if this @ is a separator or part of scope
+        Some(idx) if idx > 0 => Some((
+            clean_key[..idx].to_string(),
+            clean_key[idx + 1..].to_string(),
+        )),
+        _ => None,
     }
+}
 
-    // Process 'snapshots' (new in v9 for describing the graph, but keys are essentially packages)
-    for key in lock.snapshots.keys() {
-        process_key(key);
+/// The package a dependency entry points at. Values are versions with an
+/// optional peer suffix (`1.0.0(react@18.2.0)`, `1.0.0_react@18.2.0` before
+/// v6), a package key for aliases, or `link:` for workspace projects.
+fn dependency_ref(name: &str, value: &Value) -> Option<PackageRef> {
+    let version = match value {
+        Value::Mapping(_) => value.get("version")?.as_str()?,
+        _ => value.as_str()?,
+    };
+    if version.starts_with('/') {
+        let (name, version) = parse_key(version)?;
+        return Some(npm_ref(name, version));
     }
-
-    let mut refs_vec: Vec<_> = refs.into_iter().collect();
-    refs_vec.sort_by(|a, b| match a.0.cmp(&b.0) {
-        std::cmp::Ordering::Equal => a.1.cmp(&b.1),
-        other => other,
-    });
-
-    Ok(refs_vec
-        .into_iter()
-        .map(|(name, version)| PackageRef {
-            ecosystem: Ecosystem::Npm,
-            name,
-            version,
-        })
-        .collect())
+    let version = version.split(['(', '_']).next().unwrap_or(version);
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(npm_ref(name.to_string(), version.to_string()))
 }
 
 #[cfg(test)]
@@ -148,4 +227,72 @@ snapshots:
         assert_eq!(map.get("@types/node"), Some(&"18.0.0"));
         assert_eq!(map.get("jest-config"), Some(&"30.0.3"));
     }
+
+    fn chains(graph: &DependencyGraph, name: &str, version: &str) -> Vec<Vec<String>> {
+        let target = npm_ref(name.to_string(), version.to_string());
+        graph
+            .shortest_paths(&target, crate::graph::MAX_PATHS)
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|p| format!("{}@{}", p.name, p.version))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_graph_v9_importers_and_snapshots() {
+        let content = r#"
+lockfileVersion: '9.0'
+importers:
+  .:
+    dependencies:
+      express:
+        specifier: ^4.17.0
+        version: 4.17.1
+      local:
+        specifier: workspace:*
+        version: link:packages/local
+packages:
+  express@4.17.1:
+    resolution: {integrity: sha512-xxx}
+  qs@6.7.0:
+    resolution: {integrity: sha512-xxx}
+snapshots:
+  express@4.17.1:
+    dependencies:
+      body-parser: 1.19.0(supports-color@8.1.1)
+  body-parser@1.19.0(supports-color@8.1.1):
+    dependencies:
+      qs: 6.7.0
+  qs@6.7.0: {}
+"#;
+        let (refs, graph) = parse_pnpm_lock_with_graph(content).unwrap();
+        assert_eq!(refs.len(), 3);
+        assert_eq!(
+            chains(&graph, "qs", "6.7.0"),
+            vec![vec!["express@4.17.1", "body-parser@1.19.0", "qs@6.7.0"]]
+        );
+    }
+
+    #[test]
+    fn test_graph_v5_top_level_dependencies() {
+        let content = r#"
+lockfileVersion: 5.4
+dependencies:
+  express: 4.17.1
+packages:
+  /express/4.17.1:
+    dependencies:
+      qs: 6.7.0
+  /qs/6.7.0:
+    resolution: {integrity: sha512-xxx}
+"#;
+        let (_, graph) = parse_pnpm_lock_with_graph(content).unwrap();
+        assert_eq!(
+            chains(&graph, "qs", "6.7.0"),
+            vec![vec!["express@4.17.1", "qs@6.7.0"]]
+        );
+    }
 }
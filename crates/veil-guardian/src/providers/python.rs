@@ -0,0 +1,241 @@
+use crate::models::{Ecosystem, PackageRef};
+use crate::GuardianError;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryPackage {
+    name: String,
+    version: String,
+    source: Option<PoetrySource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetrySource {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfileLock {
+    #[serde(default)]
+    default: BTreeMap<String, PipfileEntry>,
+    #[serde(default)]
+    develop: BTreeMap<String, PipfileEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfileEntry {
+    version: Option<String>,
+}
+
+/// Parses a `poetry.lock` file content and extracts package references.
+///
+/// Packages installed from git, a local directory/file or a direct URL are
+/// not on PyPI and are skipped.
+pub fn parse_poetry_lock(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let lock: PoetryLock = toml::from_str(content)
+        .map_err(|e| GuardianError::LockfileParseError(format!("Invalid TOML: {}", e)))?;
+
+    let packages = lock
+        .package
+        .into_iter()
+        .filter(|p| {
+            !p.source
+                .as_ref()
+                .is_some_and(|s| matches!(s.kind.as_str(), "git" | "directory" | "file" | "url"))
+        })
+        .map(|p| (p.name, p.version));
+    Ok(to_refs(packages))
+}
+
+/// Parses a `Pipfile.lock` file content (`default` and `develop` sections).
+///
+/// Entries without a `==` pin (git, path or editable installs) are skipped.
+pub fn parse_pipfile_lock(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let lock: PipfileLock = serde_json::from_str(content)
+        .map_err(|e| GuardianError::LockfileParseError(format!("Invalid JSON: {}", e)))?;
+
+    let packages = lock
+        .default
+        .into_iter()
+        .chain(lock.develop)
+        .filter_map(|(name, entry)| {
+            let version = entry.version?.strip_prefix("==")?.trim().to_string();
+            Some((name, version))
+        });
+    Ok(to_refs(packages))
+}
+
+/// Parses a `requirements.txt` file content.
+///
+/// Only exact pins (`name==1.2.3`, `name===1.2.3`) identify a version, so
+/// ranges, wildcards, option lines (`-r`, `-e`, `--hash`) and URLs are
+/// skipped.
+pub fn parse_requirements_txt(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let mut packages = Vec::new();
+    let mut logical = String::new();
+
+    for raw in content.lines() {
+        let line = match raw.find(" #") {
+            Some(idx) => &raw[..idx],
+            None if raw.trim_start().starts_with('#') => "",
+            None => raw,
+        };
+        if let Some(continued) = line.trim_end().strip_suffix('\\') {
+            logical.push_str(continued);
+            logical.push(' ');
+            continue;
+        }
+        logical.push_str(line);
+        if let Some(pin) = parse_requirement_pin(&logical) {
+            packages.push(pin);
+        }
+        logical.clear();
+    }
+
+    Ok(to_refs(packages))
+}
+
+fn parse_requirement_pin(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('-') {
+        return None;
+    }
+    // Drop environment markers and per-requirement options such as --hash.
+    let requirement = line.split(';').next()?;
+    let requirement = requirement.split(" --").next()?.trim();
+    if requirement.contains("://") || requirement.contains(" @ ") {
+        return None;
+    }
+
+    let (name, version) = requirement
+        .split_once("===")
+        .or_else(|| requirement.split_once("=="))?;
+    let name = name.split('[').next()?.trim();
+    let version = version.trim();
+    if name.is_empty()
+        || version.is_empty()
+        || version.contains(['*', ',', ' ', '<', '>', '!', '~'])
+    {
+        return None;
+    }
+    Some((name.to_string(), version.to_string()))
+}
+
+/// PEP 503 name normalization: case-insensitive, and runs of `-`, `_` and
+/// `.` are equivalent.
+fn normalize_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut in_separator = false;
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !in_separator {
+                out.push('-');
+            }
+            in_separator = true;
+        } else {
+            out.push(c.to_ascii_lowercase());
+            in_separator = false;
+        }
+    }
+    out
+}
+
+fn to_refs(packages: impl IntoIterator<Item = (String, String)>) -> Vec<PackageRef> {
+    let unique: HashSet<(String, String)> = packages
+        .into_iter()
+        .map(|(name, version)| (normalize_name(&name), version))
+        .collect();
+    let mut refs: Vec<PackageRef> = unique
+        .into_iter()
+        .map(|(name, version)| PackageRef {
+            ecosystem: Ecosystem::PyPI,
+            name,
+            version,
+        })
+        .collect();
+    refs.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(refs: &[PackageRef]) -> Vec<(&str, &str)> {
+        refs.iter()
+            .map(|r| (r.name.as_str(), r.version.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_poetry_fixture() {
+        let content = include_str!("../../../../tests/fixtures/python/poetry.lock");
+        let result = parse_poetry_lock(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("django", "3.2.4"),
+                ("requests", "2.25.0"),
+                ("zope-interface", "5.4.0")
+            ]
+        );
+        assert!(result.iter().all(|r| r.ecosystem == Ecosystem::PyPI));
+    }
+
+    #[test]
+    fn test_pipfile_fixture() {
+        let content = include_str!("../../../../tests/fixtures/python/Pipfile.lock");
+        let result = parse_pipfile_lock(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("jinja2", "2.10"),
+                ("pytest", "6.2.4"),
+                ("urllib3", "1.26.4")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_requirements_fixture() {
+        let content = include_str!("../../../../tests/fixtures/python/requirements.txt");
+        let result = parse_requirements_txt(content).unwrap();
+        assert_eq!(
+            pairs(&result),
+            vec![
+                ("cryptography", "3.3.1"),
+                ("pillow", "8.1.0"),
+                ("pyyaml", "5.3.1"),
+                ("requests", "2.25.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_requirements_skips_unpinned_lines() {
+        let content = r#"
+-r base.txt
+-e git+https://github.com/org/pkg.git#egg=pkg
+flask>=1.0
+numpy==1.*
+pandas>=1.0,<2
+local @ file:///tmp/local
+https://example.com/pkg.tar.gz
+"#;
+        assert!(parse_requirements_txt(content).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Zope.Interface"), "zope-interface");
+        assert_eq!(normalize_name("ruamel__yaml"), "ruamel-yaml");
+    }
+}
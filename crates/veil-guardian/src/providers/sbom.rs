@@ -0,0 +1,142 @@
+use crate::models::PackageRef;
+use crate::purl;
+use crate::GuardianError;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Parses an SBOM in CycloneDX JSON or SPDX JSON, detected from the
+/// document itself. Packages are taken from their purls; entries without a
+/// purl of a supported type are skipped.
+pub fn parse_sbom_json(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    let doc: Value = serde_json::from_str(content)
+        .map_err(|e| GuardianError::LockfileParseError(format!("SBOM is not JSON: {}", e)))?;
+
+    if doc.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+        Ok(parse_cyclonedx(&doc))
+    } else if doc.get("spdxVersion").is_some() {
+        Ok(parse_spdx(&doc))
+    } else {
+        Err(GuardianError::LockfileParseError(
+            "Unrecognized SBOM: expected CycloneDX (`bomFormat`) or SPDX (`spdxVersion`) JSON"
+                .to_string(),
+        ))
+    }
+}
+
+/// CycloneDX `components[]`, including nested `components`.
+fn parse_cyclonedx(doc: &Value) -> Vec<PackageRef> {
+    let mut purls = Vec::new();
+    let mut stack: Vec<&Value> = doc
+        .get("components")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .collect();
+    while let Some(component) = stack.pop() {
+        if let Some(purl) = component.get("purl").and_then(Value::as_str) {
+            purls.push(purl);
+        }
+        stack.extend(
+            component
+                .get("components")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten(),
+        );
+    }
+    to_refs(purls)
+}
+
+/// SPDX `packages[].externalRefs[]` of type `purl`.
+fn parse_spdx(doc: &Value) -> Vec<PackageRef> {
+    let purls = doc
+        .get("packages")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .flat_map(|package| {
+            package
+                .get("externalRefs")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+        })
+        .filter(|reference| reference.get("referenceType").and_then(Value::as_str) == Some("purl"))
+        .filter_map(|reference| reference.get("referenceLocator").and_then(Value::as_str))
+        .collect();
+    to_refs(purls)
+}
+
+fn to_refs(purls: Vec<&str>) -> Vec<PackageRef> {
+    let mut seen = HashSet::new();
+    let mut refs: Vec<PackageRef> = purls
+        .into_iter()
+        .filter_map(purl::parse)
+        .filter(|package| seen.insert(package.clone()))
+        .collect();
+    refs.sort_by(|a, b| {
+        (a.ecosystem.osv_name(), &a.name, &a.version).cmp(&(
+            b.ecosystem.osv_name(),
+            &b.name,
+            &b.version,
+        ))
+    });
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Ecosystem;
+
+    #[test]
+    fn reads_cyclonedx_components_recursively() {
+        let content = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "components": [
+                { "type": "library", "name": "serde", "purl": "pkg:cargo/serde@1.0.200",
+                  "components": [
+                    { "type": "library", "name": "core", "purl": "pkg:npm/%40babel/core@7.24.0" }
+                  ] },
+                { "type": "application", "name": "no-purl" },
+                { "type": "library", "name": "rails", "purl": "pkg:gem/rails@7.0.0" }
+            ]
+        }"#;
+        let packages = parse_sbom_json(content).unwrap();
+        let names: Vec<(Ecosystem, &str)> = packages
+            .iter()
+            .map(|p| (p.ecosystem, p.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![(Ecosystem::Rust, "serde"), (Ecosystem::Npm, "@babel/core")]
+        );
+    }
+
+    #[test]
+    fn reads_spdx_purl_references() {
+        let content = r#"{
+            "spdxVersion": "SPDX-2.3",
+            "packages": [
+                { "SPDXID": "SPDXRef-1", "name": "requests", "externalRefs": [
+                    { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+                      "referenceLocator": "pkg:pypi/requests@2.31.0" },
+                    { "referenceCategory": "SECURITY", "referenceType": "cpe23Type",
+                      "referenceLocator": "cpe:2.3:a:python:requests:2.31.0:*:*:*:*:*:*:*" }
+                ] },
+                { "SPDXID": "SPDXRef-2", "name": "root" }
+            ]
+        }"#;
+        let packages = parse_sbom_json(content).unwrap();
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].ecosystem, Ecosystem::PyPI);
+        assert_eq!(packages[0].version, "2.31.0");
+    }
+
+    #[test]
+    fn rejects_unknown_documents() {
+        assert!(parse_sbom_json(r#"{ "name": "x" }"#).is_err());
+        assert!(parse_sbom_json("not json").is_err());
+    }
+}
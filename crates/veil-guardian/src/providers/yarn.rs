@@ -1,12 +1,22 @@
+use crate::graph::DependencyGraph;
 use crate::models::{Ecosystem, PackageRef};
 use crate::GuardianError;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Parses a `yarn.lock` file content and extracts package references.
 ///
 /// This function distinguishes between Yarn Berry (v2+, YAML-based with `__metadata`)
 /// and Yarn Classic (v1, stanza-based).
 pub fn parse_yarn_lock(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
+    parse_yarn_lock_with_graph(content).map(|(refs, _)| refs)
+}
+
+/// Like [`parse_yarn_lock`], also returning which package depends on which.
+/// Berry lockfiles list the workspaces, whose dependencies become the graph
+/// roots; classic lockfiles do not record direct dependencies.
+pub fn parse_yarn_lock_with_graph(
+    content: &str,
+) -> Result<(Vec<PackageRef>, DependencyGraph), GuardianError> {
     // strict Berry detection: try parsing as YAML and look for top-level keys
     if let Ok(value) = serde_yaml::from_str::<serde_yaml::Value>(content) {
         if let Some(mapping) = value.as_mapping() {
@@ -24,8 +34,20 @@ pub fn parse_yarn_lock(content: &str) -> Result<Vec<PackageRef>, GuardianError>
     parse_yarn_classic(content)
 }
 
-fn parse_yarn_berry(mapping: &serde_yaml::Mapping) -> Result<Vec<PackageRef>, GuardianError> {
-    let mut packages = HashSet::new();
+/// One lockfile entry: the descriptors it resolves, its version and the
+/// `(name, range)` pairs it depends on.
+#[derive(Default)]
+struct Entry {
+    selectors: Vec<String>,
+    version: Option<String>,
+    dependencies: Vec<(String, String)>,
+    workspace: bool,
+}
+
+fn parse_yarn_berry(
+    mapping: &serde_yaml::Mapping,
+) -> Result<(Vec<PackageRef>, DependencyGraph), GuardianError> {
+    let mut entries = Vec::new();
 
     for (key, value) in mapping {
         let key_str = match key.as_str() {
@@ -38,6 +60,26 @@ fn parse_yarn_berry(mapping: &serde_yaml::Mapping) -> Result<Vec<PackageRef>, Gu
             continue;
         }
 
+        let dependencies = ["dependencies", "optionalDependencies"]
+            .iter()
+            .filter_map(|field| value.get(field).and_then(|deps| deps.as_mapping()))
+            .flatten()
+            .filter_map(|(name, range)| {
+                Some((name.as_str()?.to_string(), range.as_str()?.to_string()))
+            })
+            .collect();
+
+        // Workspaces are not packages, but their dependencies are the
+        // project's direct dependencies.
+        if key_str.contains("@workspace:") {
+            entries.push(Entry {
+                dependencies,
+                workspace: true,
+                ..Entry::default()
+            });
+            continue;
+        }
+
         // Berry keys can be comma-separated: "pkg@protocol:ver, pkg@protocol:ver2"
         // We need to check if ANY of the selectors denote a protocol we should skip.
         // If *all* selectors are skipped protocols, we skip the entry.
@@ -53,8 +95,7 @@ fn parse_yarn_berry(mapping: &serde_yaml::Mapping) -> Result<Vec<PackageRef>, Gu
         // Example: "lodash@npm:4.17.21" -> OK
         // Example: "mypkg@workspace:." -> SKIP
 
-        if key_str.contains("@workspace:")
-            || key_str.contains("@patch:")
+        if key_str.contains("@patch:")
             || key_str.contains("@portal:")
             || key_str.contains("@link:")
             || key_str.contains("@file:")
@@ -68,35 +109,27 @@ fn parse_yarn_berry(mapping: &serde_yaml::Mapping) -> Result<Vec<PackageRef>, Gu
             None => continue, // Entry without version? Skip.
         };
 
-        // Extract package name from the key.
-        // The key might be "a@npm:1, b@npm:1". We take the first one to determine the name.
-        let first_selector = key_str.split(',').next().unwrap_or(key_str).trim();
-
-        // Parse name from "name@..."
-        // Handles scoped packages "@scope/pkg@..."
-        let name = parse_package_name(first_selector);
-
-        if !name.is_empty() {
-            packages.insert((name.to_string(), version.to_string()));
-        }
+        entries.push(Entry {
+            selectors: key_str.split(',').map(|s| s.trim().to_string()).collect(),
+            version: Some(version.to_string()),
+            dependencies,
+            workspace: false,
+        });
     }
 
-    let mut result_vec: Vec<_> = packages.into_iter().collect();
-    result_vec.sort();
-
-    Ok(result_vec
-        .into_iter()
-        .map(|(name, version)| PackageRef {
-            ecosystem: Ecosystem::Npm,
-            name,
-            version,
-        })
-        .collect())
+    // Berry dependency ranges usually carry the protocol ("npm:^1.0.0"),
+    // but a bare range refers to the npm registry too.
+    Ok(build(&entries, |name, range| {
+        vec![
+            format!("{}@{}", name, range),
+            format!("{}@npm:{}", name, range),
+        ]
+    }))
 }
 
-fn parse_yarn_classic(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
-    let mut packages = HashSet::new();
-    let mut current_keys: Vec<String> = Vec::new();
+fn parse_yarn_classic(content: &str) -> Result<(Vec<PackageRef>, DependencyGraph), GuardianError> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut in_dependencies = false;
 
     for line in content.lines() {
         let line = line.trim_end();
@@ -111,38 +144,105 @@ fn parse_yarn_classic(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
         // Example: "lodash@^4.17.15, lodash@4.17.21":
         // Example: lodash@^4.17.15, lodash@4.17.21:
         if !line.starts_with(' ') && line.ends_with(':') {
-            // New stanza starts, reset keys
-            current_keys.clear();
+            // New stanza starts
+            in_dependencies = false;
 
             // Remove trailing ':'
             let header = &line[..line.len() - 1];
 
-            for sel in split_selectors_quote_aware(header) {
-                if should_skip_protocol(&sel) {
-                    continue;
-                }
-                current_keys.push(sel);
+            entries.push(Entry {
+                selectors: split_selectors_quote_aware(header)
+                    .into_iter()
+                    .filter(|sel| !should_skip_protocol(sel))
+                    .collect(),
+                ..Entry::default()
+            });
+            continue;
+        }
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+
+        if line.starts_with("    ") {
+            // Line is like: "    name \"^1.0.0\"" inside a dependencies block
+            if in_dependencies {
+                let mut parts = line.trim().splitn(2, ' ');
+                let name = parts.next().unwrap_or_default().trim_matches('"');
+                let range = parts.next().unwrap_or_default().trim().trim_matches('"');
+                entry
+                    .dependencies
+                    .push((name.to_string(), range.to_string()));
             }
             continue;
-        } else if line.trim_start().starts_with("version ") {
+        }
+
+        let field = line.trim_start();
+        in_dependencies = field == "dependencies:" || field == "optionalDependencies:";
+        if let Some(rest) = field.strip_prefix("version ") {
             // Line is like: "  version \"1.2.3\""
-            // Get text after "version "
-            let rest = line.trim_start()[8..].trim();
+            let rest = rest.trim();
             // Strip quotes
             let version = if rest.starts_with('"') && rest.ends_with('"') {
                 &rest[1..rest.len() - 1]
             } else {
                 rest
             };
+            entry.version = Some(version.to_string());
+        }
+    }
 
-            // Associate this version with all current keys
-            for key in &current_keys {
-                // Key is like "lodash@^4.17.15" or "@types/node@*"
-                // Extract name
-                let name = parse_package_name(key);
-                if !name.is_empty() {
-                    packages.insert((name.to_string(), version.to_string()));
-                }
+    Ok(build(&entries, |name, range| {
+        vec![format!("{}@{}", name, range)]
+    }))
+}
+
+/// Collects the packages of `entries` and links each dependency to the
+/// entry resolving one of the descriptors `descriptors(name, range)`.
+fn build(
+    entries: &[Entry],
+    descriptors: impl Fn(&str, &str) -> Vec<String>,
+) -> (Vec<PackageRef>, DependencyGraph) {
+    let mut packages = HashSet::new();
+    let mut by_selector: HashMap<&str, PackageRef> = HashMap::new();
+
+    for entry in entries {
+        let Some(version) = &entry.version else {
+            continue;
+        };
+        for selector in &entry.selectors {
+            // Selector is like "lodash@^4.17.15" or "@types/node@*"
+            // Extract name
+            let name = parse_package_name(selector);
+            if name.is_empty() {
+                continue;
+            }
+            let package = PackageRef {
+                ecosystem: Ecosystem::Npm,
+                name,
+                version: version.clone(),
+            };
+            packages.insert((package.name.clone(), package.version.clone()));
+            by_selector.insert(selector, package);
+        }
+    }
+
+    let mut graph = DependencyGraph::new();
+    for entry in entries {
+        let from = entry
+            .selectors
+            .iter()
+            .find_map(|selector| by_selector.get(selector.as_str()));
+        for (name, range) in &entry.dependencies {
+            let Some(to) = descriptors(name, range)
+                .iter()
+                .find_map(|descriptor| by_selector.get(descriptor.as_str()))
+            else {
+                continue;
+            };
+            if entry.workspace {
+                graph.add_root(to.clone());
+            } else if let Some(from) = from {
+                graph.add_edge(from.clone(), to.clone());
             }
         }
     }
@@ -150,14 +250,15 @@ fn parse_yarn_classic(content: &str) -> Result<Vec<PackageRef>, GuardianError> {
     let mut result_vec: Vec<_> = packages.into_iter().collect();
     result_vec.sort();
 
-    Ok(result_vec
+    let refs = result_vec
         .into_iter()
         .map(|(name, version)| PackageRef {
             ecosystem: Ecosystem::Npm,
             name,
             version,
         })
-        .collect())
+        .collect();
+    (refs, graph)
 }
 
 ///
@@ -379,4 +480,82 @@ lodash@^4.17.15, lodash@4.17.21:
         assert_eq!(result[0].name, "ok");
         assert_eq!(result[0].version, "1.0.1");
     }
+
+    fn chains(graph: &DependencyGraph, name: &str, version: &str) -> Vec<Vec<String>> {
+        let target = PackageRef {
+            ecosystem: Ecosystem::Npm,
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        graph
+            .shortest_paths(&target, crate::graph::MAX_PATHS)
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|p| format!("{}@{}", p.name, p.version))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_berry_graph_starts_at_workspace_dependencies() {
+        let content = r#"
+__metadata:
+  version: 6
+
+"app@workspace:.":
+  version: 0.0.0-use.local
+  dependencies:
+    express: "npm:^4.17.0"
+
+"express@npm:^4.17.0":
+  version: 4.17.1
+  dependencies:
+    qs: "npm:6.7.0"
+
+"qs@npm:6.7.0":
+  version: 6.7.0
+"#;
+        let (refs, graph) = parse_yarn_lock_with_graph(content).unwrap();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(
+            chains(&graph, "qs", "6.7.0"),
+            vec![vec!["express@4.17.1", "qs@6.7.0"]]
+        );
+    }
+
+    #[test]
+    fn test_classic_graph_follows_dependency_blocks() {
+        let content = r#"
+# yarn lockfile v1
+
+express@^4.17.0:
+  version "4.17.1"
+  dependencies:
+    body-parser "1.19.0"
+    "@scope/util" ">= 1.0.0 < 2"
+
+body-parser@1.19.0:
+  version "1.19.0"
+  dependencies:
+    qs "6.7.0"
+
+"@scope/util@>= 1.0.0 < 2":
+  version "1.2.0"
+
+qs@6.7.0:
+  version "6.7.0"
+"#;
+        let (refs, graph) = parse_yarn_lock_with_graph(content).unwrap();
+        assert_eq!(refs.len(), 4);
+        assert_eq!(
+            chains(&graph, "qs", "6.7.0"),
+            vec![vec!["express@4.17.1", "body-parser@1.19.0", "qs@6.7.0"]]
+        );
+        assert_eq!(
+            chains(&graph, "@scope/util", "1.2.0"),
+            vec![vec!["express@4.17.1", "@scope/util@1.2.0"]]
+        );
+    }
 }
@@ -0,0 +1,193 @@
+//! Package URLs (`pkg:type/namespace/name@version`) for the ecosystems
+//! guardian understands, as used by CycloneDX and SPDX documents.
+
+use crate::models::{Ecosystem, PackageRef};
+
+/// Maps a purl to a package. `None` for other purl types, or when the
+/// version is missing.
+pub fn parse(purl: &str) -> Option<PackageRef> {
+    let rest = purl.trim().strip_prefix("pkg:")?;
+    // Qualifiers and subpath do not identify the package version.
+    let rest = rest.split(['?', '#']).next()?;
+    let (path, version) = rest.rsplit_once('@')?;
+    let version = decode(version);
+    if version.is_empty() {
+        return None;
+    }
+    let (kind, path) = path.split_once('/')?;
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(decode)
+        .collect();
+    let last = segments.last()?.clone();
+
+    let (ecosystem, name, version) = match kind.to_ascii_lowercase().as_str() {
+        "cargo" => (Ecosystem::Rust, last, version),
+        "npm" => (Ecosystem::Npm, segments.join("/"), version),
+        "pypi" => (Ecosystem::PyPI, normalize_pypi(&last), version),
+        "golang" => (
+            Ecosystem::Go,
+            segments.join("/"),
+            version
+                .trim_start_matches('v')
+                .trim_end_matches("+incompatible")
+                .to_string(),
+        ),
+        "maven" if segments.len() >= 2 => (
+            Ecosystem::Maven,
+            format!("{}:{}", segments[..segments.len() - 1].join("."), last),
+            version,
+        ),
+        _ => return None,
+    };
+    Some(PackageRef {
+        ecosystem,
+        name,
+        version,
+    })
+}
+
+/// The purl of a package, the inverse of [`parse`].
+pub fn format(package: &PackageRef) -> String {
+    match package.ecosystem {
+        Ecosystem::Rust => format!(
+            "pkg:cargo/{}@{}",
+            encode(&package.name),
+            encode(&package.version)
+        ),
+        Ecosystem::Npm => match package.name.split_once('/') {
+            Some((scope, name)) => format!(
+                "pkg:npm/{}/{}@{}",
+                encode(scope),
+                encode(name),
+                encode(&package.version)
+            ),
+            None => format!(
+                "pkg:npm/{}@{}",
+                encode(&package.name),
+                encode(&package.version)
+            ),
+        },
+        Ecosystem::PyPI => format!(
+            "pkg:pypi/{}@{}",
+            encode(&package.name),
+            encode(&package.version)
+        ),
+        Ecosystem::Go => format!(
+            "pkg:golang/{}@v{}",
+            package
+                .name
+                .split('/')
+                .map(encode)
+                .collect::<Vec<_>>()
+                .join("/"),
+            encode(&package.version)
+        ),
+        Ecosystem::Maven => {
+            let (group, artifact) = package.name.split_once(':').unwrap_or(("", &package.name));
+            format!(
+                "pkg:maven/{}/{}@{}",
+                encode(group),
+                encode(artifact),
+                encode(&package.version)
+            )
+        }
+    }
+}
+
+fn normalize_pypi(name: &str) -> String {
+    name.to_ascii_lowercase().replace(['_', '.'], "-")
+}
+
+fn decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'+' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(ecosystem: Ecosystem, name: &str, version: &str) -> PackageRef {
+        PackageRef {
+            ecosystem,
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_each_supported_type() {
+        assert_eq!(
+            parse("pkg:cargo/serde@1.0.200"),
+            Some(package(Ecosystem::Rust, "serde", "1.0.200"))
+        );
+        assert_eq!(
+            parse("pkg:npm/%40babel/core@7.24.0?repository_url=x"),
+            Some(package(Ecosystem::Npm, "@babel/core", "7.24.0"))
+        );
+        assert_eq!(
+            parse("pkg:pypi/Django_Rest@3.0"),
+            Some(package(Ecosystem::PyPI, "django-rest", "3.0"))
+        );
+        assert_eq!(
+            parse("pkg:golang/github.com/gin-gonic/gin@v1.9.1#subpath"),
+            Some(package(Ecosystem::Go, "github.com/gin-gonic/gin", "1.9.1"))
+        );
+        assert_eq!(
+            parse("pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1?type=jar"),
+            Some(package(
+                Ecosystem::Maven,
+                "org.apache.logging.log4j:log4j-core",
+                "2.14.1"
+            ))
+        );
+        assert_eq!(parse("pkg:gem/rails@7.0.0"), None);
+        assert_eq!(parse("pkg:npm/left-pad"), None);
+    }
+
+    #[test]
+    fn format_roundtrips_through_parse() {
+        for p in [
+            package(Ecosystem::Rust, "serde", "1.0.200"),
+            package(Ecosystem::Npm, "@babel/core", "7.24.0"),
+            package(Ecosystem::PyPI, "django-rest", "3.0"),
+            package(Ecosystem::Go, "github.com/gin-gonic/gin", "1.9.1"),
+            package(Ecosystem::Maven, "org.apache:commons-text", "1.9"),
+        ] {
+            assert_eq!(parse(&format(&p)).as_ref(), Some(&p), "{}", format(&p));
+        }
+        assert_eq!(
+            format(&package(Ecosystem::Npm, "@babel/core", "7.24.0")),
+            "pkg:npm/%40babel/core@7.24.0"
+        );
+    }
+}
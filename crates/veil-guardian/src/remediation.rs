@@ -0,0 +1,368 @@
+//! Fixed-version advice.
+//!
+//! An advisory knows which versions it affects either through OSV
+//! `affected[].ranges[].events` (only present once details were fetched) or
+//! through the builtin database's `vulnerable_versions` requirement. From
+//! that we derive the smallest version above the current one that is no
+//! longer affected, first per advisory and then for the whole package.
+
+use crate::models::{Advisory, Ecosystem};
+use crate::report::Vulnerability;
+use crate::version;
+use semver::{Comparator, Op, Version, VersionReq};
+use std::cmp::Ordering;
+
+/// Upper bound on upgrade rounds when one advisory's fix lands inside
+/// another advisory's range.
+const MAX_ROUNDS: usize = 16;
+
+/// The smallest version above `vuln.version` that none of its advisories
+/// affect. `None` if any advisory has no known fix.
+pub fn fixed_version(vuln: &Vulnerability) -> Option<String> {
+    if vuln.advisories.is_empty() {
+        return None;
+    }
+
+    let mut candidate = vuln.version.clone();
+    for _ in 0..MAX_ROUNDS {
+        let mut next: Option<String> = None;
+        for advisory in &vuln.advisories {
+            // The scanned version was reported as affected, whatever the
+            // ranges say.
+            let affected = candidate == vuln.version
+                || affects(advisory, vuln.ecosystem, &vuln.package_name, &candidate)?;
+            if !affected {
+                continue;
+            }
+            let fix = advisory_fix(advisory, vuln.ecosystem, &vuln.package_name, &candidate)?;
+            next = Some(match next {
+                Some(current) if is_greater(vuln.ecosystem, &current, &fix) => current,
+                _ => fix,
+            });
+        }
+        match next {
+            Some(next) => candidate = next,
+            None => return Some(candidate),
+        }
+    }
+    None
+}
+
+/// Advisories on `vuln` that some upgrade resolves, even when the package
+/// as a whole has no fixed version because another advisory has none.
+pub fn fixable_advisories(vuln: &Vulnerability) -> impl Iterator<Item = &Advisory> {
+    vuln.advisories.iter().filter(|advisory| {
+        advisory_fix(advisory, vuln.ecosystem, &vuln.package_name, &vuln.version).is_some()
+    })
+}
+
+/// The smallest version above `current` that `advisory` does not affect.
+pub fn advisory_fix(
+    advisory: &Advisory,
+    ecosystem: Ecosystem,
+    package: &str,
+    current: &str,
+) -> Option<String> {
+    let mut candidates = fix_candidates(advisory, ecosystem, package);
+    candidates.sort_by(|a, b| version::compare(ecosystem, a, b).unwrap_or(Ordering::Equal));
+    candidates.dedup();
+    candidates.into_iter().find(|candidate| {
+        is_greater(ecosystem, candidate, current)
+            && affects(advisory, ecosystem, package, candidate) == Some(false)
+    })
+}
+
+/// Whether `advisory` affects `version`. `None` when the advisory carries
+/// no range information or the version cannot be ordered.
+pub fn affects(
+    advisory: &Advisory,
+    ecosystem: Ecosystem,
+    package: &str,
+    version: &str,
+) -> Option<bool> {
+    version::compare(ecosystem, version, version)?;
+
+    if let Some(affected) = osv_affected(advisory, package) {
+        let mut any_range = false;
+        for entry in affected {
+            let listed = entry
+                .get("versions")
+                .and_then(|v| v.as_array())
+                .is_some_and(|versions| versions.iter().any(|v| v.as_str() == Some(version)));
+            if listed {
+                return Some(true);
+            }
+            for range in ranges(entry) {
+                any_range = true;
+                if range_affects(range, ecosystem, version) {
+                    return Some(true);
+                }
+            }
+        }
+        if any_range {
+            return Some(false);
+        }
+    }
+
+    builtin_req(advisory, ecosystem)
+        .and_then(|req| Some(req.matches(&Version::parse(version).ok()?)))
+}
+
+fn is_greater(ecosystem: Ecosystem, a: &str, b: &str) -> bool {
+    version::compare(ecosystem, a, b) == Some(Ordering::Greater)
+}
+
+/// `affected` entries for `package`. Entries without a package name apply to
+/// every package the advisory was reported for.
+fn osv_affected<'a>(advisory: &'a Advisory, package: &str) -> Option<Vec<&'a serde_json::Value>> {
+    let affected = advisory.details.as_ref()?.get("affected")?.as_array()?;
+    let normalized = normalize_name(package);
+    Some(
+        affected
+            .iter()
+            .filter(|entry| {
+                entry
+                    .get("package")
+                    .and_then(|p| p.get("name"))
+                    .and_then(|n| n.as_str())
+                    .is_none_or(|name| normalize_name(name) == normalized)
+            })
+            .collect(),
+    )
+}
+
+/// Ranges that order by version. `GIT` ranges hold commit hashes.
+fn ranges(entry: &serde_json::Value) -> impl Iterator<Item = &serde_json::Value> {
+    entry
+        .get("ranges")
+        .and_then(|r| r.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|range| range.get("type").and_then(|t| t.as_str()) != Some("GIT"))
+}
+
+fn events(range: &serde_json::Value) -> impl Iterator<Item = (&str, &str)> {
+    range
+        .get("events")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|event| {
+            let event = event.as_object()?;
+            ["introduced", "fixed", "last_affected", "limit"]
+                .into_iter()
+                .find_map(|kind| Some((kind, event.get(kind)?.as_str()?)))
+        })
+}
+
+/// OSV range evaluation: walking events in version order, `introduced`
+/// opens the range and `fixed`/`last_affected` close it.
+fn range_affects(range: &serde_json::Value, ecosystem: Ecosystem, version: &str) -> bool {
+    let mut events: Vec<(&str, &str)> = events(range)
+        .filter(|(kind, v)| {
+            *kind != "limit" && (*v == "0" || version::compare(ecosystem, v, v).is_some())
+        })
+        .collect();
+    events.sort_by(|(_, a), (_, b)| match (*a == "0", *b == "0") {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => version::compare(ecosystem, a, b).unwrap_or(Ordering::Equal),
+    });
+
+    let mut affected = false;
+    for (kind, at) in events {
+        let order = if at == "0" {
+            Ordering::Greater
+        } else {
+            match version::compare(ecosystem, version, at) {
+                Some(order) => order,
+                None => continue,
+            }
+        };
+        match kind {
+            "introduced" if order != Ordering::Less => affected = true,
+            "fixed" if order != Ordering::Less => affected = false,
+            "last_affected" if order == Ordering::Greater => affected = false,
+            _ => {}
+        }
+    }
+    affected
+}
+
+fn fix_candidates(advisory: &Advisory, ecosystem: Ecosystem, package: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    if let Some(affected) = osv_affected(advisory, package) {
+        for entry in affected {
+            for range in ranges(entry) {
+                candidates.extend(
+                    events(range)
+                        .filter(|(kind, v)| {
+                            *kind == "fixed" && version::compare(ecosystem, v, v).is_some()
+                        })
+                        .map(|(_, v)| v.to_string()),
+                );
+            }
+        }
+    }
+    if let Some(req) = builtin_req(advisory, ecosystem) {
+        candidates.extend(req.comparators.iter().filter_map(upper_bound));
+    }
+    candidates
+}
+
+/// `vulnerable_versions` is only meaningful for builtin (semver) entries;
+/// OSV advisories carry `*`.
+fn builtin_req(advisory: &Advisory, ecosystem: Ecosystem) -> Option<&VersionReq> {
+    (ecosystem == Ecosystem::Rust && advisory.vulnerable_versions != VersionReq::STAR)
+        .then_some(&advisory.vulnerable_versions)
+}
+
+/// The first version past a `<` or `<=` comparator.
+fn upper_bound(comparator: &Comparator) -> Option<String> {
+    let minor = comparator.minor.unwrap_or(0);
+    let patch = comparator.patch.unwrap_or(0);
+    let version = match comparator.op {
+        Op::Less => Version {
+            pre: comparator.pre.clone(),
+            ..Version::new(comparator.major, minor, patch)
+        },
+        Op::LessEq => match (comparator.minor, comparator.patch) {
+            (Some(_), Some(_)) => Version::new(comparator.major, minor, patch + 1),
+            (Some(_), None) => Version::new(comparator.major, minor + 1, 0),
+            _ => Version::new(comparator.major + 1, 0, 0),
+        },
+        _ => return None,
+    };
+    Some(version.to_string())
+}
+
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace(['_', '.'], "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn advisory(id: &str, req: &str, details: Option<serde_json::Value>) -> Advisory {
+        Advisory {
+            id: id.to_string(),
+            crate_name: "pkg".to_string(),
+            vulnerable_versions: VersionReq::parse(req).unwrap(),
+            description: String::new(),
+            aliases: Vec::new(),
+            details,
+            cache_status: None,
+            last_fetched_at: None,
+        }
+    }
+
+    fn osv(events: serde_json::Value) -> Option<serde_json::Value> {
+        Some(json!({
+            "affected": [{
+                "package": { "name": "pkg" },
+                "ranges": [{ "type": "ECOSYSTEM", "events": events }]
+            }]
+        }))
+    }
+
+    fn vuln(ecosystem: Ecosystem, version: &str, advisories: Vec<Advisory>) -> Vulnerability {
+        Vulnerability {
+            ecosystem,
+            package_name: "pkg".to_string(),
+            version: version.to_string(),
+            advisories,
+            locations: Vec::new(),
+            fixed_version: None,
+            severity: None,
+            cvss_score: None,
+            dependency_paths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn picks_the_fix_of_the_range_the_version_is_in() {
+        let adv = advisory(
+            "OSV-1",
+            "*",
+            osv(json!([
+                { "introduced": "0" }, { "fixed": "1.2.3" },
+                { "introduced": "2.0.0" }, { "fixed": "2.0.5" }
+            ])),
+        );
+        assert_eq!(
+            advisory_fix(&adv, Ecosystem::Npm, "pkg", "2.0.1").as_deref(),
+            Some("2.0.5")
+        );
+        assert_eq!(
+            advisory_fix(&adv, Ecosystem::Npm, "pkg", "1.0.0").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(affects(&adv, Ecosystem::Npm, "pkg", "1.5.0"), Some(false));
+    }
+
+    #[test]
+    fn derives_fix_from_builtin_requirement() {
+        let adv = advisory("GF-002", ">= 2.0.0, < 2.1.3", None);
+        assert_eq!(
+            advisory_fix(&adv, Ecosystem::Rust, "pkg", "2.0.0").as_deref(),
+            Some("2.1.3")
+        );
+        let adv = advisory("GF-X", "<= 1.4", None);
+        assert_eq!(
+            advisory_fix(&adv, Ecosystem::Rust, "pkg", "1.4.2").as_deref(),
+            Some("1.5.0")
+        );
+    }
+
+    #[test]
+    fn package_fix_clears_every_advisory() {
+        let a = advisory(
+            "OSV-A",
+            "*",
+            osv(json!([{ "introduced": "0" }, { "fixed": "1.1.0" }])),
+        );
+        let b = advisory(
+            "OSV-B",
+            "*",
+            osv(json!([{ "introduced": "1.1.0" }, { "fixed": "1.3.0" }])),
+        );
+        let v = vuln(Ecosystem::PyPI, "1.0", vec![a.clone(), b]);
+        assert_eq!(fixed_version(&v).as_deref(), Some("1.3.0"));
+
+        let unfixed = advisory(
+            "OSV-C",
+            "*",
+            osv(json!([{ "introduced": "0" }, { "last_affected": "9.0" }])),
+        );
+        let v = vuln(Ecosystem::PyPI, "1.0", vec![a, unfixed]);
+        assert_eq!(fixed_version(&v), None);
+    }
+
+    #[test]
+    fn one_unfixed_advisory_leaves_the_others_fixable() {
+        let fixed = advisory(
+            "OSV-A",
+            "*",
+            osv(json!([{ "introduced": "0" }, { "fixed": "1.1.0" }])),
+        );
+        let unfixed = advisory(
+            "OSV-B",
+            "*",
+            osv(json!([{ "introduced": "0" }, { "last_affected": "9.0" }])),
+        );
+        let v = vuln(Ecosystem::PyPI, "1.0", vec![fixed, unfixed]);
+        assert_eq!(fixed_version(&v), None);
+        let fixable: Vec<_> = fixable_advisories(&v).map(|a| a.id.as_str()).collect();
+        assert_eq!(fixable, ["OSV-A"]);
+    }
+
+    #[test]
+    fn advisories_without_ranges_have_no_fix() {
+        let adv = advisory("OSV-1", "*", None);
+        let v = vuln(Ecosystem::Npm, "1.0.0", vec![adv]);
+        assert_eq!(fixed_version(&v), None);
+    }
+}
@@ -1,22 +1,96 @@
-use crate::models::{Advisory, Ecosystem};
+use crate::cvss;
+use crate::models::{Advisory, Ecosystem, PackageRef};
+use crate::remediation;
+use crate::sbom;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Serialize, Default)]
 pub struct ScanResult {
     pub vulnerabilities: Vec<Vulnerability>,
     pub scanned_crates: usize,
+    /// Advisories accepted by an active guardian exception.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<SuppressedAdvisory>,
+    /// Exceptions that matched but have expired; their advisories are
+    /// reported again.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub expired_exceptions: Vec<SuppressedAdvisory>,
+    /// Imported OSV databases that answered an offline scan.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub offline_databases: Vec<OfflineDatabase>,
+    /// Packages whose declared license breaks the configured license policy.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub license_violations: Vec<LicenseViolation>,
+    /// Every package the scanned lockfiles pin, for SBOM output.
+    #[serde(skip)]
+    pub packages: Vec<PackageRef>,
 }
 
+/// One advisory matched by a guardian exception entry.
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SuppressedAdvisory {
+    pub ecosystem: Ecosystem,
+    pub package_name: String,
+    pub version: String,
+    pub advisory_id: String,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A package whose license the license policy rejects.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct LicenseViolation {
+    pub ecosystem: Ecosystem,
+    pub package_name: String,
+    pub version: String,
+    /// Declared SPDX expression; absent when it could not be determined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    pub reason: String,
+    pub locations: Vec<String>,
+}
+
+/// An imported OSV export (`veil guardian db import`) used offline.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct OfflineDatabase {
+    pub ecosystem: Ecosystem,
+    pub source: String,
+    pub advisories: usize,
+    pub imported_at: DateTime<Utc>,
+    /// Newest advisory modification in the export.
+    pub updated_at: DateTime<Utc>,
+    /// Days between `updated_at` and the scan.
+    pub age_days: i64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct Vulnerability {
     pub ecosystem: Ecosystem,
     pub package_name: String,
     pub version: String,
     pub advisories: Vec<Advisory>,
     pub locations: Vec<String>,
+    /// Smallest version that no listed advisory affects, when every advisory
+    /// has a known fix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_version: Option<String>,
+    /// Most severe rating among the advisories that have one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    /// Highest CVSS base score among the advisories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_score: Option<f64>,
+    /// Shortest chains of `name@version` from a direct dependency down to
+    /// this package, for lockfiles that record the dependency graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependency_paths: Vec<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum Severity {
     Critical,
     High,
@@ -26,7 +100,7 @@ pub enum Severity {
 }
 
 impl Severity {
-    fn from_str(s: &str) -> Self {
+    fn from_label(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "critical" => Severity::Critical,
             "high" => Severity::High,
@@ -36,6 +110,118 @@ impl Severity {
             _ => Severity::Unknown,
         }
     }
+
+    /// CVSS qualitative rating of a base score. A 0.0 score rates "None",
+    /// which has no counterpart here.
+    pub fn from_score(score: f64) -> Self {
+        if score >= 9.0 {
+            Severity::Critical
+        } else if score >= 7.0 {
+            Severity::High
+        } else if score >= 4.0 {
+            Severity::Medium
+        } else if score > 0.0 {
+            Severity::Low
+        } else {
+            Severity::Unknown
+        }
+    }
+
+    /// Whether `self` is at least as severe as `threshold`. `Unknown` never
+    /// reaches a threshold.
+    pub fn meets(self, threshold: Severity) -> bool {
+        self != Severity::Unknown && self <= threshold
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Critical => "CRITICAL",
+            Severity::High => "HIGH",
+            Severity::Medium => "MEDIUM",
+            Severity::Low => "LOW",
+            Severity::Unknown => "UNKNOWN",
+        })
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "crit" => Ok(Severity::Critical),
+            "med" => Ok(Severity::Medium),
+            other => match Severity::from_label(other) {
+                Severity::Unknown => Err(format!(
+                    "Invalid severity: {s}. Use Low|Medium|High|Critical"
+                )),
+                severity => Ok(severity),
+            },
+        }
+    }
+}
+
+/// Severity and CVSS base score of one advisory, from OSV details: CVSS
+/// vectors in `severity[]` (v3 preferred over v4), then the
+/// `database_specific.severity` label.
+pub fn advisory_severity(advisory: &Advisory) -> (Severity, Option<f64>) {
+    let Some(details) = &advisory.details else {
+        return (Severity::Unknown, None);
+    };
+
+    let preferred = preferred_cvss(details);
+    if let Some((_, score)) = preferred {
+        let severity = Severity::from_score(score);
+        if severity != Severity::Unknown {
+            return (severity, Some(score));
+        }
+    }
+
+    let label = details
+        .get("database_specific")
+        .and_then(|d| d.get("severity"))
+        .and_then(|s| s.as_str())
+        .map_or(Severity::Unknown, Severity::from_label);
+    (label, preferred.map(|(_, score)| score))
+}
+
+/// The CVSS vector an advisory's score is taken from, with that score: the
+/// first v3 vector, else the first v4 vector.
+pub(crate) fn preferred_cvss(details: &serde_json::Value) -> Option<(&str, f64)> {
+    let scores: Vec<(&str, f64)> = details
+        .get("severity")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("score").and_then(|s| s.as_str()))
+        .filter_map(|vector| Some((vector, cvss::base_score(vector)?)))
+        .collect();
+    scores
+        .iter()
+        .find(|(vector, _)| !vector.starts_with("CVSS:4.0/"))
+        .or_else(|| scores.first())
+        .copied()
+}
+
+impl Vulnerability {
+    /// Recomputes the per-package summary (fixed version, severity, score)
+    /// from the current advisories.
+    pub fn refresh_summary(&mut self) {
+        self.fixed_version = remediation::fixed_version(self);
+        let assessed: Vec<(Severity, Option<f64>)> =
+            self.advisories.iter().map(advisory_severity).collect();
+        self.severity = assessed
+            .iter()
+            .map(|(severity, _)| *severity)
+            .filter(|severity| *severity != Severity::Unknown)
+            .min();
+        self.cvss_score = assessed
+            .iter()
+            .filter_map(|(_, score)| *score)
+            .max_by(|a, b| a.total_cmp(b));
+    }
 }
 
 impl ScanResult {
@@ -46,23 +232,63 @@ impl ScanResult {
     pub fn is_clean(&self) -> bool {
         self.vulnerabilities.is_empty()
     }
+
+    /// Whether any vulnerable package has an advisory that an upgrade
+    /// resolves.
+    pub fn has_fixable(&self) -> bool {
+        self.vulnerabilities
+            .iter()
+            .any(|vuln| remediation::fixable_advisories(vuln).next().is_some())
+    }
+
+    /// Drops advisories rated below `min`. Advisories without a rating are
+    /// kept, since nothing shows they are minor.
+    pub fn retain_min_severity(&mut self, min: Severity) {
+        for vuln in &mut self.vulnerabilities {
+            vuln.advisories.retain(|advisory| {
+                let (severity, _) = advisory_severity(advisory);
+                severity == Severity::Unknown || severity.meets(min)
+            });
+            vuln.refresh_summary();
+        }
+        self.vulnerabilities
+            .retain(|vuln| !vuln.advisories.is_empty());
+    }
 }
 
 pub enum OutputFormat {
     Human,
     Json,
+    /// CycloneDX 1.5 JSON with VEX statements for the findings.
+    CycloneDx,
+    /// SPDX 2.3 JSON.
+    Spdx,
 }
 
 impl ScanResult {
     pub fn display(&self, format: OutputFormat) -> String {
         match format {
             OutputFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            OutputFormat::CycloneDx => {
+                serde_json::to_string_pretty(&sbom::cyclonedx(self, Utc::now())).unwrap_or_default()
+            }
+            OutputFormat::Spdx => {
+                serde_json::to_string_pretty(&sbom::spdx(self, Utc::now())).unwrap_or_default()
+            }
             OutputFormat::Human => {
                 if self.is_clean() {
-                    return format!(
+                    let mut out = format!(
                         "No vulnerabilities found in {} packages.",
                         self.scanned_crates
                     );
+                    let footer = self.display_exceptions()
+                        + &self.display_license_violations()
+                        + &self.display_offline_databases();
+                    if !footer.is_empty() {
+                        out.push_str("\n\n");
+                        out.push_str(&footer);
+                    }
+                    return out;
                 }
 
                 // 1. Group by (Ecosystem, Package, Version) to merge duplicates
@@ -83,13 +309,20 @@ impl ScanResult {
                     if let Some(existing) = grouped_vulns.get_mut(&key) {
                         // Merge locations
                         existing.locations.extend(vuln.locations.iter().cloned());
+                        for path in &vuln.dependency_paths {
+                            if !existing.dependency_paths.contains(path) {
+                                existing.dependency_paths.push(path.clone());
+                            }
+                        }
 
-                        // Merge advisories (dedup by ID)
+                        // Merge advisories (dedup by ID and aliases)
                         for adv in &vuln.advisories {
-                            if !existing.advisories.iter().any(|a| a.id == adv.id) {
-                                existing.advisories.push(adv.clone());
-                            }
+                            crate::models::push_unique_advisory(
+                                &mut existing.advisories,
+                                adv.clone(),
+                            );
                         }
+                        existing.refresh_summary();
                     } else {
                         grouped_vulns.insert(key, vuln.clone());
                     }
@@ -132,11 +365,14 @@ impl ScanResult {
                     }
                 }
 
+                let fixable = vulns.iter().filter(|v| v.fixed_version.is_some()).count();
+
                 let mut out = String::new();
                 out.push_str(&format!(
-                    "Found {} vulnerabilities in {} packages scanned:\n",
+                    "Found {} vulnerabilities in {} packages scanned ({} fixable by upgrading):\n",
                     vulns.len(),
-                    self.scanned_crates
+                    self.scanned_crates,
+                    fixable
                 ));
 
                 if has_cache_info {
@@ -151,8 +387,8 @@ impl ScanResult {
                 for vuln in &mut vulns {
                     // 2. Sort Advisories (Severity -> ID)
                     vuln.advisories.sort_by(|a, b| {
-                        let sev_a = get_severity_score(a);
-                        let sev_b = get_severity_score(b);
+                        let (sev_a, _) = advisory_severity(a);
+                        let (sev_b, _) = advisory_severity(b);
                         // Critical (0) < High (1) ... so distinct from Severity enum order?
                         // Severity enum: Critical, High, Medium, Low, Unknown
                         // Ord derivation makes Critical < High (based on definition order).
@@ -167,20 +403,40 @@ impl ScanResult {
                     vuln.locations.sort();
                     vuln.locations.dedup();
 
+                    let rating = match (vuln.severity, vuln.cvss_score) {
+                        (Some(severity), Some(score)) => format!(" [{} {:.1}]", severity, score),
+                        (Some(severity), None) => format!(" [{}]", severity),
+                        _ => String::new(),
+                    };
                     out.push_str(&format!(
-                        "- {} v{} ({})\n",
-                        vuln.package_name, vuln.version, vuln.ecosystem
+                        "- {} v{} ({}){}\n",
+                        vuln.package_name, vuln.version, vuln.ecosystem, rating
                     ));
 
                     if !vuln.locations.is_empty() {
                         out.push_str(&format!("  Locations: {}\n", vuln.locations.join(", ")));
                     }
 
+                    for path in &vuln.dependency_paths {
+                        if path.len() == 1 {
+                            out.push_str(&format!("  Path: {} (direct dependency)\n", path[0]));
+                        } else {
+                            out.push_str(&format!("  Path: {}\n", path.join(" > ")));
+                        }
+                    }
+
+                    match &vuln.fixed_version {
+                        Some(fixed) => {
+                            out.push_str(&format!("  Upgrade: {} -> {}\n", vuln.version, fixed))
+                        }
+                        None => out.push_str("  Upgrade: no fixed version covers every advisory\n"),
+                    }
+
                     for advisory in &vuln.advisories {
                         out.push_str(&format!("  [{}] {}\n", advisory.id, advisory.description));
 
                         // Action Line
-                        let fix = recommend_fix(advisory, &vuln.version);
+                        let fix = recommend_fix(advisory, vuln);
                         out.push_str(&format!("    Fix: {}\n", fix));
 
                         // 3. Cache Status
@@ -198,13 +454,25 @@ impl ScanResult {
                             }
 
                             // Severity Display
-                            if let Some(severity) =
-                                details.get("severity").and_then(|v| v.as_array())
-                            {
-                                if let Some(first) = severity.first() {
-                                    if let Some(score) = first.get("score").and_then(|s| s.as_str())
+                            let (severity, _) = advisory_severity(advisory);
+                            match preferred_cvss(details) {
+                                Some((vector, score)) if severity != Severity::Unknown => out
+                                    .push_str(&format!(
+                                        "    Severity: {} {:.1} ({})\n",
+                                        severity, score, vector
+                                    )),
+                                _ if severity != Severity::Unknown => {
+                                    out.push_str(&format!("    Severity: {}\n", severity))
+                                }
+                                _ => {
+                                    if let Some(vector) = details
+                                        .get("severity")
+                                        .and_then(|v| v.as_array())
+                                        .and_then(|entries| entries.first())
+                                        .and_then(|first| first.get("score"))
+                                        .and_then(|s| s.as_str())
                                     {
-                                        out.push_str(&format!("    Severity: {}\n", score));
+                                        out.push_str(&format!("    Severity: {}\n", vector));
                                     }
                                 }
                             }
@@ -230,78 +498,97 @@ impl ScanResult {
                         out.push('\n');
                     }
                 }
+                out.push_str(&self.display_exceptions());
+                out.push_str(&self.display_license_violations());
+                out.push_str(&self.display_offline_databases());
                 out
             }
         }
     }
-}
 
-fn recommend_fix(advisory: &Advisory, current_version: &str) -> String {
-    // Attempt to find a fixed version in OSV details
-    // details["affected"][]["ranges"][]["events"][]["fixed"]
-    if let Some(details) = &advisory.details {
-        if let Some(affected) = details.get("affected").and_then(|v| v.as_array()) {
-            let current = semver::Version::parse(current_version).ok();
-            let mut candidates = Vec::new();
-
-            for aff in affected {
-                if let Some(ranges) = aff.get("ranges").and_then(|r| r.as_array()) {
-                    for range in ranges {
-                        if let Some(events) = range.get("events").and_then(|e| e.as_array()) {
-                            for event in events {
-                                if let Some(fixed) = event.get("fixed").and_then(|v| v.as_str()) {
-                                    if let Ok(fixed_ver) = semver::Version::parse(fixed) {
-                                        candidates.push(fixed_ver);
-                                    } else {
-                                        // Fallback for non-semver versions?
-                                        // For now, only collect valid semvers.
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    fn display_license_violations(&self) -> String {
+        if self.license_violations.is_empty() {
+            return String::new();
+        }
+        let mut out = format!(
+            "License policy violations ({}):\n",
+            self.license_violations.len()
+        );
+        for violation in &self.license_violations {
+            out.push_str(&format!(
+                "- {} v{} ({}): {} ({})\n",
+                violation.package_name,
+                violation.version,
+                violation.ecosystem,
+                violation.license.as_deref().unwrap_or("unknown"),
+                violation.reason
+            ));
+            if !violation.locations.is_empty() {
+                out.push_str(&format!(
+                    "  Locations: {}\n",
+                    violation.locations.join(", ")
+                ));
             }
+        }
+        out
+    }
 
-            // Sort candidates
-            candidates.sort();
+    fn display_offline_databases(&self) -> String {
+        if self.offline_databases.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from("Offline OSV database:\n");
+        for db in &self.offline_databases {
+            out.push_str(&format!(
+                "  {}: {} advisories from {}, updated {} ({} days old)\n",
+                db.ecosystem,
+                db.advisories,
+                db.source,
+                db.updated_at.format("%Y-%m-%d"),
+                db.age_days
+            ));
+        }
+        out
+    }
 
-            if let Some(curr) = current {
-                // Find smallest fixed > current
-                if let Some(upgrade) = candidates.iter().find(|&v| v > &curr) {
-                    return format!("Upgrade to >= {}", upgrade);
-                }
-            } else {
-                // If current version parsing failed, just suggest the first available fixed version (assuming it's sorted ascending, likely smallest)
-                if let Some(first) = candidates.first() {
-                    return format!("Upgrade to >= {}", first);
-                }
+    fn display_exceptions(&self) -> String {
+        let mut out = String::new();
+        let sections = [
+            (
+                "Expired exceptions (reported again):",
+                &self.expired_exceptions,
+            ),
+            ("Suppressed by guardian exceptions:", &self.suppressed),
+        ];
+        for (title, entries) in sections {
+            if entries.is_empty() {
+                continue;
+            }
+            out.push_str(title);
+            out.push('\n');
+            for entry in entries {
+                let expiry = entry
+                    .expires_at
+                    .map(|at| format!(", expires {}", at.format("%Y-%m-%d")))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "  [{}] {} v{} ({}): {}{}\n",
+                    entry.advisory_id,
+                    entry.package_name,
+                    entry.version,
+                    entry.ecosystem,
+                    entry.reason,
+                    expiry
+                ));
             }
         }
+        out
     }
-    "No fixed version available (mitigation required)".to_string()
 }
 
-fn get_severity_score(advisory: &Advisory) -> Severity {
-    if let Some(details) = &advisory.details {
-        if let Some(severity) = details
-            .get("database_specific")
-            .and_then(|d| d.get("severity"))
-            .and_then(|s| s.as_str())
-        {
-            return Severity::from_str(severity);
-        }
-        // Fallback to standard severity field if needed, but OSV usually puts it in specific places or as CVSS.
-        // For now, let's assume we can parse "severity" from the top level if we extracted it,
-        // but the Advisory struct has `details` which is raw JSON.
-        // Let's look for "severity" -> [ { "type": "CVSS_V3", "score": "..." } ]
-        // Parsing CVSS score to "Critical/High" is hard without a calculator.
-        // However, GitHub/OSV often provide "database_specific": { "severity": "MODERATE" }.
-        if let Some(db_specific) = details.get("database_specific") {
-            if let Some(severity_str) = db_specific.get("severity").and_then(|s| s.as_str()) {
-                return Severity::from_str(severity_str);
-            }
-        }
+pub(crate) fn recommend_fix(advisory: &Advisory, vuln: &Vulnerability) -> String {
+    match remediation::advisory_fix(advisory, vuln.ecosystem, &vuln.package_name, &vuln.version) {
+        Some(fixed) => format!("Upgrade to >= {}", fixed),
+        None => "No fixed version available (mitigation required)".to_string(),
     }
-    Severity::Unknown
 }
@@ -0,0 +1,321 @@
+//! SBOM output for scan results: the scanned package inventory as CycloneDX
+//! 1.5 JSON or SPDX 2.3 JSON.
+//!
+//! CycloneDX documents embed VEX statements: every finding becomes a
+//! `vulnerabilities[]` entry whose `analysis` tells whether it is still open
+//! (`in_triage`, with an upgrade `response` when a fix exists) or accepted
+//! through a guardian exception (`exploitable` / `will_not_fix`, with the
+//! exception reason). SPDX 2.3 has no VEX model, so advisories are attached
+//! to packages as `SECURITY` external references carrying the same status
+//! in their comment.
+
+use crate::models::{Advisory, PackageRef};
+use crate::purl;
+use crate::report::{
+    advisory_severity, preferred_cvss, recommend_fix, ScanResult, Severity, SuppressedAdvisory,
+    Vulnerability,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+const TOOL_NAME: &str = "veil-guardian";
+
+/// The scan result as a CycloneDX 1.5 JSON document.
+pub fn cyclonedx(result: &ScanResult, now: DateTime<Utc>) -> Value {
+    let inventory = inventory(result);
+    let components: Vec<Value> = inventory
+        .iter()
+        .map(|(purl, package)| {
+            json!({
+                "type": "library",
+                "bom-ref": purl,
+                "name": package.name,
+                "version": package.version,
+                "purl": purl,
+            })
+        })
+        .collect();
+
+    let mut vulnerabilities = Vec::new();
+    for vuln in &result.vulnerabilities {
+        let purl = purl::format(&vuln_package(vuln));
+        for advisory in &vuln.advisories {
+            let expired = find_entry(&result.expired_exceptions, vuln, &advisory.id);
+            vulnerabilities.push(cyclonedx_finding(advisory, vuln, &purl, expired));
+        }
+    }
+    for entry in &result.suppressed {
+        let purl = purl::format(&suppressed_package(entry));
+        vulnerabilities.push(json!({
+            "bom-ref": format!("{}@{}", entry.advisory_id, purl),
+            "id": entry.advisory_id,
+            "affects": [{ "ref": purl }],
+            "analysis": {
+                "state": "exploitable",
+                "response": ["will_not_fix"],
+                "detail": exception_detail("Accepted by guardian exception", entry),
+            },
+        }));
+    }
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", document_id(&inventory, now)),
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp(now),
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                }],
+            },
+        },
+        "components": components,
+        "vulnerabilities": vulnerabilities,
+    })
+}
+
+/// The scan result as an SPDX 2.3 JSON document.
+pub fn spdx(result: &ScanResult, now: DateTime<Utc>) -> Value {
+    let inventory = inventory(result);
+    let mut advisories: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+    for vuln in &result.vulnerabilities {
+        let purl = purl::format(&vuln_package(vuln));
+        for advisory in &vuln.advisories {
+            let status = match find_entry(&result.expired_exceptions, vuln, &advisory.id) {
+                Some(entry) => exception_detail("Affected; guardian exception expired", entry),
+                None => format!("Affected; {}", recommend_fix(advisory, vuln)),
+            };
+            let comment = match advisory_severity(advisory).0 {
+                Severity::Unknown => status,
+                severity => format!("{} [{}]", status, severity),
+            };
+            advisories
+                .entry(inventory_key(&inventory, &purl))
+                .or_default()
+                .push(advisory_ref(&advisory.id, advisory_url(advisory), comment));
+        }
+    }
+    for entry in &result.suppressed {
+        let purl = purl::format(&suppressed_package(entry));
+        let url = Some(osv_url(&entry.advisory_id));
+        advisories
+            .entry(inventory_key(&inventory, &purl))
+            .or_default()
+            .push(advisory_ref(
+                &entry.advisory_id,
+                url,
+                exception_detail("Accepted by guardian exception", entry),
+            ));
+    }
+
+    let packages: Vec<Value> = inventory
+        .iter()
+        .enumerate()
+        .map(|(index, (purl, package))| {
+            let mut refs = vec![json!({
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": purl,
+            })];
+            refs.extend(advisories.remove(purl.as_str()).unwrap_or_default());
+            json!({
+                "SPDXID": format!("SPDXRef-Package-{}", index + 1),
+                "name": package.name,
+                "versionInfo": package.version,
+                "downloadLocation": "NOASSERTION",
+                "filesAnalyzed": false,
+                "externalRefs": refs,
+            })
+        })
+        .collect();
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "veil-guardian-scan",
+        "documentNamespace": format!(
+            "https://spdx.org/spdxdocs/{}-{}",
+            TOOL_NAME,
+            document_id(&inventory, now)
+        ),
+        "creationInfo": {
+            "created": timestamp(now),
+            "creators": [format!("Tool: {}-{}", TOOL_NAME, env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+    })
+}
+
+fn cyclonedx_finding(
+    advisory: &Advisory,
+    vuln: &Vulnerability,
+    purl: &str,
+    expired: Option<&SuppressedAdvisory>,
+) -> Value {
+    let mut finding = json!({
+        "bom-ref": format!("{}@{}", advisory.id, purl),
+        "id": advisory.id,
+        "affects": [{ "ref": purl }],
+    });
+    if let Some(url) = advisory_url(advisory) {
+        finding["source"] = json!({ "name": "OSV", "url": url });
+    }
+    if !advisory.description.is_empty() {
+        finding["description"] = json!(advisory.description);
+    }
+
+    let (severity, _) = advisory_severity(advisory);
+    if severity != Severity::Unknown {
+        let mut rating = json!({ "severity": severity.to_string().to_lowercase() });
+        // A severity taken from an advisory label has no score or vector.
+        match advisory.details.as_ref().and_then(preferred_cvss) {
+            Some((vector, score)) => {
+                rating["method"] = json!(cvss_method(vector));
+                rating["vector"] = json!(vector);
+                rating["score"] = json!(score);
+            }
+            None => rating["method"] = json!("other"),
+        }
+        finding["ratings"] = json!([rating]);
+    }
+
+    let mut analysis = json!({ "state": "in_triage" });
+    let fix = recommend_fix(advisory, vuln);
+    if fix.starts_with("Upgrade") {
+        analysis["response"] = json!(["update"]);
+        finding["recommendation"] = json!(fix);
+    }
+    if let Some(entry) = expired {
+        analysis["detail"] = json!(exception_detail("Guardian exception expired", entry));
+    }
+    finding["analysis"] = analysis;
+    finding
+}
+
+/// Every scanned package plus the packages findings refer to, keyed and
+/// ordered by purl.
+fn inventory(result: &ScanResult) -> BTreeMap<String, PackageRef> {
+    result
+        .packages
+        .iter()
+        .cloned()
+        .chain(result.vulnerabilities.iter().map(vuln_package))
+        .chain(result.suppressed.iter().map(suppressed_package))
+        .map(|package| (purl::format(&package), package))
+        .collect()
+}
+
+fn inventory_key<'a>(inventory: &'a BTreeMap<String, PackageRef>, purl: &str) -> &'a str {
+    inventory
+        .get_key_value(purl)
+        .map(|(key, _)| key.as_str())
+        .expect("findings are part of the inventory")
+}
+
+fn vuln_package(vuln: &Vulnerability) -> PackageRef {
+    PackageRef {
+        ecosystem: vuln.ecosystem,
+        name: vuln.package_name.clone(),
+        version: vuln.version.clone(),
+    }
+}
+
+fn suppressed_package(entry: &SuppressedAdvisory) -> PackageRef {
+    PackageRef {
+        ecosystem: entry.ecosystem,
+        name: entry.package_name.clone(),
+        version: entry.version.clone(),
+    }
+}
+
+fn find_entry<'a>(
+    entries: &'a [SuppressedAdvisory],
+    vuln: &Vulnerability,
+    advisory_id: &str,
+) -> Option<&'a SuppressedAdvisory> {
+    entries.iter().find(|entry| {
+        entry.advisory_id == advisory_id
+            && entry.ecosystem == vuln.ecosystem
+            && entry.package_name == vuln.package_name
+            && entry.version == vuln.version
+    })
+}
+
+fn exception_detail(prefix: &str, entry: &SuppressedAdvisory) -> String {
+    let expiry = entry
+        .expires_at
+        .map(|at| format!(" (expires {})", at.format("%Y-%m-%d")))
+        .unwrap_or_default();
+    format!("{}: {}{}", prefix, entry.reason, expiry)
+}
+
+fn advisory_ref(id: &str, url: Option<String>, comment: String) -> Value {
+    match url {
+        Some(url) => json!({
+            "referenceCategory": "SECURITY",
+            "referenceType": "advisory",
+            "referenceLocator": url,
+            "comment": format!("{}: {}", id, comment),
+        }),
+        // Builtin advisories have no public page to point at.
+        None => json!({
+            "referenceCategory": "OTHER",
+            "referenceType": "veil-guardian-advisory",
+            "referenceLocator": id,
+            "comment": comment,
+        }),
+    }
+}
+
+/// Advisories with OSV details came from OSV and have a page there.
+fn advisory_url(advisory: &Advisory) -> Option<String> {
+    advisory.details.as_ref().map(|_| osv_url(&advisory.id))
+}
+
+fn osv_url(id: &str) -> String {
+    format!("https://osv.dev/vulnerability/{}", id)
+}
+
+fn cvss_method(vector: &str) -> &'static str {
+    if vector.starts_with("CVSS:4.0") {
+        "CVSSv4"
+    } else if vector.starts_with("CVSS:3.1") {
+        "CVSSv31"
+    } else {
+        "CVSSv3"
+    }
+}
+
+fn timestamp(now: DateTime<Utc>) -> String {
+    now.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// A UUID (version 4 layout) derived from the inventory and the time, so
+/// the same scan rendered twice gets the same id.
+fn document_id(inventory: &BTreeMap<String, PackageRef>, now: DateTime<Utc>) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(timestamp(now).as_bytes());
+    for purl in inventory.keys() {
+        hasher.update(purl.as_bytes());
+        hasher.update(b"\n");
+    }
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hasher.finalize().as_bytes()[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
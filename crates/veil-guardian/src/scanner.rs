@@ -1,11 +1,16 @@
 use crate::db::GuardianDb;
+use crate::exceptions::ExceptionRegistry;
+use crate::graph::{DependencyGraph, MAX_PATHS};
+use crate::license::{self, LicensePolicy};
 
-use crate::providers::{npm, osv, pnpm, yarn};
-use crate::report::{ScanResult, Vulnerability};
+use crate::models::{push_unique_advisory, Ecosystem, PackageRef};
+use crate::providers::{go, maven, npm, osv, pnpm, python, sbom, yarn};
+use crate::report::{LicenseViolation, ScanResult, Vulnerability};
 use crate::GuardianError;
 use crate::Metrics;
 use semver::Version;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
@@ -15,145 +20,348 @@ pub struct ScanOptions {
     pub osv_api_url: Option<String>,
     pub metrics: Option<Arc<Metrics>>,
     pub cache_dir: Option<std::path::PathBuf>,
+    /// Accepted advisories to move out of the findings.
+    pub exceptions: Option<ExceptionRegistry>,
+    /// License policy to check npm and crates.io packages against.
+    pub license_policy: Option<LicensePolicy>,
+}
+
+/// Packages extracted from one lockfile.
+struct ParsedLockfile {
+    location: String,
+    /// Everything the lockfile pins; counted as scanned and used to
+    /// attribute findings back to this file.
+    packages: Vec<PackageRef>,
+    /// The subset that is published to a registry OSV knows about.
+    osv_packages: Vec<PackageRef>,
+    /// Who depends on whom, for the npm lockfiles that record it.
+    graph: DependencyGraph,
+}
+
+/// Whether `filename` is a lockfile, manifest or SBOM `scan_lockfile`
+/// understands.
+pub fn is_supported_lockfile(filename: &str) -> bool {
+    matches!(
+        filename,
+        "Cargo.lock"
+            | "package-lock.json"
+            | "pnpm-lock.yaml"
+            | "yarn.lock"
+            | "poetry.lock"
+            | "Pipfile.lock"
+            | "go.sum"
+            | "go.mod"
+            | "pom.xml"
+    ) || (filename.starts_with("requirements") && filename.ends_with(".txt"))
+        || filename.ends_with("gradle.lockfile")
+        || is_sbom(filename)
+}
+
+/// CycloneDX (`bom.json`, `*.cdx.json`) and SPDX (`*.spdx.json`) documents.
+fn is_sbom(filename: &str) -> bool {
+    filename == "bom.json" || filename.ends_with(".cdx.json") || filename.ends_with(".spdx.json")
 }
 
 pub fn scan_lockfile(path: &Path, options: ScanOptions) -> Result<ScanResult, GuardianError> {
-    let filename = path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| GuardianError::LockfileParseError("Invalid filename".to_string()))?;
+    scan_lockfiles(&[path.to_path_buf()], options)
+}
+
+/// Scans several lockfiles as one project. Packages shared between lockfiles
+/// are queried once, and each vulnerability lists every lockfile that pins
+/// the affected package version.
+pub fn scan_lockfiles(
+    paths: &[PathBuf],
+    options: ScanOptions,
+) -> Result<ScanResult, GuardianError> {
+    let lockfiles = paths
+        .iter()
+        .map(|path| parse_lockfile(path, options.metrics.as_deref()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut seen = HashSet::new();
+    let osv_packages: Vec<PackageRef> = lockfiles
+        .iter()
+        .flat_map(|lockfile| lockfile.osv_packages.iter())
+        .filter(|package| seen.insert(*package))
+        .cloned()
+        .collect();
 
-    if filename == "package-lock.json" {
-        return scan_npm(path, options);
+    let client = osv::OsvClient::new(
+        options.offline,
+        options.osv_api_url,
+        options.metrics.clone(),
+        options.cache_dir,
+    );
+    let mut vulns = client.check_packages(&osv_packages, options.show_details)?;
+    let offline_databases = client.offline_databases(&osv_packages);
+    for vuln in &mut vulns {
+        for advisory in std::mem::take(&mut vuln.advisories) {
+            push_unique_advisory(&mut vuln.advisories, advisory);
+        }
     }
 
-    if filename == "pnpm-lock.yaml" {
-        return scan_pnpm(path, options);
+    let mut all_packages: Vec<&PackageRef> = Vec::new();
+    let mut seen = HashSet::new();
+    for package in lockfiles
+        .iter()
+        .flat_map(|lockfile| lockfile.packages.iter())
+    {
+        if seen.insert(package) {
+            all_packages.push(package);
+        }
     }
 
-    if filename == "yarn.lock" {
-        return scan_yarn(path, options);
+    merge_builtin_advisories(&mut vulns, &all_packages)?;
+
+    for vuln in &mut vulns {
+        for lockfile in &lockfiles {
+            let pinned = lockfile.packages.iter().any(|p| {
+                p.ecosystem == vuln.ecosystem
+                    && p.name == vuln.package_name
+                    && p.version == vuln.version
+            });
+            if pinned {
+                vuln.locations.push(lockfile.location.clone());
+                record_dependency_paths(vuln, &lockfile.graph);
+            }
+        }
     }
 
-    // Default to Cargo.lock
-    scan_cargo(path)
+    let license_violations = match &options.license_policy {
+        Some(policy) if !policy.is_empty() => check_licenses(&lockfiles, policy)?,
+        _ => Vec::new(),
+    };
+
+    let mut result = ScanResult {
+        scanned_crates: all_packages.len(),
+        vulnerabilities: vulns,
+        offline_databases,
+        license_violations,
+        packages: all_packages.into_iter().cloned().collect(),
+        ..ScanResult::default()
+    };
+    if let Some(registry) = &options.exceptions {
+        registry.apply(&mut result, chrono::Utc::now());
+    }
+    for vuln in &mut result.vulnerabilities {
+        vuln.refresh_summary();
+    }
+    Ok(result)
 }
 
-fn scan_cargo(path: &Path) -> Result<ScanResult, GuardianError> {
-    let lockfile = cargo_lock::Lockfile::load(path)
-        .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
+/// Builtin advisories are merged in after OSV; an entry whose id or aliases
+/// OSV already reported is folded into the OSV one.
+fn merge_builtin_advisories(
+    vulns: &mut Vec<Vulnerability>,
+    packages: &[&PackageRef],
+) -> Result<(), GuardianError> {
+    let crates: Vec<&PackageRef> = packages
+        .iter()
+        .copied()
+        .filter(|p| p.ecosystem == Ecosystem::Rust)
+        .collect();
+    if crates.is_empty() {
+        return Ok(());
+    }
 
     let db = GuardianDb::load_builtin()?;
-    let mut result = ScanResult::new();
-
-    // Iterate over all packages
-    for package in lockfile.packages {
-        let name = package.name.as_str();
-        let version_str = package.version.to_string();
-
-        // Count scanned packages
-        result.scanned_crates += 1;
-
-        if let Ok(version) = Version::parse(&version_str) {
-            let advisories = db.check_vulnerabilities(name, &version);
-            if !advisories.is_empty() {
-                let owned_advisories: Vec<_> = advisories.into_iter().cloned().collect();
-                result.vulnerabilities.push(Vulnerability {
-                    ecosystem: crate::models::Ecosystem::Rust,
-                    package_name: name.to_string(),
-                    version: version_str,
-                    advisories: owned_advisories,
-                    locations: vec![path.to_string_lossy().to_string()],
+    for package in crates {
+        let Ok(version) = Version::parse(&package.version) else {
+            continue;
+        };
+        let builtin = db.check_vulnerabilities(&package.name, &version);
+        if builtin.is_empty() {
+            continue;
+        }
+
+        let index = match vulns.iter().position(|v| {
+            v.ecosystem == Ecosystem::Rust
+                && v.package_name == package.name
+                && v.version == package.version
+        }) {
+            Some(index) => index,
+            None => {
+                vulns.push(Vulnerability {
+                    ecosystem: Ecosystem::Rust,
+                    package_name: package.name.clone(),
+                    version: package.version.clone(),
+                    advisories: Vec::new(),
+                    locations: Vec::new(),
+                    fixed_version: None,
+                    severity: None,
+                    cvss_score: None,
+                    dependency_paths: Vec::new(),
                 });
+                vulns.len() - 1
             }
+        };
+        for advisory in builtin {
+            push_unique_advisory(&mut vulns[index].advisories, advisory.clone());
         }
     }
-
-    Ok(result)
+    Ok(())
 }
 
-fn scan_npm(path: &Path, options: ScanOptions) -> Result<ScanResult, GuardianError> {
-    let start_parse = std::time::Instant::now();
-    let packages = npm::parse_package_lock(path)?;
-    if let Some(m) = &options.metrics {
-        m.time_parse_ms
-            .fetch_add(start_parse.elapsed().as_millis() as u64, Ordering::Relaxed);
-    }
+/// Checks the registry packages of npm and Cargo lockfiles against
+/// `policy`. Licenses come from package-lock.json itself, else from
+/// installed or vendored manifests next to the lockfile.
+fn check_licenses(
+    lockfiles: &[ParsedLockfile],
+    policy: &LicensePolicy,
+) -> Result<Vec<LicenseViolation>, GuardianError> {
+    let mut violations: Vec<LicenseViolation> = Vec::new();
+    for lockfile in lockfiles {
+        let path = Path::new(&lockfile.location);
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let declared = if path
+            .file_name()
+            .is_some_and(|name| name == "package-lock.json")
+        {
+            npm::package_lock_licenses(path)?
+        } else {
+            HashMap::new()
+        };
 
-    let show_details = options.show_details;
-    let client = osv::OsvClient::new(
-        options.offline,
-        options.osv_api_url,
-        options.metrics.clone(),
-        options.cache_dir,
-    );
-    let mut vulns = client.check_packages(&packages, show_details)?;
-    for vuln in &mut vulns {
-        vuln.locations.push(path.to_string_lossy().to_string());
+        for package in &lockfile.osv_packages {
+            let found = match package.ecosystem {
+                Ecosystem::Npm => declared.get(package).cloned().or_else(|| {
+                    license::node_modules_license(dir, &package.name, &package.version)
+                }),
+                Ecosystem::Rust => license::cargo_license(dir, &package.name, &package.version),
+                _ => continue,
+            };
+            let Some(reason) = policy.check(found.as_deref()) else {
+                continue;
+            };
+
+            match violations.iter_mut().find(|v| {
+                v.ecosystem == package.ecosystem
+                    && v.package_name == package.name
+                    && v.version == package.version
+            }) {
+                Some(existing) => existing.locations.push(lockfile.location.clone()),
+                None => violations.push(LicenseViolation {
+                    ecosystem: package.ecosystem,
+                    package_name: package.name.clone(),
+                    version: package.version.clone(),
+                    license: found,
+                    reason,
+                    locations: vec![lockfile.location.clone()],
+                }),
+            }
+        }
     }
+    violations.sort_by(|a, b| {
+        (a.ecosystem, &a.package_name, &a.version).cmp(&(b.ecosystem, &b.package_name, &b.version))
+    });
+    Ok(violations)
+}
 
-    Ok(ScanResult {
-        scanned_crates: packages.len(),
-        vulnerabilities: vulns,
-    })
+/// Adds the shortest chains leading to the vulnerable package in `graph`,
+/// keeping only the shortest across lockfiles.
+fn record_dependency_paths(vuln: &mut Vulnerability, graph: &DependencyGraph) {
+    if graph.is_empty() {
+        return;
+    }
+    let target = PackageRef {
+        ecosystem: vuln.ecosystem,
+        name: vuln.package_name.clone(),
+        version: vuln.version.clone(),
+    };
+    for path in graph.shortest_paths(&target, MAX_PATHS) {
+        let path: Vec<String> = path
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect();
+        if !vuln.dependency_paths.contains(&path) {
+            vuln.dependency_paths.push(path);
+        }
+    }
+    let shortest = vuln.dependency_paths.iter().map(Vec::len).min();
+    vuln.dependency_paths
+        .retain(|path| Some(path.len()) == shortest);
+    vuln.dependency_paths.sort();
+    vuln.dependency_paths.truncate(MAX_PATHS);
 }
 
-fn scan_pnpm(path: &Path, options: ScanOptions) -> Result<ScanResult, GuardianError> {
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
+fn parse_lockfile(path: &Path, metrics: Option<&Metrics>) -> Result<ParsedLockfile, GuardianError> {
+    let filename = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| GuardianError::LockfileParseError("Invalid filename".to_string()))?;
 
     let start_parse = std::time::Instant::now();
-    let packages = pnpm::parse_pnpm_lock(&content)
-        .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
-    if let Some(m) = &options.metrics {
+    let mut graph = DependencyGraph::new();
+    let (packages, osv_packages) = match filename {
+        "package-lock.json" => {
+            let (packages, npm_graph) = npm::parse_package_lock_with_graph(path)?;
+            graph = npm_graph;
+            same(packages)
+        }
+        "pnpm-lock.yaml" => {
+            let (packages, pnpm_graph) = pnpm::parse_pnpm_lock_with_graph(&read(path)?)
+                .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
+            graph = pnpm_graph;
+            same(packages)
+        }
+        "yarn.lock" => {
+            let (packages, yarn_graph) = yarn::parse_yarn_lock_with_graph(&read(path)?)?;
+            graph = yarn_graph;
+            same(packages)
+        }
+        "poetry.lock" => same(python::parse_poetry_lock(&read(path)?)?),
+        "Pipfile.lock" => same(python::parse_pipfile_lock(&read(path)?)?),
+        "go.sum" => same(go::parse_go_sum(&read(path)?)?),
+        "go.mod" => same(go::parse_go_mod(&read(path)?)?),
+        "pom.xml" => same(maven::parse_pom_xml(&read(path)?)?),
+        name if name.starts_with("requirements") && name.ends_with(".txt") => {
+            same(python::parse_requirements_txt(&read(path)?)?)
+        }
+        name if name.ends_with("gradle.lockfile") => {
+            same(maven::parse_gradle_lockfile(&read(path)?)?)
+        }
+        name if is_sbom(name) => same(sbom::parse_sbom_json(&read(path)?)?),
+        // Default to Cargo.lock
+        _ => parse_cargo(path)?,
+    };
+    if let Some(m) = metrics {
         m.time_parse_ms
             .fetch_add(start_parse.elapsed().as_millis() as u64, Ordering::Relaxed);
     }
 
-    let show_details = options.show_details;
-    let client = osv::OsvClient::new(
-        options.offline,
-        options.osv_api_url,
-        options.metrics.clone(),
-        options.cache_dir,
-    );
-    let mut vulns = client.check_packages(&packages, show_details)?;
-    for vuln in &mut vulns {
-        vuln.locations.push(path.to_string_lossy().to_string());
-    }
-
-    Ok(ScanResult {
-        scanned_crates: packages.len(),
-        vulnerabilities: vulns,
+    Ok(ParsedLockfile {
+        location: path.to_string_lossy().to_string(),
+        packages,
+        osv_packages,
+        graph,
     })
 }
 
-fn scan_yarn(path: &Path, options: ScanOptions) -> Result<ScanResult, GuardianError> {
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
+fn read(path: &Path) -> Result<String, GuardianError> {
+    std::fs::read_to_string(path).map_err(|e| GuardianError::LockfileParseError(e.to_string()))
+}
 
-    let start_parse = std::time::Instant::now();
-    let packages = yarn::parse_yarn_lock(&content)
-        .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
-    if let Some(m) = &options.metrics {
-        m.time_parse_ms
-            .fetch_add(start_parse.elapsed().as_millis() as u64, Ordering::Relaxed);
-    }
+fn same(packages: Vec<PackageRef>) -> (Vec<PackageRef>, Vec<PackageRef>) {
+    (packages.clone(), packages)
+}
 
-    let show_details = options.show_details;
-    let client = osv::OsvClient::new(
-        options.offline,
-        options.osv_api_url,
-        options.metrics.clone(),
-        options.cache_dir,
-    );
-    let mut vulns = client.check_packages(&packages, show_details)?;
-    for vuln in &mut vulns {
-        vuln.locations.push(path.to_string_lossy().to_string());
-    }
+fn parse_cargo(path: &Path) -> Result<(Vec<PackageRef>, Vec<PackageRef>), GuardianError> {
+    let lockfile = cargo_lock::Lockfile::load(path)
+        .map_err(|e| GuardianError::LockfileParseError(e.to_string()))?;
 
-    Ok(ScanResult {
-        scanned_crates: packages.len(),
-        vulnerabilities: vulns,
-    })
+    let to_ref = |p: &cargo_lock::Package| PackageRef {
+        ecosystem: Ecosystem::Rust,
+        name: p.name.as_str().to_string(),
+        version: p.version.to_string(),
+    };
+    let packages = lockfile.packages.iter().map(to_ref).collect();
+    // Workspace members and path/git dependencies are not published on
+    // crates.io, so OSV only hears about registry packages.
+    let osv_packages = lockfile
+        .packages
+        .iter()
+        .filter(|p| p.source.as_ref().is_some_and(|s| s.is_default_registry()))
+        .map(to_ref)
+        .collect();
+    Ok((packages, osv_packages))
 }
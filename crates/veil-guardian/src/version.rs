@@ -0,0 +1,302 @@
+//! Version ordering per ecosystem.
+//!
+//! OSV reports `fixed` versions in each ecosystem's native format, so
+//! "is this fix newer than what we have" has to follow that ecosystem's
+//! rules: semver for crates.io and npm, semver with a `v` prefix for Go,
+//! PEP 440 for PyPI and Maven's `ComparableVersion` ordering for Maven.
+
+use crate::models::Ecosystem;
+use std::cmp::Ordering;
+
+/// Compares two versions of the same package. `None` if either side cannot
+/// be parsed under the ecosystem's rules.
+pub fn compare(ecosystem: Ecosystem, a: &str, b: &str) -> Option<Ordering> {
+    match ecosystem {
+        Ecosystem::Rust | Ecosystem::Npm => {
+            let a = semver::Version::parse(a).ok()?;
+            let b = semver::Version::parse(b).ok()?;
+            Some(a.cmp(&b))
+        }
+        Ecosystem::Go => {
+            let a = semver::Version::parse(a.trim_start_matches('v')).ok()?;
+            let b = semver::Version::parse(b.trim_start_matches('v')).ok()?;
+            Some(a.cmp_precedence(&b))
+        }
+        Ecosystem::PyPI => Some(Pep440::parse(a)?.cmp(&Pep440::parse(b)?)),
+        Ecosystem::Maven => Some(compare_maven(a, b)),
+    }
+}
+
+/// A PEP 440 version reduced to a sortable key. Local labels (`+ubuntu1`)
+/// do not take part in ordering against public versions and are dropped.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Pep440 {
+    epoch: u64,
+    release: Vec<u64>,
+    /// (0, ..) dev-only release, (1, kind, n) pre-release, (2, 0, 0) final.
+    pre: (u8, u8, u64),
+    /// (0, 0) no post-release, (1, n) `.postN`.
+    post: (u8, u64),
+    /// (0, n) `.devN`, (1, 0) no dev segment.
+    dev: (u8, u64),
+}
+
+impl Pep440 {
+    fn parse(input: &str) -> Option<Self> {
+        let lower = input.trim().to_ascii_lowercase();
+        let public = lower.split('+').next()?;
+        let public = public.strip_prefix('v').unwrap_or(public);
+        let (epoch, rest) = match public.split_once('!') {
+            Some((epoch, rest)) => (epoch.parse().ok()?, rest),
+            None => (0, public),
+        };
+
+        let release_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let mut release: Vec<u64> = rest[..release_end]
+            .trim_end_matches('.')
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+
+        let mut pre = None;
+        let mut post = None;
+        let mut dev = None;
+        let mut tail = &rest[release_end..];
+        while !tail.is_empty() {
+            tail = tail.trim_start_matches(['.', '-', '_']);
+            let label_end = tail
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(tail.len());
+            let (label, after) = tail.split_at(label_end);
+            let after = after.trim_start_matches(['.', '-', '_']);
+            let num_end = after
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after.len());
+            let number = after[..num_end].parse().unwrap_or(0);
+            tail = &after[num_end..];
+            match label {
+                "a" | "alpha" => pre = Some((0, number)),
+                "b" | "beta" => pre = Some((1, number)),
+                "c" | "rc" | "pre" | "preview" => pre = Some((2, number)),
+                "post" | "rev" | "r" => post = Some(number),
+                // `1.0-1` is the implicit post-release spelling.
+                "" if num_end > 0 => post = Some(number),
+                "dev" => dev = Some(number),
+                _ => return None,
+            }
+        }
+
+        Some(Self {
+            epoch,
+            release,
+            pre: match (pre, post, dev) {
+                (Some((kind, n)), _, _) => (1, kind, n),
+                (None, None, Some(_)) => (0, 0, 0),
+                _ => (2, 0, 0),
+            },
+            post: post.map_or((0, 0), |n| (1, n)),
+            dev: dev.map_or((1, 0), |n| (0, n)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MavenItem {
+    Number(u64),
+    Qualifier(String),
+}
+
+/// Maven orders qualifiers below a plain release except for `sp`, and any
+/// number above any qualifier.
+fn compare_maven(a: &str, b: &str) -> Ordering {
+    let a = maven_items(a);
+    let b = maven_items(b);
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => compare_maven_item(x, y),
+            (Some(x), None) => compare_maven_item(x, &maven_padding(x)),
+            (None, Some(y)) => compare_maven_item(&maven_padding(y), y),
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn maven_padding(item: &MavenItem) -> MavenItem {
+    match item {
+        MavenItem::Number(_) => MavenItem::Number(0),
+        MavenItem::Qualifier(_) => MavenItem::Qualifier(String::new()),
+    }
+}
+
+fn compare_maven_item(a: &MavenItem, b: &MavenItem) -> Ordering {
+    match (a, b) {
+        (MavenItem::Number(x), MavenItem::Number(y)) => x.cmp(y),
+        (MavenItem::Number(_), MavenItem::Qualifier(_)) => Ordering::Greater,
+        (MavenItem::Qualifier(_), MavenItem::Number(_)) => Ordering::Less,
+        (MavenItem::Qualifier(x), MavenItem::Qualifier(y)) => {
+            maven_qualifier_rank(x).cmp(&maven_qualifier_rank(y))
+        }
+    }
+}
+
+fn maven_qualifier_rank(qualifier: &str) -> (u8, &str) {
+    match qualifier {
+        "alpha" => (0, ""),
+        "beta" => (1, ""),
+        "milestone" => (2, ""),
+        "rc" => (3, ""),
+        "snapshot" => (4, ""),
+        "" => (5, ""),
+        "sp" => (6, ""),
+        other => (7, other),
+    }
+}
+
+fn maven_items(version: &str) -> Vec<MavenItem> {
+    let lower = version.trim().to_ascii_lowercase();
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let flush = |current: &mut String, items: &mut Vec<MavenItem>, next: Option<char>| {
+        if current.is_empty() {
+            return;
+        }
+        let item = match current.parse() {
+            Ok(n) => MavenItem::Number(n),
+            Err(_) => {
+                let followed_by_digit = next.is_some_and(|c| c.is_ascii_digit());
+                let name = match current.as_str() {
+                    "a" if followed_by_digit => "alpha",
+                    "b" if followed_by_digit => "beta",
+                    "m" if followed_by_digit => "milestone",
+                    "cr" => "rc",
+                    "ga" | "final" | "release" => "",
+                    other => other,
+                };
+                MavenItem::Qualifier(name.to_string())
+            }
+        };
+        items.push(item);
+        current.clear();
+    };
+
+    let chars: Vec<char> = lower.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '.' || c == '-' {
+            flush(&mut current, &mut items, chars.get(i + 1).copied());
+            continue;
+        }
+        let switches_kind = current
+            .chars()
+            .last()
+            .is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit());
+        if switches_kind {
+            flush(&mut current, &mut items, Some(c));
+        }
+        current.push(c);
+    }
+    flush(&mut current, &mut items, None);
+
+    while let Some(last) = items.last() {
+        match last {
+            MavenItem::Number(0) => items.pop(),
+            MavenItem::Qualifier(q) if q.is_empty() => items.pop(),
+            _ => break,
+        };
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lt(ecosystem: Ecosystem, a: &str, b: &str) {
+        assert_eq!(
+            compare(ecosystem, a, b),
+            Some(Ordering::Less),
+            "{ecosystem}: expected {a} < {b}"
+        );
+    }
+
+    #[test]
+    fn pypi_follows_pep440() {
+        let order = [
+            "1.0.dev1",
+            "1.0a1",
+            "1.0a2.dev1",
+            "1.0a2",
+            "1.0b1",
+            "1.0rc1",
+            "1.0",
+            "1.0.post1",
+            "1.1",
+            "1!0.1",
+        ];
+        for pair in order.windows(2) {
+            lt(Ecosystem::PyPI, pair[0], pair[1]);
+        }
+        assert_eq!(
+            compare(Ecosystem::PyPI, "2.0", "2.0.0"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare(Ecosystem::PyPI, "2.31", "2.31.0+local"),
+            Some(Ordering::Equal)
+        );
+        lt(Ecosystem::PyPI, "2.9.0", "2.31");
+        assert_eq!(compare(Ecosystem::PyPI, "not-a-version", "1.0"), None);
+    }
+
+    #[test]
+    fn go_accepts_v_prefix_and_pseudo_versions() {
+        lt(
+            Ecosystem::Go,
+            "v0.0.0-20210921155107-089bfa567519",
+            "v0.1.0",
+        );
+        lt(Ecosystem::Go, "1.2.3", "v1.10.0");
+        assert_eq!(
+            compare(Ecosystem::Go, "v2.0.0+incompatible", "2.0.0"),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn maven_orders_qualifiers() {
+        let order = [
+            "1.0-alpha1",
+            "1.0-beta2",
+            "1.0-M1",
+            "1.0-rc1",
+            "1.0-SNAPSHOT",
+            "1.0",
+            "1.0-sp1",
+            "1.0.1",
+        ];
+        for pair in order.windows(2) {
+            lt(Ecosystem::Maven, pair[0], pair[1]);
+        }
+        assert_eq!(
+            compare(Ecosystem::Maven, "1.0.0.Final", "1"),
+            Some(Ordering::Equal)
+        );
+        lt(Ecosystem::Maven, "2.9.10", "2.10.0");
+    }
+
+    #[test]
+    fn semver_ecosystems_use_semver() {
+        lt(Ecosystem::Npm, "4.17.15", "4.17.21");
+        lt(Ecosystem::Rust, "1.0.0-rc.1", "1.0.0");
+        assert_eq!(compare(Ecosystem::Rust, "1.0", "1.0.0"), None);
+    }
+}
@@ -0,0 +1,104 @@
+use veil_guardian::{scan_lockfile, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "my-service"
+version = "0.1.0"
+dependencies = ["dummy-vulnerable-crate"]
+
+[[package]]
+name = "dummy-vulnerable-crate"
+version = "0.9.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "d432322312643a21f8a85e87d1211151631557a5f6e812543962635683226593"
+"#;
+
+#[test]
+fn test_cargo_lock_queries_osv_and_merges_builtin_advisories() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    // OSV knows GF-001 under a RUSTSEC id, and reports one more advisory.
+    let body = r#"{
+        "results": [
+            {
+                "vulns": [
+                    {
+                        "id": "RUSTSEC-2099-0001",
+                        "summary": "Memory corruption in dummy-vulnerable-crate",
+                        "aliases": ["GF-001"]
+                    },
+                    {
+                        "id": "GHSA-aaaa-bbbb-cccc",
+                        "summary": "Denial of service in dummy-vulnerable-crate"
+                    }
+                ]
+            }
+        ]
+    }"#;
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let lock_path = temp_dir.path().join("Cargo.lock");
+    std::fs::write(&lock_path, LOCKFILE).unwrap();
+
+    let result = scan_lockfile(
+        &lock_path,
+        ScanOptions {
+            offline: false,
+            show_details: false,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    assert_eq!(result.scanned_crates, 2);
+    assert_eq!(result.vulnerabilities.len(), 1);
+    let vuln = &result.vulnerabilities[0];
+    assert_eq!(vuln.package_name, "dummy-vulnerable-crate");
+    let ids: Vec<&str> = vuln.advisories.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, vec!["RUSTSEC-2099-0001", "GHSA-aaaa-bbbb-cccc"]);
+    assert_eq!(vuln.advisories[0].aliases, vec!["GF-001".to_string()]);
+
+    // The workspace member is never sent to OSV.
+    let requests = rt.block_on(mock_server.received_requests()).unwrap();
+    let sent = String::from_utf8_lossy(&requests[0].body).to_string();
+    assert!(sent.contains("\"ecosystem\":\"crates.io\""));
+    assert!(!sent.contains("my-service"));
+}
+
+#[test]
+fn test_cargo_lock_offline_cache_miss_is_an_error() {
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let lock_path = temp_dir.path().join("Cargo.lock");
+    std::fs::write(&lock_path, LOCKFILE).unwrap();
+
+    let err = scan_lockfile(
+        &lock_path,
+        ScanOptions {
+            offline: true,
+            show_details: false,
+            osv_api_url: None,
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Offline mode"));
+}
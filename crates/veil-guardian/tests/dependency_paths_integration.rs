@@ -0,0 +1,86 @@
+use veil_guardian::report::OutputFormat;
+use veil_guardian::{scan_lockfile, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+const PACKAGE_LOCK: &str = r#"{
+    "name": "app",
+    "lockfileVersion": 3,
+    "packages": {
+        "": { "name": "app", "dependencies": { "express": "^4.17.0", "qs": "^6.7.0" } },
+        "node_modules/express": { "version": "4.17.1",
+            "dependencies": { "body-parser": "1.19.0" } },
+        "node_modules/body-parser": { "version": "1.19.0",
+            "dependencies": { "qs": "6.7.0" } },
+        "node_modules/body-parser/node_modules/qs": { "version": "6.7.0" },
+        "node_modules/qs": { "version": "6.11.0" }
+    }
+}"#;
+
+#[test]
+fn test_transitive_finding_reports_dependency_path() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    // Queries are sorted by name and version: body-parser, express, qs 6.11.0, qs 6.7.0.
+    let body = r#"{
+        "results": [
+            {},
+            {},
+            {},
+            { "vulns": [ { "id": "GHSA-qs", "summary": "Prototype pollution in qs" } ] }
+        ]
+    }"#;
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let lock_path = temp_dir.path().join("package-lock.json");
+    std::fs::write(&lock_path, PACKAGE_LOCK).unwrap();
+
+    let result = scan_lockfile(
+        &lock_path,
+        ScanOptions {
+            offline: false,
+            show_details: false,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    assert_eq!(result.vulnerabilities.len(), 1);
+    let vuln = &result.vulnerabilities[0];
+    assert_eq!(vuln.package_name, "qs");
+    assert_eq!(vuln.version, "6.7.0");
+    assert_eq!(
+        vuln.dependency_paths,
+        vec![vec![
+            "express@4.17.1".to_string(),
+            "body-parser@1.19.0".to_string(),
+            "qs@6.7.0".to_string(),
+        ]]
+    );
+
+    let human = result.display(OutputFormat::Human);
+    assert!(
+        human.contains("  Path: express@4.17.1 > body-parser@1.19.0 > qs@6.7.0\n"),
+        "{}",
+        human
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&result.display(OutputFormat::Json)).unwrap();
+    assert_eq!(
+        json["vulnerabilities"][0]["dependency_paths"][0][1],
+        "body-parser@1.19.0"
+    );
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use veil_guardian::models::Ecosystem;
+use veil_guardian::{scan_lockfile, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+#[test]
+fn test_python_go_maven_lockfiles_query_osv_ecosystems() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    // Every query reports the same advisory for the first package only.
+    let body = r#"{
+        "results": [
+            { "vulns": [ { "id": "OSV-TEST-1", "summary": "Test advisory" } ] }
+        ]
+    }"#;
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+    });
+
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures");
+    let cases = [
+        (
+            "python/poetry.lock",
+            "poetry.lock",
+            Ecosystem::PyPI,
+            3,
+            "django",
+        ),
+        (
+            "python/Pipfile.lock",
+            "Pipfile.lock",
+            Ecosystem::PyPI,
+            3,
+            "jinja2",
+        ),
+        (
+            "python/requirements.txt",
+            "requirements.txt",
+            Ecosystem::PyPI,
+            4,
+            "cryptography",
+        ),
+        (
+            "go/go.sum",
+            "go.sum",
+            Ecosystem::Go,
+            3,
+            "github.com/docker/docker",
+        ),
+        (
+            "go/go.mod",
+            "go.mod",
+            Ecosystem::Go,
+            4,
+            "github.com/gin-gonic/gin",
+        ),
+        (
+            "maven/pom.xml",
+            "pom.xml",
+            Ecosystem::Maven,
+            4,
+            "com.example:service-common",
+        ),
+        (
+            "maven/gradle.lockfile",
+            "gradle.lockfile",
+            Ecosystem::Maven,
+            3,
+            "com.fasterxml.jackson.core:jackson-databind",
+        ),
+    ];
+
+    for (fixture, filename, ecosystem, scanned, first_package) in cases {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let lock_path = temp_dir.path().join(filename);
+        std::fs::copy(fixtures.join(fixture), &lock_path).expect("failed to copy fixture");
+
+        let result = scan_lockfile(
+            &lock_path,
+            ScanOptions {
+                offline: false,
+                show_details: false,
+                osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+                metrics: None,
+                cache_dir: Some(temp_dir.path().join("cache")),
+                exceptions: None,
+                license_policy: None,
+            },
+        )
+        .unwrap_or_else(|e| panic!("{fixture}: {e}"));
+
+        assert_eq!(result.scanned_crates, scanned, "{fixture}");
+        assert_eq!(result.vulnerabilities.len(), 1, "{fixture}");
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.ecosystem, ecosystem, "{fixture}");
+        assert_eq!(vuln.package_name, first_package, "{fixture}");
+
+        let requests = rt.block_on(mock_server.received_requests()).unwrap();
+        let sent = String::from_utf8_lossy(&requests.last().unwrap().body).to_string();
+        assert!(
+            sent.contains(&format!("\"ecosystem\":\"{}\"", ecosystem.osv_name())),
+            "{fixture}: {sent}"
+        );
+    }
+}
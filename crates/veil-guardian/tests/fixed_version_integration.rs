@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+use veil_guardian::{report::OutputFormat, scan_lockfile, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+#[test]
+fn test_fixed_version_from_osv_ranges() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    let body = r#"{
+        "results": [
+            { "vulns": [ { "id": "GHSA-a1" }, { "id": "GHSA-a2" } ] },
+            { "vulns": [ { "id": "GHSA-b1" } ] }
+        ]
+    }"#;
+    // a-lib@1.0.0: GHSA-a1 is fixed in 1.0.1, but 1.0.1 is inside GHSA-a2,
+    // which is fixed in 1.2.0. b-lib@2.0.0 has no fix at all.
+    let details = [
+        (
+            "GHSA-a1",
+            r#"{ "id": "GHSA-a1", "summary": "A1",
+                 "affected": [ { "package": { "ecosystem": "npm", "name": "a-lib" },
+                   "ranges": [ { "type": "SEMVER",
+                     "events": [ { "introduced": "0" }, { "fixed": "1.0.1" } ] } ] } ] }"#,
+        ),
+        (
+            "GHSA-a2",
+            r#"{ "id": "GHSA-a2", "summary": "A2",
+                 "affected": [ { "package": { "ecosystem": "npm", "name": "a-lib" },
+                   "ranges": [ { "type": "SEMVER",
+                     "events": [ { "introduced": "0.9.0" }, { "fixed": "1.2.0" } ] } ] } ] }"#,
+        ),
+        (
+            "GHSA-b1",
+            r#"{ "id": "GHSA-b1", "summary": "B1",
+                 "affected": [ { "package": { "ecosystem": "npm", "name": "b-lib" },
+                   "ranges": [ { "type": "SEMVER",
+                     "events": [ { "introduced": "0" }, { "last_affected": "2.5.0" } ] } ] } ] }"#,
+        ),
+    ];
+
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        for (id, json) in details {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/vulns/{}", id)))
+                .respond_with(ResponseTemplate::new(200).set_body_string(json))
+                .mount(&mock_server)
+                .await;
+        }
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let lock_path = temp_dir.path().join("package-lock.json");
+    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/complex.package-lock.json");
+    std::fs::copy(fixture_path, &lock_path).expect("failed to copy fixture");
+
+    let result = scan_lockfile(
+        &lock_path,
+        ScanOptions {
+            offline: false,
+            show_details: true,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    let fixed: Vec<(&str, Option<&str>)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.package_name.as_str(), v.fixed_version.as_deref()))
+        .collect();
+    assert_eq!(fixed, vec![("a-lib", Some("1.2.0")), ("b-lib", None)]);
+    assert!(result.has_fixable());
+
+    let human = result.display(OutputFormat::Human);
+    assert!(human.contains("(1 fixable by upgrading)"));
+    assert!(human.contains("  Upgrade: 1.0.0 -> 1.2.0"));
+    assert!(human.contains("  Upgrade: no fixed version covers every advisory"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&result.display(OutputFormat::Json)).unwrap();
+    assert_eq!(json["vulnerabilities"][0]["fixed_version"], "1.2.0");
+    assert!(json["vulnerabilities"][1].get("fixed_version").is_none());
+}
+
+#[test]
+fn test_fixed_version_from_builtin_advisory() {
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let lock_path = temp_dir.path().join("Cargo.lock");
+    // A path dependency: only the builtin DB (GF-002, ">= 2.0.0, < 2.1.3") applies.
+    std::fs::write(
+        &lock_path,
+        "version = 3\n\n[[package]]\nname = \"another-crate\"\nversion = \"2.1.0\"\n",
+    )
+    .unwrap();
+
+    let result = scan_lockfile(
+        &lock_path,
+        ScanOptions {
+            offline: true,
+            show_details: false,
+            osv_api_url: None,
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    assert_eq!(result.vulnerabilities.len(), 1);
+    assert_eq!(
+        result.vulnerabilities[0].fixed_version.as_deref(),
+        Some("2.1.3")
+    );
+    assert!(result
+        .display(OutputFormat::Human)
+        .contains("    Fix: Upgrade to >= 2.1.3"));
+}
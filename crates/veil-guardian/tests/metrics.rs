@@ -101,6 +101,8 @@ fn test_metrics_collection() {
             osv_api_url: Some(osv_url),
             metrics: Some(metrics.clone()),
             cache_dir: None,
+            exceptions: None,
+            license_policy: None,
         },
     )
     .expect("Scan failed");
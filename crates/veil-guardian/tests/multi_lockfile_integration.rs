@@ -0,0 +1,78 @@
+use veil_guardian::{scan_lockfiles, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+fn package_lock(packages: &[(&str, &str)]) -> String {
+    let entries: Vec<String> = packages
+        .iter()
+        .map(|(name, version)| format!(r#""node_modules/{name}": {{ "version": "{version}" }}"#))
+        .collect();
+    format!(
+        r#"{{ "name": "app", "lockfileVersion": 3, "packages": {{ "": {{ "name": "app", "version": "1.0.0" }}, {} }} }}"#,
+        entries.join(", ")
+    )
+}
+
+#[test]
+fn test_shared_packages_are_queried_once_and_located_in_every_lockfile() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    // Unique packages in first-seen order: lodash, minimist.
+    let body = r#"{
+        "results": [
+            { "vulns": [ { "id": "GHSA-p6mc-m468-83gw", "summary": "Prototype Pollution in lodash" } ] },
+            {}
+        ]
+    }"#;
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let web = temp_dir.path().join("web/package-lock.json");
+    let admin = temp_dir.path().join("admin/package-lock.json");
+    std::fs::create_dir_all(web.parent().unwrap()).unwrap();
+    std::fs::create_dir_all(admin.parent().unwrap()).unwrap();
+    std::fs::write(
+        &web,
+        package_lock(&[("lodash", "4.17.15"), ("minimist", "1.2.6")]),
+    )
+    .unwrap();
+    std::fs::write(&admin, package_lock(&[("lodash", "4.17.15")])).unwrap();
+
+    let result = scan_lockfiles(
+        &[web.clone(), admin.clone()],
+        ScanOptions {
+            offline: false,
+            show_details: false,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    assert_eq!(result.scanned_crates, 2);
+    assert_eq!(result.vulnerabilities.len(), 1);
+    let vuln = &result.vulnerabilities[0];
+    assert_eq!(vuln.package_name, "lodash");
+    assert_eq!(
+        vuln.locations,
+        vec![
+            web.to_string_lossy().to_string(),
+            admin.to_string_lossy().to_string()
+        ]
+    );
+
+    let requests = rt.block_on(mock_server.received_requests()).unwrap();
+    let sent = String::from_utf8_lossy(&requests[0].body).to_string();
+    assert_eq!(sent.matches("\"lodash\"").count(), 1);
+}
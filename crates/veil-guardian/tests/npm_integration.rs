@@ -59,6 +59,8 @@ fn test_npm_integration() {
             osv_api_url: Some(osv_url),
             metrics: None,
             cache_dir: None,
+            exceptions: None,
+            license_policy: None,
         },
     )
     .expect("Scan failed");
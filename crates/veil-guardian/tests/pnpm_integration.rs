@@ -49,6 +49,8 @@ fn test_pnpm_integration() {
             osv_api_url: Some(osv_url),
             metrics: None,
             cache_dir: None,
+            exceptions: None,
+            license_policy: None,
         },
     )
     .expect("Scan failed");
@@ -110,6 +110,8 @@ fn test_report_output_deterministic() {
             osv_api_url: Some(osv_url),
             metrics: None,
             cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
         },
     )
     .expect("Scan failed");
@@ -188,6 +190,8 @@ fn test_report_output_duplicates() {
             osv_api_url: Some(osv_url),
             metrics: None,
             cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
         },
     )
     .expect("Scan failed");
@@ -220,6 +224,7 @@ fn test_report_grouping_logic() {
         crate_name: "manual-lib".to_string(),
         vulnerable_versions: VersionReq::parse("*").unwrap(),
         description: "Dup 1".to_string(),
+        aliases: Vec::new(),
         details: None,
         cache_status: Some("Hit (Fresh)".to_string()),
         last_fetched_at: None,
@@ -231,6 +236,10 @@ fn test_report_grouping_logic() {
         version: "1.0.0".to_string(),
         advisories: vec![adv1.clone()],
         locations: vec!["path/to/lock_A".to_string()],
+        fixed_version: None,
+        severity: None,
+        cvss_score: None,
+        dependency_paths: Vec::new(),
     };
 
     let vuln2 = Vulnerability {
@@ -239,11 +248,16 @@ fn test_report_grouping_logic() {
         version: "1.0.0".to_string(),
         advisories: vec![adv1.clone()], // Duplicate advisory, should merge
         locations: vec!["path/to/lock_B".to_string()], // Different location, should append
+        fixed_version: None,
+        severity: None,
+        cvss_score: None,
+        dependency_paths: Vec::new(),
     };
 
     let scan_result = ScanResult {
         vulnerabilities: vec![vuln1, vuln2],
         scanned_crates: 10,
+        ..ScanResult::default()
     };
 
     let output = scan_result.display(OutputFormat::Human);
@@ -0,0 +1,245 @@
+use veil_guardian::exceptions::{AdvisoryException, ExceptionRegistry};
+use veil_guardian::report::OutputFormat;
+use veil_guardian::{is_supported_lockfile, scan_lockfile, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+const CYCLONEDX: &str = r#"{
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.5",
+    "components": [
+        { "type": "library", "name": "a-lib", "purl": "pkg:npm/a-lib@1.0.0" },
+        { "type": "library", "name": "dummy-vulnerable-crate",
+          "purl": "pkg:cargo/dummy-vulnerable-crate@0.9.9" },
+        { "type": "library", "name": "requests", "purl": "pkg:pypi/requests@2.31.0" }
+    ]
+}"#;
+
+#[test]
+fn test_sbom_filenames_are_supported() {
+    assert!(is_supported_lockfile("bom.json"));
+    assert!(is_supported_lockfile("app.cdx.json"));
+    assert!(is_supported_lockfile("app.spdx.json"));
+    assert!(!is_supported_lockfile("app.json"));
+}
+
+#[test]
+fn test_cyclonedx_input_and_vex_output() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    // Queries are sorted by ecosystem: crates.io, PyPI, npm.
+    let body = r#"{
+        "results": [
+            {},
+            {},
+            { "vulns": [ { "id": "GHSA-a1" }, { "id": "GHSA-a2" } ] }
+        ]
+    }"#;
+    let details = r#"{ "id": "GHSA-a1", "summary": "Prototype pollution",
+        "severity": [ { "type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H" } ],
+        "affected": [ { "package": { "ecosystem": "npm", "name": "a-lib" },
+          "ranges": [ { "type": "SEMVER",
+            "events": [ { "introduced": "0" }, { "fixed": "1.2.0" } ] } ] } ] }"#;
+    let v4_details = r#"{ "id": "GHSA-a2", "summary": "Info leak",
+        "severity": [ { "type": "CVSS_V4",
+          "score": "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:N/VA:N/SC:N/SI:N/SA:N" } ],
+        "database_specific": { "severity": "MODERATE" } }"#;
+
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/GHSA-a1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(details))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/GHSA-a2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(v4_details))
+            .mount(&mock_server)
+            .await;
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let sbom_path = temp_dir.path().join("app.cdx.json");
+    std::fs::write(&sbom_path, CYCLONEDX).unwrap();
+
+    let exceptions = ExceptionRegistry {
+        exceptions: vec![AdvisoryException {
+            advisory: "GF-001".to_string(),
+            package: "dummy-vulnerable-crate".to_string(),
+            versions: None,
+            reason: "Test-only crate".to_string(),
+            created_at: None,
+            created_by: None,
+            expires_at: None,
+        }],
+        ..ExceptionRegistry::default()
+    };
+
+    let result = scan_lockfile(
+        &sbom_path,
+        ScanOptions {
+            offline: false,
+            show_details: true,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: Some(exceptions),
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    assert_eq!(result.scanned_crates, 3);
+    assert_eq!(result.vulnerabilities.len(), 1);
+    assert_eq!(result.suppressed.len(), 1);
+
+    let bom: serde_json::Value =
+        serde_json::from_str(&result.display(OutputFormat::CycloneDx)).unwrap();
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+    assert_eq!(bom["specVersion"], "1.5");
+    assert!(bom["serialNumber"]
+        .as_str()
+        .unwrap()
+        .starts_with("urn:uuid:"));
+    let purls: Vec<&str> = bom["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["purl"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        purls,
+        vec![
+            "pkg:cargo/dummy-vulnerable-crate@0.9.9",
+            "pkg:npm/a-lib@1.0.0",
+            "pkg:pypi/requests@2.31.0",
+        ]
+    );
+
+    let vulns = bom["vulnerabilities"].as_array().unwrap();
+    assert_eq!(vulns.len(), 3);
+    let open = &vulns[0];
+    assert_eq!(open["id"], "GHSA-a1");
+    assert_eq!(open["affects"][0]["ref"], "pkg:npm/a-lib@1.0.0");
+    assert_eq!(
+        open["source"]["url"],
+        "https://osv.dev/vulnerability/GHSA-a1"
+    );
+    assert_eq!(open["ratings"][0]["method"], "CVSSv31");
+    assert_eq!(open["ratings"][0]["severity"], "high");
+    assert_eq!(open["ratings"][0]["score"], 7.5);
+    assert_eq!(open["analysis"]["state"], "in_triage");
+    assert_eq!(open["analysis"]["response"][0], "update");
+    assert_eq!(open["recommendation"], "Upgrade to >= 1.2.0");
+
+    // The v4 score takes precedence over the advisory's MODERATE label.
+    let v4_only = &vulns[1];
+    assert_eq!(v4_only["id"], "GHSA-a2");
+    assert_eq!(
+        v4_only["ratings"],
+        serde_json::json!([{
+            "severity": "high",
+            "method": "CVSSv4",
+            "score": 8.7,
+            "vector": "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:N/VA:N/SC:N/SI:N/SA:N"
+        }])
+    );
+
+    let accepted = &vulns[2];
+    assert_eq!(accepted["id"], "GF-001");
+    assert_eq!(
+        accepted["affects"][0]["ref"],
+        "pkg:cargo/dummy-vulnerable-crate@0.9.9"
+    );
+    assert_eq!(accepted["analysis"]["state"], "exploitable");
+    assert_eq!(accepted["analysis"]["response"][0], "will_not_fix");
+    assert_eq!(
+        accepted["analysis"]["detail"],
+        "Accepted by guardian exception: Test-only crate"
+    );
+
+    let spdx: serde_json::Value =
+        serde_json::from_str(&result.display(OutputFormat::Spdx)).unwrap();
+    assert_eq!(spdx["spdxVersion"], "SPDX-2.3");
+    let packages = spdx["packages"].as_array().unwrap();
+    assert_eq!(packages.len(), 3);
+    assert_eq!(packages[1]["name"], "a-lib");
+    let refs = packages[1]["externalRefs"].as_array().unwrap();
+    assert_eq!(refs[0]["referenceLocator"], "pkg:npm/a-lib@1.0.0");
+    assert_eq!(refs[1]["referenceCategory"], "SECURITY");
+    assert_eq!(
+        refs[1]["comment"],
+        "GHSA-a1: Affected; Upgrade to >= 1.2.0 [HIGH]"
+    );
+}
+
+#[test]
+fn test_spdx_input_roundtrips_through_cyclonedx_output() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{ "results": [ {}, {} ] }"#),
+            )
+            .mount(&mock_server)
+            .await;
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let sbom_path = temp_dir.path().join("app.spdx.json");
+    std::fs::write(
+        &sbom_path,
+        r#"{
+            "spdxVersion": "SPDX-2.3",
+            "packages": [
+                { "SPDXID": "SPDXRef-1", "name": "serde", "externalRefs": [
+                    { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+                      "referenceLocator": "pkg:cargo/serde@1.0.200" } ] },
+                { "SPDXID": "SPDXRef-2", "name": "gin", "externalRefs": [
+                    { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl",
+                      "referenceLocator": "pkg:golang/github.com/gin-gonic/gin@v1.9.1" } ] }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let result = scan_lockfile(
+        &sbom_path,
+        ScanOptions {
+            offline: false,
+            show_details: false,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+    assert_eq!(result.scanned_crates, 2);
+
+    let bom: serde_json::Value =
+        serde_json::from_str(&result.display(OutputFormat::CycloneDx)).unwrap();
+    let purls: Vec<&str> = bom["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["purl"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        purls,
+        vec![
+            "pkg:cargo/serde@1.0.200",
+            "pkg:golang/github.com/gin-gonic/gin@v1.9.1",
+        ]
+    );
+    assert_eq!(bom["vulnerabilities"].as_array().unwrap().len(), 0);
+}
@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+use veil_guardian::report::{OutputFormat, Severity};
+use veil_guardian::{scan_lockfile, ScanOptions};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+#[test]
+fn test_severity_from_cvss_and_database_specific() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mock_server = rt.block_on(wiremock::MockServer::start());
+
+    let body = r#"{
+        "results": [
+            { "vulns": [ { "id": "GHSA-crit" }, { "id": "GHSA-v4" } ] },
+            { "vulns": [ { "id": "GHSA-low" }, { "id": "GHSA-v4-low" } ] }
+        ]
+    }"#;
+    let details = [
+        (
+            "GHSA-crit",
+            r#"{ "id": "GHSA-crit", "summary": "RCE",
+                 "severity": [
+                   { "type": "CVSS_V4", "score": "CVSS:4.0/AV:L/AC:H/AT:P/PR:H/UI:A/VC:L/VI:N/VA:N/SC:N/SI:N/SA:N" },
+                   { "type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" }
+                 ] }"#,
+        ),
+        (
+            "GHSA-v4",
+            r#"{ "id": "GHSA-v4", "summary": "Info leak",
+                 "severity": [
+                   { "type": "CVSS_V4", "score": "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:N/VA:N/SC:N/SI:N/SA:N" }
+                 ] }"#,
+        ),
+        (
+            "GHSA-low",
+            r#"{ "id": "GHSA-low", "summary": "Minor",
+                 "database_specific": { "severity": "LOW" } }"#,
+        ),
+        (
+            "GHSA-v4-low",
+            r#"{ "id": "GHSA-v4-low", "summary": "Local info leak",
+                 "severity": [
+                   { "type": "CVSS_V4", "score": "CVSS:4.0/AV:L/AC:H/AT:P/PR:H/UI:A/VC:L/VI:N/VA:N/SC:N/SI:N/SA:N" }
+                 ] }"#,
+        ),
+    ];
+
+    rt.block_on(async {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        for (id, json) in details {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/vulns/{}", id)))
+                .respond_with(ResponseTemplate::new(200).set_body_string(json))
+                .mount(&mock_server)
+                .await;
+        }
+    });
+
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let lock_path = temp_dir.path().join("package-lock.json");
+    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/complex.package-lock.json");
+    std::fs::copy(fixture_path, &lock_path).expect("failed to copy fixture");
+
+    let mut result = scan_lockfile(
+        &lock_path,
+        ScanOptions {
+            offline: false,
+            show_details: true,
+            osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
+            metrics: None,
+            cache_dir: Some(temp_dir.path().join("cache")),
+            exceptions: None,
+            license_policy: None,
+        },
+    )
+    .expect("Scan failed");
+
+    let rated: Vec<(&str, Option<Severity>, Option<f64>)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.package_name.as_str(), v.severity, v.cvss_score))
+        .collect();
+    assert_eq!(
+        rated,
+        vec![
+            ("a-lib", Some(Severity::Critical), Some(9.8)),
+            ("b-lib", Some(Severity::Low), Some(1.0)),
+        ]
+    );
+
+    let human = result.display(OutputFormat::Human);
+    assert!(human.contains("- a-lib v1.0.0 (npm) [CRITICAL 9.8]"));
+    assert!(
+        human.contains("    Severity: CRITICAL 9.8 (CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H)")
+    );
+    assert!(human.contains(
+        "    Severity: HIGH 8.7 (CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:N/VA:N/SC:N/SI:N/SA:N)"
+    ));
+    assert!(human.contains("- b-lib v2.0.0 (npm) [LOW 1.0]"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&result.display(OutputFormat::Json)).unwrap();
+    assert_eq!(json["vulnerabilities"][0]["severity"], "Critical");
+    assert_eq!(json["vulnerabilities"][0]["cvss_score"], 9.8);
+
+    result.retain_min_severity(Severity::High);
+    let remaining: Vec<&str> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| v.package_name.as_str())
+        .collect();
+    assert_eq!(remaining, vec!["a-lib"]);
+    assert_eq!(result.vulnerabilities[0].advisories.len(), 2);
+}
+
+#[test]
+fn test_severity_thresholds() {
+    assert_eq!("moderate".parse::<Severity>(), Ok(Severity::Medium));
+    assert_eq!("Critical".parse::<Severity>(), Ok(Severity::Critical));
+    assert!("severe".parse::<Severity>().is_err());
+
+    assert_eq!(Severity::from_score(9.0), Severity::Critical);
+    assert_eq!(Severity::from_score(6.9), Severity::Medium);
+    assert_eq!(Severity::from_score(0.0), Severity::Unknown);
+
+    assert!(Severity::Critical.meets(Severity::High));
+    assert!(Severity::High.meets(Severity::High));
+    assert!(!Severity::Medium.meets(Severity::High));
+    assert!(!Severity::Unknown.meets(Severity::Low));
+}
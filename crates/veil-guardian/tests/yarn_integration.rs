@@ -47,6 +47,8 @@ fn test_yarn_integration() {
         osv_api_url: Some(mock_server.uri() + "/v1/querybatch"),
         metrics: None,
         cache_dir: None,
+        exceptions: None,
+        license_policy: None,
     };
     let result = scan_lockfile(&lockfile_path, options).unwrap();
 
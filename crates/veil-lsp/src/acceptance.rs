@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use veil_core::baseline::{apply_baseline, load_baseline, resolve_compatible_baseline_path};
+use veil_core::registry::{ExceptionStatus, Registry, RegistryError};
+use veil_core::scanner::path_filter::relative_to;
+use veil_core::{Finding, FindingId, Rule};
+
+use crate::diagnostics::findings_to_diagnostics;
+
+/// Repository-relative location of the exception registry, as used by
+/// `veil exceptions`.
+pub const REGISTRY_FILE: &str = "ops/exceptions.toml";
+
+/// Findings CI already accepts: entries of `veil.baseline.json` and active
+/// exceptions of the registry, both resolved against the server's root.
+#[derive(Debug, Default)]
+pub struct Acceptance {
+    root: PathBuf,
+    baseline: Option<veil_core::baseline::BaselineSnapshot>,
+    registry: Registry,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Accepted {
+    Baseline,
+    Exception(Option<DateTime<Utc>>),
+    ExceptionExpired(DateTime<Utc>),
+}
+
+impl Acceptance {
+    pub fn load(root: &Path) -> Result<Self> {
+        let baseline = resolve_compatible_baseline_path(root)
+            .map(|path| {
+                load_baseline(&path)
+                    .with_context(|| format!("Failed to load baseline from {}", path.display()))
+            })
+            .transpose()?;
+
+        let registry = match Registry::load(&root.join(REGISTRY_FILE)) {
+            Ok(registry) => registry,
+            Err(RegistryError::NotFound(_)) => Registry::default(),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            baseline,
+            registry,
+        })
+    }
+
+    pub fn registry_path(&self) -> PathBuf {
+        self.root.join(REGISTRY_FILE)
+    }
+
+    /// Stable id of a finding for the exception registry.
+    pub fn finding_id(&self, finding: &Finding) -> FindingId {
+        FindingId::for_finding(finding, &self.root)
+    }
+
+    /// Diagnostics for `findings`, with accepted findings demoted to hints
+    /// (or left out when `hide_accepted` is set).
+    pub fn diagnostics(
+        &self,
+        findings: Vec<Finding>,
+        rules: &[Rule],
+        hide_accepted: bool,
+        now: DateTime<Utc>,
+    ) -> Vec<Diagnostic> {
+        let (new, baselined) = self.partition_baseline(findings);
+
+        let mut diagnostics = Vec::new();
+        for (findings, baselined) in [(new, false), (baselined, true)] {
+            let ids: Vec<FindingId> = findings
+                .iter()
+                .map(|finding| self.finding_id(finding))
+                .collect();
+            for (mut diagnostic, id) in findings_to_diagnostics(&findings, rules)
+                .into_iter()
+                .zip(ids)
+            {
+                let accepted = if baselined {
+                    Some(Accepted::Baseline)
+                } else {
+                    match self.registry.check(&id, now) {
+                        ExceptionStatus::Active => Some(Accepted::Exception(
+                            self.registry
+                                .exceptions
+                                .iter()
+                                .find(|entry| entry.id == id)
+                                .and_then(|entry| entry.expires_at),
+                        )),
+                        ExceptionStatus::Expired(expired_at) => {
+                            Some(Accepted::ExceptionExpired(expired_at))
+                        }
+                        ExceptionStatus::NotExcepted => None,
+                    }
+                };
+                let suppressed =
+                    matches!(accepted, Some(Accepted::Baseline | Accepted::Exception(_)));
+                if suppressed && hide_accepted {
+                    continue;
+                }
+
+                annotate(&mut diagnostic, &id, accepted);
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.range.start, diagnostic.range.end));
+        diagnostics
+    }
+
+    /// Splits off baselined findings. Baseline fingerprints contain the path
+    /// as `veil scan` printed it, so both `src/a.rs` and `./src/a.rs` forms
+    /// (relative to the root) are tried.
+    fn partition_baseline(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<Finding>) {
+        let Some(baseline) = &self.baseline else {
+            return (findings, Vec::new());
+        };
+
+        // Every form of a path normalizes to the same relative key.
+        let original_paths: HashMap<PathBuf, PathBuf> = findings
+            .iter()
+            .map(|finding| (relative_to(&finding.path, &self.root), finding.path.clone()))
+            .collect();
+        let path_forms: [fn(PathBuf) -> PathBuf; 2] = [
+            |relative| relative,
+            |relative| Path::new(".").join(relative),
+        ];
+
+        let mut remaining = findings;
+        let mut suppressed = Vec::new();
+        for form in path_forms {
+            for finding in &mut remaining {
+                finding.path = form(relative_to(&finding.path, &self.root));
+            }
+            let result = apply_baseline(remaining, Some(baseline));
+            suppressed.extend(result.suppressed);
+            remaining = result.new;
+        }
+
+        let restore = |mut finding: Finding| {
+            let relative = relative_to(&finding.path, &self.root);
+            if let Some(original) = original_paths.get(&relative) {
+                finding.path = original.clone();
+            }
+            finding
+        };
+        (
+            remaining.into_iter().map(restore).collect(),
+            suppressed.into_iter().map(restore).collect(),
+        )
+    }
+}
+
+fn annotate(diagnostic: &mut Diagnostic, id: &FindingId, accepted: Option<Accepted>) {
+    let accepted_data = match accepted {
+        Some(Accepted::Baseline) => {
+            diagnostic.severity = Some(DiagnosticSeverity::HINT);
+            diagnostic.message.push_str(" — accepted in baseline");
+            Some(json!({ "source": "baseline" }))
+        }
+        Some(Accepted::Exception(expires_at)) => {
+            diagnostic.severity = Some(DiagnosticSeverity::HINT);
+            match expires_at {
+                Some(expires_at) => diagnostic.message.push_str(&format!(
+                    " — accepted until {}",
+                    expires_at.format("%Y-%m-%d")
+                )),
+                None => diagnostic.message.push_str(" — accepted without expiry"),
+            }
+            Some(json!({
+                "source": "exception",
+                "expiresAt": expires_at.map(|expires_at| expires_at.to_rfc3339()),
+            }))
+        }
+        Some(Accepted::ExceptionExpired(expired_at)) => {
+            diagnostic.message.push_str(&format!(
+                " — exception expired on {}",
+                expired_at.format("%Y-%m-%d")
+            ));
+            None
+        }
+        None => None,
+    };
+
+    if let Some(serde_json::Value::Object(data)) = diagnostic.data.as_mut() {
+        data.insert("findingId".to_string(), json!(id.to_string()));
+        if let Some(accepted_data) = accepted_data {
+            data.insert("accepted".to_string(), accepted_data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tower_lsp::lsp_types::NumberOrString;
+    use veil_config::Config;
+    use veil_core::baseline::{from_findings, save_baseline, DEFAULT_BASELINE_FILE};
+    use veil_core::registry::ExceptionEntry;
+    use veil_core::{scan_content, try_get_all_rules};
+
+    const TEXT: &str = "first = test@example.com\nsecond = other@example.com\n";
+
+    fn scan(root: &Path, rules: &[Rule], config: &Config) -> Vec<Finding> {
+        let findings = scan_content(TEXT, &root.join("src/contacts.txt"), rules, config);
+        assert_eq!(findings.len(), 2, "{findings:?}");
+        findings
+    }
+
+    fn is_email(diagnostic: &Diagnostic) -> bool {
+        matches!(
+            diagnostic.code.as_ref(),
+            Some(NumberOrString::String(code)) if code == "pii.net.email"
+        )
+    }
+
+    #[test]
+    fn baselined_findings_become_hints() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let root = workspace.path();
+        let config = Config::default();
+        let rules = try_get_all_rules(&config, Vec::new()).expect("rules");
+
+        // `veil scan .` records paths with a leading `./`.
+        let mut baselined = scan(root, &rules, &config).remove(0);
+        baselined.path = PathBuf::from("./src/contacts.txt");
+        save_baseline(
+            &root.join(DEFAULT_BASELINE_FILE),
+            &from_findings(&[baselined], "test"),
+        )
+        .expect("baseline");
+
+        let acceptance = Acceptance::load(root).expect("acceptance");
+        let diagnostics =
+            acceptance.diagnostics(scan(root, &rules, &config), &rules, false, Utc::now());
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(is_email));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::HINT));
+        assert!(diagnostics[0].message.ends_with("— accepted in baseline"));
+        assert_eq!(
+            diagnostics[0].data.as_ref().expect("data")["accepted"]["source"],
+            "baseline"
+        );
+        assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::WARNING));
+        assert!(diagnostics[1].data.as_ref().expect("data")["accepted"].is_null());
+        assert_eq!(
+            diagnostics[1].related_information,
+            findings_to_diagnostics(&scan(root, &rules, &config), &rules)[1].related_information
+        );
+
+        let hidden = acceptance.diagnostics(scan(root, &rules, &config), &rules, true, Utc::now());
+        assert_eq!(hidden.len(), 1);
+        assert_eq!(hidden[0].range.start.line, 1);
+    }
+
+    #[test]
+    fn registry_exceptions_are_tagged_with_expiry() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let root = workspace.path();
+        let config = Config::default();
+        let rules = try_get_all_rules(&config, Vec::new()).expect("rules");
+        let now = Utc::now();
+
+        let without_registry = Acceptance::load(root).expect("acceptance");
+        let findings = scan(root, &rules, &config);
+        let active_id = without_registry.finding_id(&findings[0]);
+        let expired_id = without_registry.finding_id(&findings[1]);
+        let expires_at = DateTime::parse_from_rfc3339("2099-01-31T00:00:00Z")
+            .expect("date")
+            .with_timezone(&Utc);
+        let mut registry = Registry::default();
+        for (id, expires_at) in [
+            (active_id.clone(), Some(expires_at)),
+            (expired_id, Some(now - Duration::days(1))),
+        ] {
+            registry.exceptions.push(ExceptionEntry {
+                id,
+                reason: "test fixture".to_string(),
+                created_at: None,
+                created_by: None,
+                expires_at,
+            });
+        }
+        registry.save(&root.join(REGISTRY_FILE)).expect("registry");
+
+        let acceptance = Acceptance::load(root).expect("acceptance");
+        let diagnostics = acceptance.diagnostics(findings, &rules, false, now);
+
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::HINT));
+        assert!(diagnostics[0]
+            .message
+            .ends_with("— accepted until 2099-01-31"));
+        let data = diagnostics[0].data.as_ref().expect("data");
+        assert_eq!(data["findingId"], active_id.to_string());
+        assert_eq!(data["accepted"]["source"], "exception");
+
+        assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::WARNING));
+        assert!(diagnostics[1].message.contains("— exception expired on"));
+    }
+
+    #[test]
+    fn load_rejects_malformed_registry() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let root = workspace.path();
+        std::fs::create_dir_all(root.join("ops")).expect("ops");
+        std::fs::write(root.join(REGISTRY_FILE), "exceptions = [").expect("registry");
+
+        let error = Acceptance::load(root).expect_err("malformed registry");
+
+        assert!(error.to_string().contains("failed to parse registry"));
+    }
+}
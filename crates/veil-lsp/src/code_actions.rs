@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use chrono::{Duration, Utc};
 use tower_lsp::lsp_types::{
-    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString, Position,
+    CodeAction, CodeActionKind, CodeActionOrCommand, Command, Diagnostic, NumberOrString, Position,
     TextEdit, Url, WorkspaceEdit,
 };
 use veil_config::MaskMode;
 use veil_core::{apply_masks, DEFAULT_PLACEHOLDER};
 
+use crate::commands::{AddExceptionArgs, ADD_EXCEPTION, DEFAULT_EXCEPTION_DAYS};
 use crate::document_store::{byte_range_for_lsp_range, line_byte_bounds};
 
 pub fn code_actions(
@@ -23,6 +25,7 @@ pub fn code_actions(
                 mask_code_action(uri, text, diagnostic),
                 partial_mask_code_action(uri, text, diagnostic),
                 inline_ignore_code_action(uri, language_id, text, diagnostic),
+                add_exception_code_action(diagnostic),
             ]
         })
         .flatten()
@@ -133,6 +136,39 @@ fn inline_ignore_code_action(
     })
 }
 
+/// Records the finding in the exception registry through
+/// [`ADD_EXCEPTION`]; the reason can be refined in the registry afterwards.
+fn add_exception_code_action(diagnostic: &Diagnostic) -> Option<CodeAction> {
+    let data = diagnostic.data.as_ref()?;
+    if data.get("accepted").is_some() {
+        return None;
+    }
+    let finding_id = data.get("findingId")?.as_str()?;
+    let rule_id = data.get("ruleId")?.as_str()?;
+
+    let title = format!("Add exception to registry (expires in {DEFAULT_EXCEPTION_DAYS} days)");
+    let arguments = AddExceptionArgs {
+        finding_id: finding_id.to_string(),
+        reason: format!("Accepted {rule_id} finding from the editor"),
+        expires_at: Some(Utc::now() + Duration::days(DEFAULT_EXCEPTION_DAYS)),
+    };
+
+    Some(CodeAction {
+        title: title.clone(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: None,
+        command: Some(Command {
+            title,
+            command: ADD_EXCEPTION.to_string(),
+            arguments: Some(vec![serde_json::to_value(arguments).ok()?]),
+        }),
+        is_preferred: Some(false),
+        disabled: None,
+        data: None,
+    })
+}
+
 fn workspace_edit(
     uri: &Url,
     range: tower_lsp::lsp_types::Range,
@@ -326,6 +362,50 @@ mod tests {
         assert_eq!(ignore_text_edits[0].new_text, " // veil:ignore=secret.test");
     }
 
+    #[test]
+    fn code_actions_offer_registry_exception_for_unaccepted_findings() {
+        let uri = Url::parse("file:///tmp/example.txt").expect("uri");
+        let mut diagnostic = finding_diagnostic();
+        let data = diagnostic.data.as_mut().expect("data");
+        data["findingId"] = json!("fx_example");
+
+        let actions = code_actions(
+            &uri,
+            "plaintext",
+            "token = raw-secret-value",
+            &[diagnostic.clone()],
+        );
+        let Some(CodeActionOrCommand::CodeAction(action)) = actions.last() else {
+            panic!("expected code action");
+        };
+        assert_eq!(
+            action.title,
+            "Add exception to registry (expires in 30 days)"
+        );
+        let command = action.command.as_ref().expect("command");
+        assert_eq!(command.command, ADD_EXCEPTION);
+        let arguments: AddExceptionArgs =
+            serde_json::from_value(command.arguments.as_ref().expect("arguments")[0].clone())
+                .expect("arguments");
+        assert_eq!(arguments.finding_id, "fx_example");
+        assert_eq!(
+            arguments.reason,
+            "Accepted secret.test finding from the editor"
+        );
+        let expires_in = arguments.expires_at.expect("expiry") - Utc::now();
+        assert!(expires_in > Duration::days(29) && expires_in <= Duration::days(30));
+
+        diagnostic.data.as_mut().expect("data")["accepted"] = json!({ "source": "baseline" });
+        let actions = code_actions(&uri, "plaintext", "token = raw-secret-value", &[diagnostic]);
+        assert!(actions.iter().all(|action| !matches!(
+            action,
+            CodeActionOrCommand::CodeAction(CodeAction {
+                command: Some(_),
+                ..
+            })
+        )));
+    }
+
     #[test]
     fn code_actions_hide_inline_ignore_for_json() {
         let uri = Url::parse("file:///tmp/example.json").expect("uri");
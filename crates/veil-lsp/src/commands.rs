@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tower_lsp::lsp_types::{Position, Range, TextEdit, Url, WorkspaceEdit};
+use veil_config::Config;
+use veil_core::masking::{finding_byte_range, line_offsets};
+use veil_core::registry::{ExceptionEntry, Registry, RegistryError};
+use veil_core::{apply_masks_spans, scan_content, FindingId, MaskSpan, Rule};
+
+use crate::document_store::end_position;
+
+/// `workspace/executeCommand` id that records a finding in the exception
+/// registry.
+pub const ADD_EXCEPTION: &str = "veil.addException";
+
+/// `workspace/executeCommand` id that masks every finding of a document,
+/// like `veil mask` does for a file on disk.
+pub const MASK_FILE: &str = "veil.maskFile";
+
+/// `workspace/executeCommand` id that rescans the workspace roots.
+pub const SCAN_WORKSPACE: &str = "veil.scanWorkspace";
+
+/// Expiry offered by the "add exception" code action.
+pub const DEFAULT_EXCEPTION_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddExceptionArgs {
+    pub finding_id: String,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaskFileArgs {
+    pub uri: Url,
+}
+
+/// Adds or replaces the registry entry for `args.finding_id`, keeping the
+/// creation metadata of an existing entry like `veil exceptions add` does.
+pub fn add_exception(
+    registry_path: &Path,
+    args: &AddExceptionArgs,
+    now: DateTime<Utc>,
+) -> Result<FindingId> {
+    let id = FindingId::from_str(&args.finding_id).map_err(|e| anyhow::anyhow!(e))?;
+    if args.reason.trim().is_empty() {
+        anyhow::bail!("An exception needs a reason");
+    }
+
+    let mut registry = match Registry::load(registry_path) {
+        Ok(registry) => registry,
+        Err(RegistryError::NotFound(_)) => Registry::default(),
+        Err(error) => return Err(error.into()),
+    };
+
+    let (created_at, created_by) = match registry.exceptions.iter().find(|entry| entry.id == id) {
+        Some(existing) => (existing.created_at, existing.created_by.clone()),
+        None => (Some(now), std::env::var("USER").ok()),
+    };
+    registry.exceptions.retain(|entry| entry.id != id);
+    registry.exceptions.push(ExceptionEntry {
+        id: id.clone(),
+        reason: args.reason.clone(),
+        created_at,
+        created_by,
+        expires_at: args.expires_at,
+    });
+    registry.save(registry_path)?;
+
+    Ok(id)
+}
+
+/// `text` with every finding replaced using `[masking] placeholder` and
+/// `[output] mask_mode`, plus the number of masked findings; `None` when
+/// there is nothing to mask.
+pub fn mask_text(
+    text: &str,
+    path: &Path,
+    rules: &[Rule],
+    config: &Config,
+) -> Option<(String, usize)> {
+    let findings = scan_content(text, path, rules, config);
+    let offsets = line_offsets(text);
+    let spans: Vec<MaskSpan> = findings
+        .iter()
+        .filter_map(|finding| finding_byte_range(finding, &offsets, text.len()))
+        .map(|range| MaskSpan {
+            start: range.start,
+            end: range.end,
+            placeholder: config.masking.placeholder.clone(),
+            priority: 0,
+        })
+        .collect();
+    if spans.is_empty() {
+        return None;
+    }
+
+    let masked_count = spans.len();
+    let masked = apply_masks_spans(text, spans, config.output.mask_mode.unwrap_or_default());
+    Some((masked, masked_count))
+}
+
+/// Edit replacing the whole of `original` with `masked`, for
+/// `workspace/applyEdit`.
+pub fn mask_file_edit(uri: &Url, original: &str, masked: String) -> WorkspaceEdit {
+    WorkspaceEdit {
+        changes: Some(HashMap::from([(
+            uri.clone(),
+            vec![TextEdit {
+                range: Range {
+                    start: Position::default(),
+                    end: end_position(original),
+                },
+                new_text: masked,
+            }],
+        )])),
+        document_changes: None,
+        change_annotations: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use veil_config::MaskMode;
+    use veil_core::finding_id::SpanData;
+    use veil_core::try_get_all_rules;
+
+    fn finding_id(capture: &str) -> String {
+        FindingId::new(
+            "pii.net.email",
+            Path::new("src/contacts.txt"),
+            &SpanData {
+                start_line: 0,
+                start_col: 8,
+                end_line: 0,
+                end_col: 24,
+            },
+            capture,
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn add_exception_creates_and_updates_registry_entries() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let registry_path = workspace.path().join("ops/exceptions.toml");
+        let created = Utc::now();
+        let args = AddExceptionArgs {
+            finding_id: finding_id("a"),
+            reason: "Test fixture address".to_string(),
+            expires_at: Some(created + Duration::days(DEFAULT_EXCEPTION_DAYS)),
+        };
+
+        let id = add_exception(&registry_path, &args, created).expect("add");
+        assert_eq!(id.to_string(), args.finding_id);
+
+        let updated = AddExceptionArgs {
+            reason: "Still a fixture".to_string(),
+            expires_at: None,
+            ..args.clone()
+        };
+        add_exception(&registry_path, &updated, created + Duration::days(1)).expect("update");
+
+        let registry = Registry::load(&registry_path).expect("registry");
+        assert_eq!(registry.exceptions.len(), 1);
+        let entry = &registry.exceptions[0];
+        assert_eq!(entry.reason, "Still a fixture");
+        assert_eq!(entry.expires_at, None);
+        assert_eq!(
+            entry.created_at.map(|at| at.timestamp()),
+            Some(created.timestamp())
+        );
+    }
+
+    #[test]
+    fn add_exception_rejects_invalid_arguments() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let registry_path = workspace.path().join("ops/exceptions.toml");
+
+        let error = add_exception(
+            &registry_path,
+            &AddExceptionArgs {
+                finding_id: "not-an-id".to_string(),
+                reason: "reason".to_string(),
+                expires_at: None,
+            },
+            Utc::now(),
+        )
+        .expect_err("invalid id");
+        assert!(error.to_string().contains("fx_"));
+
+        let error = add_exception(
+            &registry_path,
+            &AddExceptionArgs {
+                finding_id: finding_id("a"),
+                reason: " ".to_string(),
+                expires_at: None,
+            },
+            Utc::now(),
+        )
+        .expect_err("empty reason");
+        assert!(error.to_string().contains("reason"));
+        assert!(!registry_path.exists());
+    }
+
+    #[test]
+    fn mask_text_masks_findings_with_configured_placeholder() {
+        let mut config = Config::default();
+        config.masking.placeholder = "[MASKED]".to_string();
+        let rules = try_get_all_rules(&config, Vec::new()).expect("rules");
+        let text = "first = test@example.com\nclean\nsecond = other@example.com";
+
+        let (masked, count) =
+            mask_text(text, Path::new("contacts.txt"), &rules, &config).expect("masked");
+        assert_eq!(count, 2);
+        assert_eq!(masked, "first = [MASKED]\nclean\nsecond = [MASKED]");
+
+        config.output.mask_mode = Some(MaskMode::Partial);
+        let (masked, _) =
+            mask_text(text, Path::new("contacts.txt"), &rules, &config).expect("masked");
+        assert!(masked.starts_with("first = ****.com\n"), "{masked}");
+
+        assert!(mask_text("clean\n", Path::new("clean.txt"), &rules, &config).is_none());
+    }
+
+    #[test]
+    fn mask_file_edit_replaces_the_whole_document() {
+        let uri = Url::parse("file:///tmp/contacts.txt").expect("uri");
+        let original = "🙂 test@example.com\nlast";
+
+        let edit = mask_file_edit(&uri, original, "masked".to_string());
+
+        let edits = &edit.changes.expect("changes")[&uri];
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range.start, Position::new(0, 0));
+        assert_eq!(edits[0].range.end, Position::new(1, 4));
+        assert_eq!(edits[0].new_text, "masked");
+    }
+}
@@ -1,27 +1,43 @@
 use serde_json::json;
 use tower_lsp::lsp_types::{
-    Diagnostic, DiagnosticSeverity, NumberOrString, Position as LspPosition, Range as LspRange,
+    CodeDescription, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location,
+    NumberOrString, Position as LspPosition, Range as LspRange, Url,
 };
-use veil_core::model::{Finding, Range, Severity};
+use veil_core::model::{Finding, Range, Remediation, Rule, Severity};
 use veil_core::rules::grade::Grade;
 use veil_core::RULE_ID_MAX_FILE_SIZE;
 
-pub fn findings_to_diagnostics(findings: &[Finding]) -> Vec<Diagnostic> {
-    findings.iter().map(finding_to_diagnostic).collect()
+pub fn findings_to_diagnostics(findings: &[Finding], rules: &[Rule]) -> Vec<Diagnostic> {
+    findings
+        .iter()
+        .map(|finding| {
+            let remediation = rules
+                .iter()
+                .find(|rule| rule.id == finding.rule_id)
+                .and_then(|rule| rule.remediation.as_ref());
+            finding_to_diagnostic(finding, remediation)
+        })
+        .collect()
 }
 
-pub fn finding_to_diagnostic(finding: &Finding) -> Diagnostic {
+pub fn finding_to_diagnostic(finding: &Finding, remediation: Option<&Remediation>) -> Diagnostic {
+    let range = range_to_lsp(finding.utf16_range);
     Diagnostic {
-        range: range_to_lsp(finding.utf16_range),
+        range,
         severity: Some(severity_to_lsp(&finding.severity)),
         code: Some(NumberOrString::String(finding.rule_id.clone())),
-        code_description: None,
+        code_description: remediation
+            .and_then(|remediation| remediation.revoke_url.as_deref())
+            .and_then(|url| Url::parse(url).ok())
+            .map(|href| CodeDescription { href }),
         source: Some("veil".to_string()),
         message: format!(
             "Sensitive data detected by {} (grade {}, score {})",
             finding.rule_id, finding.grade, finding.score
         ),
-        related_information: None,
+        related_information: remediation
+            .zip(Url::from_file_path(&finding.path).ok())
+            .map(|(remediation, uri)| remediation_related_information(remediation, uri, range)),
         tags: None,
         data: Some(json!({
             "ruleId": finding.rule_id,
@@ -29,10 +45,43 @@ pub fn finding_to_diagnostic(finding: &Finding) -> Diagnostic {
             "grade": finding.grade.to_string(),
             "maskedSnippet": finding.masked_snippet,
             "actions": ["mask", "partial_mask", "ignore"],
+            "remediation": remediation,
         })),
     }
 }
 
+/// One entry per remediation hint, all anchored at the finding itself.
+fn remediation_related_information(
+    remediation: &Remediation,
+    uri: Url,
+    range: LspRange,
+) -> Vec<DiagnosticRelatedInformation> {
+    let mut messages = Vec::new();
+    match (&remediation.provider, &remediation.revoke_url) {
+        (Some(provider), Some(url)) => messages.push(format!("Revoke at {provider}: {url}")),
+        (None, Some(url)) => messages.push(format!("Revoke at {url}")),
+        (Some(provider), None) => messages.push(format!("Issued by {provider}")),
+        (None, None) => {}
+    }
+    for (index, step) in remediation.rotation_steps.iter().enumerate() {
+        messages.push(format!("Rotation step {}: {}", index + 1, step));
+    }
+    if remediation.history_rewrite {
+        messages.push("History rewrite required after rotation".to_string());
+    }
+
+    messages
+        .into_iter()
+        .map(|message| DiagnosticRelatedInformation {
+            location: Location {
+                uri: uri.clone(),
+                range,
+            },
+            message,
+        })
+        .collect()
+}
+
 pub fn max_file_size_diagnostic(file_size_bytes: u64, max_size_bytes: u64) -> Diagnostic {
     Diagnostic {
         range: LspRange::default(),
@@ -92,6 +141,7 @@ mod tests {
         Finding {
             path: PathBuf::from("fixture.txt"),
             line_number: 3,
+            end_line_number: None,
             line_content: "token = raw-secret-value".to_string(),
             rule_id: "secret.test".to_string(),
             matched_content: "raw-secret-value".to_string(),
@@ -118,13 +168,14 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         }
     }
 
     #[test]
     fn diagnostic_range_uses_utf16_range() {
         let finding = finding_with(Severity::High);
-        let diagnostic = finding_to_diagnostic(&finding);
+        let diagnostic = finding_to_diagnostic(&finding, None);
 
         assert_eq!(
             diagnostic.range,
@@ -161,7 +212,7 @@ mod tests {
     #[test]
     fn diagnostic_data_contains_safe_fields_only() {
         let finding = finding_with(Severity::Critical);
-        let diagnostic = finding_to_diagnostic(&finding);
+        let diagnostic = finding_to_diagnostic(&finding, None);
         let data = diagnostic.data.expect("diagnostic data");
         let data_text = data.to_string();
 
@@ -202,7 +253,7 @@ mod tests {
     #[test]
     fn diagnostics_preserve_finding_order() {
         let findings = vec![finding_with(Severity::Low), finding_with(Severity::High)];
-        let diagnostics = findings_to_diagnostics(&findings);
+        let diagnostics = findings_to_diagnostics(&findings, &[]);
 
         assert_eq!(diagnostics.len(), 2);
         assert_eq!(
@@ -212,6 +263,45 @@ mod tests {
         assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::ERROR));
     }
 
+    #[test]
+    fn remediation_is_attached_as_related_information() {
+        let mut finding = finding_with(Severity::High);
+        finding.path = std::env::temp_dir().join("fixture.env");
+        let remediation = Remediation {
+            provider: Some("GitHub".to_string()),
+            revoke_url: Some("https://github.com/settings/tokens".to_string()),
+            rotation_steps: vec!["Delete the token".to_string()],
+            history_rewrite: true,
+        };
+        let diagnostic = finding_to_diagnostic(&finding, Some(&remediation));
+
+        assert_eq!(
+            diagnostic
+                .code_description
+                .map(|description| description.href),
+            Some(Url::parse("https://github.com/settings/tokens").unwrap())
+        );
+        let related = diagnostic.related_information.expect("related information");
+        let messages: Vec<&str> = related.iter().map(|info| info.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Revoke at GitHub: https://github.com/settings/tokens",
+                "Rotation step 1: Delete the token",
+                "History rewrite required after rotation",
+            ]
+        );
+        assert_eq!(related[0].location.range, diagnostic.range);
+        assert_eq!(
+            related[0].location.uri,
+            Url::from_file_path(&finding.path).unwrap()
+        );
+        assert_eq!(
+            diagnostic.data.unwrap()["remediation"]["provider"].as_str(),
+            Some("GitHub")
+        );
+    }
+
     #[test]
     fn max_file_size_diagnostic_is_safe_and_explicitly_skipped() {
         let diagnostic = max_file_size_diagnostic(1_500_000, 1_000_000);
@@ -62,6 +62,10 @@ impl DocumentStore {
         self.documents.get(uri).cloned()
     }
 
+    pub fn documents(&self) -> Vec<DocumentState> {
+        self.documents.values().cloned().collect()
+    }
+
     pub fn has_revision(&self, uri: &Url, scan_revision: u64) -> bool {
         self.documents
             .get(uri)
@@ -138,6 +142,15 @@ pub fn line_byte_bounds(text: &str, target_line: u32) -> Option<(usize, usize)>
     (line == target_line).then_some((line_start, text.len()))
 }
 
+/// Position just past the last character of `text`.
+pub fn end_position(text: &str) -> LspPosition {
+    let last_line_start = text.rfind('\n').map_or(0, |index| index + 1);
+    LspPosition {
+        line: text.matches('\n').count() as u32,
+        character: text[last_line_start..].encode_utf16().count() as u32,
+    }
+}
+
 fn byte_index_for_position(text: &str, position: LspPosition) -> Option<usize> {
     let (line_start, line_end) = line_byte_bounds(text, position.line)?;
     let line = &text[line_start..line_end];
@@ -0,0 +1,164 @@
+use tower_lsp::lsp_types::{
+    Diagnostic, Hover, HoverContents, MarkupContent, MarkupKind, NumberOrString, Position,
+};
+use veil_core::explain::{explain_rule, render_remediation, Markup};
+use veil_core::model::Rule;
+
+/// Hover for the findings under `position`, explaining each rule the way
+/// `veil rules explain` does plus the grade and score of the finding itself.
+pub fn hover_for_position(
+    diagnostics: &[Diagnostic],
+    position: Position,
+    rules: &[Rule],
+) -> Option<Hover> {
+    let matching: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            diagnostic.source.as_deref() == Some("veil")
+                && diagnostic.range.start <= position
+                && position <= diagnostic.range.end
+        })
+        .collect();
+    let range = matching.first()?.range;
+
+    let sections: Vec<String> = matching
+        .into_iter()
+        .map(|diagnostic| {
+            let rule_id = match diagnostic.code.as_ref() {
+                Some(NumberOrString::String(code)) => code.as_str(),
+                _ => "",
+            };
+            match rules.iter().find(|rule| rule.id == rule_id) {
+                Some(rule) => explain_markdown(rule, diagnostic),
+                None => diagnostic.message.clone(),
+            }
+        })
+        .collect();
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: sections.join("\n\n---\n\n"),
+        }),
+        range: Some(range),
+    })
+}
+
+fn explain_markdown(rule: &Rule, diagnostic: &Diagnostic) -> String {
+    let mut out = explain_rule(rule, Markup::Markdown);
+    if let Some(data) = &diagnostic.data {
+        if let (Some(grade), Some(score)) = (data["grade"].as_str(), data["score"].as_u64()) {
+            out.push_str(&format!("\n- Finding: grade {grade}, score {score}"));
+        }
+    }
+    if let Some(remediation) = &rule.remediation {
+        out.push_str("\n\n**Remediation**\n\n");
+        out.push_str(&render_remediation(remediation, Markup::Markdown));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{finding_to_diagnostic, max_file_size_diagnostic};
+    use std::path::PathBuf;
+    use tower_lsp::lsp_types::Range as LspRange;
+    use veil_config::Config;
+    use veil_core::model::{
+        Finding, FindingSpan, Position as CorePosition, Range as CoreRange, Remediation,
+    };
+    use veil_core::rules::grade::Grade;
+    use veil_core::try_get_all_rules;
+
+    fn diagnostic_for(rule: &Rule) -> Diagnostic {
+        let finding = Finding {
+            path: PathBuf::from("/tmp/fixture.txt"),
+            line_number: 1,
+            end_line_number: None,
+            line_content: "token = raw-secret-value".to_string(),
+            rule_id: rule.id.clone(),
+            matched_content: "raw-secret-value".to_string(),
+            masked_snippet: "token = <REDACTED>".to_string(),
+            severity: rule.severity.clone(),
+            score: 92,
+            grade: Grade::Critical,
+            span: FindingSpan {
+                byte_start: 8,
+                byte_end: 24,
+            },
+            utf16_range: CoreRange {
+                start: CorePosition {
+                    line: 0,
+                    character: 8,
+                },
+                end: CorePosition {
+                    line: 0,
+                    character: 24,
+                },
+            },
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            commit_sha: None,
+            author: None,
+            date: None,
+            verification: None,
+        };
+        finding_to_diagnostic(&finding, rule.remediation.as_ref())
+    }
+
+    fn text_of(hover: &Hover) -> &str {
+        match &hover.contents {
+            HoverContents::Markup(markup) => &markup.value,
+            other => panic!("unexpected hover contents: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn hover_explains_rule_and_remediation_under_cursor() {
+        let config = Config::default();
+        let mut rules = try_get_all_rules(&config, Vec::new()).expect("rules");
+        let rule = rules
+            .iter_mut()
+            .find(|rule| rule.id == "pii.net.email")
+            .expect("email rule");
+        rule.remediation = Some(Remediation {
+            provider: Some("Example".to_string()),
+            revoke_url: Some("https://example.com/revoke".to_string()),
+            rotation_steps: vec!["Issue a new token".to_string()],
+            history_rewrite: true,
+        });
+        let rule = rule.clone();
+        let diagnostics = vec![diagnostic_for(&rule)];
+
+        let hover = hover_for_position(&diagnostics, Position::new(0, 10), &rules)
+            .expect("hover inside finding");
+        let text = text_of(&hover);
+        assert!(text.starts_with("**pii.net.email**"), "{text}");
+        assert!(text.contains(&rule.description));
+        assert!(text.contains(&format!("- Severity: {}", rule.severity)));
+        assert!(text.contains(&format!("- Score: {}", rule.score)));
+        assert!(
+            text.contains("- Finding: grade CRITICAL, score 92"),
+            "{text}"
+        );
+        assert!(text.contains("- **Revoke**: <https://example.com/revoke>"));
+        assert!(text.contains("1. Issue a new token"));
+        assert!(text.contains("History rewrite required"));
+        assert!(!text.contains("raw-secret-value"));
+        assert_eq!(hover.range, Some(diagnostics[0].range));
+
+        assert!(hover_for_position(&diagnostics, Position::new(0, 3), &rules).is_none());
+        assert!(hover_for_position(&diagnostics, Position::new(1, 10), &rules).is_none());
+    }
+
+    #[test]
+    fn hover_falls_back_to_message_for_ruleless_diagnostics() {
+        let diagnostics = vec![max_file_size_diagnostic(10, 8)];
+
+        let hover = hover_for_position(&diagnostics, Position::new(0, 0), &[])
+            .expect("hover on skip diagnostic");
+        assert_eq!(text_of(&hover), diagnostics[0].message);
+        assert_eq!(hover.range, Some(LspRange::default()));
+    }
+}
@@ -1,6 +1,11 @@
+pub mod acceptance;
 pub mod code_actions;
+pub mod commands;
 pub mod config;
 pub mod diagnostics;
 pub mod document_store;
+pub mod hover;
+pub mod pull;
 pub mod range_map;
 pub mod server;
+pub mod watch;
@@ -1,4 +1,9 @@
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    veil_lsp::server::run_stdio().await
+fn main() -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    let result = runtime.block_on(veil_lsp::server::run_stdio());
+    // Don't wait for a workspace scan that is still winding down.
+    runtime.shutdown_timeout(std::time::Duration::from_secs(1));
+    result
 }
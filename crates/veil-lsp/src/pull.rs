@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use tower_lsp::lsp_types::{
+    Diagnostic, DocumentDiagnosticReport, FullDocumentDiagnosticReport, PreviousResultId,
+    RelatedFullDocumentDiagnosticReport, RelatedUnchangedDocumentDiagnosticReport,
+    UnchangedDocumentDiagnosticReport, Url, WorkspaceDiagnosticReport,
+    WorkspaceDocumentDiagnosticReport, WorkspaceFullDocumentDiagnosticReport,
+    WorkspaceUnchangedDocumentDiagnosticReport,
+};
+
+/// Result id of a pull report: a digest of the diagnostics themselves, so a
+/// matching `previousResultId` means the client already shows exactly these
+/// diagnostics, whatever changed in between.
+pub fn result_id(diagnostics: &[Diagnostic]) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    serde_json::to_string(diagnostics)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// `textDocument/diagnostic` report, `unchanged` when the client's previous
+/// result is still current.
+pub fn document_report(
+    diagnostics: Vec<Diagnostic>,
+    previous_result_id: Option<&str>,
+) -> DocumentDiagnosticReport {
+    let result_id = result_id(&diagnostics);
+    if previous_result_id == Some(result_id.as_str()) {
+        return DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+            related_documents: None,
+            unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport { result_id },
+        });
+    }
+
+    DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+        related_documents: None,
+        full_document_diagnostic_report: FullDocumentDiagnosticReport {
+            result_id: Some(result_id),
+            items: diagnostics,
+        },
+    })
+}
+
+/// `workspace/diagnostic` report for unopened files. Files the client still
+/// has results for but that no longer have findings get an empty report so
+/// their diagnostics are cleared.
+pub fn workspace_report(
+    files: Vec<(Url, Vec<Diagnostic>)>,
+    previous_result_ids: &[PreviousResultId],
+) -> WorkspaceDiagnosticReport {
+    let previous: HashMap<&Url, &str> = previous_result_ids
+        .iter()
+        .map(|previous| (&previous.uri, previous.value.as_str()))
+        .collect();
+    let cleared: Vec<(Url, Vec<Diagnostic>)> = previous_result_ids
+        .iter()
+        .filter(|previous| !files.iter().any(|(uri, _)| uri == &previous.uri))
+        .map(|previous| (previous.uri.clone(), Vec::new()))
+        .collect();
+
+    let items = files
+        .into_iter()
+        .chain(cleared)
+        .map(|(uri, diagnostics)| {
+            let result_id = result_id(&diagnostics);
+            if previous.get(&uri) == Some(&result_id.as_str()) {
+                return WorkspaceDocumentDiagnosticReport::Unchanged(
+                    WorkspaceUnchangedDocumentDiagnosticReport {
+                        uri,
+                        version: None,
+                        unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                            result_id,
+                        },
+                    },
+                );
+            }
+            WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: Some(result_id),
+                    items: diagnostics,
+                },
+            })
+        })
+        .collect();
+
+    WorkspaceDiagnosticReport { items }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::max_file_size_diagnostic;
+
+    fn uri(name: &str) -> Url {
+        Url::parse(&format!("file:///workspace/{name}")).expect("uri")
+    }
+
+    #[test]
+    fn document_report_is_unchanged_for_the_same_diagnostics() {
+        let diagnostics = vec![max_file_size_diagnostic(10, 8)];
+
+        let DocumentDiagnosticReport::Full(full) = document_report(diagnostics.clone(), None)
+        else {
+            panic!("first pull must be a full report");
+        };
+        let first_id = full
+            .full_document_diagnostic_report
+            .result_id
+            .expect("result id");
+        assert_eq!(full.full_document_diagnostic_report.items, diagnostics);
+
+        assert_eq!(
+            document_report(diagnostics, Some(&first_id)),
+            DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                    result_id: first_id.clone(),
+                },
+            })
+        );
+
+        let changed = vec![max_file_size_diagnostic(12, 8)];
+        assert!(matches!(
+            document_report(changed, Some(&first_id)),
+            DocumentDiagnosticReport::Full(_)
+        ));
+    }
+
+    #[test]
+    fn workspace_report_marks_unchanged_and_clears_fixed_files() {
+        let diagnostics = vec![max_file_size_diagnostic(10, 8)];
+        let previous = vec![
+            PreviousResultId {
+                uri: uri("same.txt"),
+                value: result_id(&diagnostics),
+            },
+            PreviousResultId {
+                uri: uri("fixed.txt"),
+                value: result_id(&diagnostics),
+            },
+        ];
+
+        let report = workspace_report(
+            vec![
+                (uri("same.txt"), diagnostics.clone()),
+                (uri("new.txt"), diagnostics.clone()),
+            ],
+            &previous,
+        );
+
+        assert_eq!(report.items.len(), 3);
+        assert!(matches!(
+            &report.items[0],
+            WorkspaceDocumentDiagnosticReport::Unchanged(unchanged) if unchanged.uri == uri("same.txt")
+        ));
+        assert!(matches!(
+            &report.items[1],
+            WorkspaceDocumentDiagnosticReport::Full(full)
+                if full.uri == uri("new.txt")
+                    && full.full_document_diagnostic_report.items == diagnostics
+        ));
+        assert!(matches!(
+            &report.items[2],
+            WorkspaceDocumentDiagnosticReport::Full(full)
+                if full.uri == uri("fixed.txt")
+                    && full.full_document_diagnostic_report.items.is_empty()
+        ));
+    }
+}
@@ -1,55 +1,164 @@
 pub const SERVER_NAME: &str = "veil-lsp";
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::acceptance::Acceptance;
 use crate::code_actions::code_actions;
-use crate::diagnostics::{findings_to_diagnostics, max_file_size_diagnostic};
+use crate::commands::{
+    add_exception, mask_file_edit, mask_text, AddExceptionArgs, MaskFileArgs, ADD_EXCEPTION,
+    MASK_FILE, SCAN_WORKSPACE,
+};
+use crate::diagnostics::max_file_size_diagnostic;
 use crate::document_store::{DocumentState, DocumentStore};
+use crate::hover::hover_for_position;
+use crate::pull::{document_report, workspace_report};
+use crate::watch::{
+    file_watchers, WatchSupport, WATCHED_FILES_METHOD, WATCHED_FILES_REGISTRATION_ID,
+};
 use anyhow::Result;
-use tower_lsp::jsonrpc::Result as LspResult;
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tower_lsp::jsonrpc::{Error as LspError, Result as LspResult};
 use tower_lsp::lsp_types::{
     CodeActionParams, CodeActionProviderCapability, CodeActionResponse, Diagnostic,
-    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    InitializeParams, InitializeResult, InitializedParams, MessageType, ServerCapabilities,
-    ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    DiagnosticOptions, DiagnosticServerCapabilities, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidChangeWatchedFilesRegistrationOptions,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentDiagnosticParams,
+    DocumentDiagnosticReportResult, ExecuteCommandOptions, ExecuteCommandParams, Hover,
+    HoverParams, HoverProviderCapability, InitializeParams, InitializeResult, InitializedParams,
+    MessageType, Registration, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Unregistration, Url, WorkspaceDiagnosticParams,
+    WorkspaceDiagnosticReportResult,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 use veil_config::Config;
-use veil_core::{scan_content, try_get_all_rules, Rule, DEFAULT_MAX_FILE_SIZE_BYTES};
+use veil_core::{
+    scan_content, scan_path_cancellable, try_get_all_rules, Finding, PathFilter, Rule,
+    DEFAULT_MAX_FILE_SIZE_BYTES,
+};
 
 const CHANGE_SCAN_DEBOUNCE: Duration = Duration::from_millis(200);
 
+/// Produces the effective config; called at start-up and again whenever a
+/// watched config, baseline or rule-pack file changes.
+pub type ConfigLoader = Arc<dyn Fn() -> Result<LoadedConfig> + Send + Sync>;
+
+/// The effective config and the config files it was merged from.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedConfig {
+    pub config: Config,
+    /// Every file the loader read (`--config`, user and org layers, ...);
+    /// each one is watched so editing it reloads the server.
+    pub config_files: Vec<PathBuf>,
+}
+
+impl From<Config> for LoadedConfig {
+    fn from(config: Config) -> Self {
+        Self {
+            config,
+            config_files: Vec::new(),
+        }
+    }
+}
+
+/// Config, the rules loaded from it and the accepted findings, swapped
+/// together on reload so a scan never mixes generations.
+#[derive(Clone)]
+struct ScanState {
+    config: Arc<Config>,
+    config_files: Arc<Vec<PathBuf>>,
+    rules: Arc<Vec<Rule>>,
+    acceptance: Arc<Acceptance>,
+}
+
+/// `initializationOptions` understood by the server.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct InitializationOptions {
+    /// Drop findings accepted by the baseline or the exception registry
+    /// instead of showing them as hints.
+    hide_accepted_findings: bool,
+}
+
 pub struct Backend {
     client: Client,
     documents: Arc<tokio::sync::Mutex<DocumentStore>>,
     pending_scans: Arc<tokio::sync::Mutex<PendingScanMap>>,
-    config: Arc<Config>,
-    rules: Arc<Vec<Rule>>,
+    /// Last diagnostics published per document, open or not; hovers are
+    /// answered from here instead of rescanning.
+    published: Arc<tokio::sync::Mutex<PublishedDiagnostics>>,
+    workspace_roots: Arc<tokio::sync::Mutex<Vec<PathBuf>>>,
+    workspace_scan: Arc<tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    /// Cancellation flag of the running workspace scan, push or pull. The
+    /// walk runs on the blocking pool, which aborting its task cannot stop.
+    workspace_scan_cancel: Arc<tokio::sync::Mutex<Arc<AtomicBool>>>,
+    watch_support: Arc<tokio::sync::Mutex<WatchSupport>>,
+    watchers_registered: Arc<tokio::sync::Mutex<bool>>,
+    hide_accepted: Arc<AtomicBool>,
+    /// The client pulls diagnostics (`textDocument/diagnostic`), so none are
+    /// pushed.
+    pull_diagnostics: Arc<AtomicBool>,
+    /// Bumped whenever diagnostics must be recomputed (reload, explicit
+    /// rescan); the cached workspace pull is valid for one generation.
+    diagnostics_generation: Arc<AtomicU64>,
+    workspace_pull: Arc<tokio::sync::Mutex<Option<WorkspacePull>>>,
+    config_loader: ConfigLoader,
+    state: Arc<tokio::sync::RwLock<ScanState>>,
 }
 
 impl Backend {
-    fn with_config_and_rules(client: Client, config: Arc<Config>, rules: Arc<Vec<Rule>>) -> Self {
+    fn with_state(client: Client, config_loader: ConfigLoader, state: ScanState) -> Self {
         Self {
             client,
             documents: Arc::new(tokio::sync::Mutex::new(DocumentStore::default())),
             pending_scans: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
-            config,
-            rules,
+            published: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            workspace_roots: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            workspace_scan: Arc::new(tokio::sync::Mutex::new(None)),
+            workspace_scan_cancel: Arc::new(tokio::sync::Mutex::new(Arc::default())),
+            watch_support: Arc::new(tokio::sync::Mutex::new(WatchSupport::default())),
+            watchers_registered: Arc::new(tokio::sync::Mutex::new(false)),
+            hide_accepted: Arc::new(AtomicBool::new(false)),
+            pull_diagnostics: Arc::new(AtomicBool::new(false)),
+            diagnostics_generation: Arc::new(AtomicU64::new(0)),
+            workspace_pull: Arc::new(tokio::sync::Mutex::new(None)),
+            config_loader,
+            state: Arc::new(tokio::sync::RwLock::new(state)),
         }
     }
 
+    async fn scan_state(&self) -> ScanState {
+        self.state.read().await.clone()
+    }
+
+    fn pull_diagnostics(&self) -> bool {
+        self.pull_diagnostics.load(Ordering::Relaxed)
+    }
+
     async fn schedule_document_diagnostics(&self, document: DocumentState, debounce: Duration) {
+        if self.pull_diagnostics() {
+            return;
+        }
+
         let uri = document.uri.clone();
         let task_uri = uri.clone();
         let scan_revision = document.scan_revision;
         let client = self.client.clone();
         let documents = Arc::clone(&self.documents);
         let pending_scans = Arc::clone(&self.pending_scans);
-        let config = Arc::clone(&self.config);
-        let rules = Arc::clone(&self.rules);
+        let published = Arc::clone(&self.published);
+        let ScanState {
+            config,
+            rules,
+            acceptance,
+            ..
+        } = self.scan_state().await;
+        let hide_accepted = self.hide_accepted.load(Ordering::Relaxed);
 
         let handle = tokio::spawn(async move {
             tokio::time::sleep(debounce).await;
@@ -64,7 +173,14 @@ impl Backend {
             let document_text = document.text.clone();
             let document_path = path_for_uri(&document.uri);
             let diagnostics = match tokio::task::spawn_blocking(move || {
-                diagnostics_for_text(&document_text, &document_path, &rules, &config)
+                document_diagnostics(
+                    &document_text,
+                    &document_path,
+                    &rules,
+                    &config,
+                    &acceptance,
+                    hide_accepted,
+                )
             })
             .await
             {
@@ -85,9 +201,14 @@ impl Backend {
                 .await
                 .is_some()
             {
-                client
-                    .publish_diagnostics(task_uri.clone(), diagnostics, Some(document.version))
-                    .await;
+                publish_diagnostics(
+                    &client,
+                    &published,
+                    task_uri.clone(),
+                    diagnostics,
+                    Some(document.version),
+                )
+                .await;
             }
 
             clear_pending_scan_if_current(&pending_scans, &task_uri, scan_revision).await;
@@ -102,13 +223,334 @@ impl Backend {
             pending_scan.handle.abort();
         }
     }
+
+    /// Stops the running workspace scan, if any, and returns the
+    /// cancellation flag for the next one.
+    async fn cancel_workspace_scan(&self) -> Arc<AtomicBool> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let previous = std::mem::replace(
+            &mut *self.workspace_scan_cancel.lock().await,
+            Arc::clone(&cancel),
+        );
+        previous.store(true, Ordering::Relaxed);
+        if let Some(previous_scan) = self.workspace_scan.lock().await.take() {
+            previous_scan.abort();
+        }
+        cancel
+    }
+
+    /// Publishes diagnostics for the files under the workspace roots that are
+    /// not open in the editor. Open documents keep their own, fresher scans.
+    async fn start_workspace_scan(&self) {
+        let roots = self.workspace_roots.lock().await.clone();
+        if roots.is_empty() {
+            return;
+        }
+        let cancel = self.cancel_workspace_scan().await;
+
+        let client = self.client.clone();
+        let documents = Arc::clone(&self.documents);
+        let published = Arc::clone(&self.published);
+        let state = self.scan_state().await;
+        let hide_accepted = self.hide_accepted.load(Ordering::Relaxed);
+
+        let handle = tokio::spawn(async move {
+            let Some(files) = scan_roots(&client, roots, state, hide_accepted, cancel).await else {
+                return;
+            };
+            let mut scanned = HashSet::new();
+            for (uri, diagnostics) in files {
+                if documents.lock().await.get(&uri).is_some() {
+                    continue;
+                }
+                scanned.insert(uri.clone());
+                publish_diagnostics(&client, &published, uri, diagnostics, None).await;
+            }
+
+            // Unopened files whose findings disappeared since the previous
+            // scan (e.g. after a config reload).
+            let stale: Vec<Url> = {
+                let published = published.lock().await;
+                let documents = documents.lock().await;
+                published
+                    .keys()
+                    .filter(|uri| !scanned.contains(*uri) && documents.get(uri).is_none())
+                    .cloned()
+                    .collect()
+            };
+            for uri in stale {
+                publish_diagnostics(&client, &published, uri, Vec::new(), None).await;
+            }
+
+            client
+                .log_message(
+                    MessageType::INFO,
+                    format!(
+                        "workspace scan finished: {} file(s) with findings",
+                        scanned.len()
+                    ),
+                )
+                .await;
+        });
+
+        *self.workspace_scan.lock().await = Some(handle);
+    }
+
+    /// (Re-)registers `workspace/didChangeWatchedFiles` for the current
+    /// config; the rule-pack and config-file watchers follow `rules_dir` and
+    /// the loaded config files across reloads.
+    async fn register_file_watchers(&self) {
+        let support = *self.watch_support.lock().await;
+        if !support.dynamic_registration {
+            return;
+        }
+
+        let mut registered = self.watchers_registered.lock().await;
+        if *registered {
+            let unregistration = Unregistration {
+                id: WATCHED_FILES_REGISTRATION_ID.to_string(),
+                method: WATCHED_FILES_METHOD.to_string(),
+            };
+            if let Err(error) = self
+                .client
+                .unregister_capability(vec![unregistration])
+                .await
+            {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("failed to unregister file watchers: {error}"),
+                    )
+                    .await;
+            }
+            *registered = false;
+        }
+
+        let ScanState {
+            config,
+            config_files,
+            ..
+        } = self.scan_state().await;
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: file_watchers(&config, &config_files, support),
+        };
+        let registration = Registration {
+            id: WATCHED_FILES_REGISTRATION_ID.to_string(),
+            method: WATCHED_FILES_METHOD.to_string(),
+            register_options: serde_json::to_value(options).ok(),
+        };
+        match self.client.register_capability(vec![registration]).await {
+            Ok(()) => *registered = true,
+            Err(error) => {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("failed to register file watchers: {error}"),
+                    )
+                    .await;
+            }
+        }
+    }
+
+    /// Reloads config and rules, keeping the previous ones if loading fails,
+    /// then rescans everything that was scanned with the old state.
+    async fn reload_config_and_rules(&self) {
+        let state = match load_scan_state(Arc::clone(&self.config_loader)).await {
+            Ok(state) => state,
+            Err(error) => {
+                self.client
+                    .show_message(
+                        MessageType::ERROR,
+                        format!(
+                            "veil: failed to reload configuration; keeping the previous one: {error:#}"
+                        ),
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        let rule_count = state.rules.len();
+        let watched_paths_changed = {
+            let mut current = self.state.write().await;
+            let changed = current.config.core.rules_dir != state.config.core.rules_dir
+                || current.config_files != state.config_files;
+            *current = state;
+            changed
+        };
+        self.client
+            .log_message(
+                MessageType::INFO,
+                format!("reloaded configuration ({rule_count} rules)"),
+            )
+            .await;
+
+        if watched_paths_changed {
+            self.register_file_watchers().await;
+        }
+        self.refresh_diagnostics().await;
+    }
+
+    /// Recomputes diagnostics with the current state: pull clients are asked
+    /// to re-request them, push clients get fresh scans of the open documents
+    /// and the workspace.
+    async fn refresh_diagnostics(&self) {
+        self.diagnostics_generation.fetch_add(1, Ordering::Relaxed);
+        if self.pull_diagnostics() {
+            if let Err(error) = self.client.workspace_diagnostic_refresh().await {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("failed to request a diagnostics refresh: {error}"),
+                    )
+                    .await;
+            }
+            return;
+        }
+
+        let open_documents = self.documents.lock().await.documents();
+        for document in open_documents {
+            self.schedule_document_diagnostics(document, Duration::ZERO)
+                .await;
+        }
+        self.start_workspace_scan().await;
+    }
+
+    /// Text of `uri` as the editor sees it: the open document, or the file on
+    /// disk.
+    async fn document_text(&self, uri: &Url) -> Option<String> {
+        if let Some(document) = self.documents.lock().await.get(uri) {
+            return Some(document.text);
+        }
+        let path = uri.to_file_path().ok()?;
+        tokio::task::spawn_blocking(move || std::fs::read_to_string(path).ok())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn run_add_exception(&self, arguments: Vec<Value>) -> LspResult<()> {
+        let args: AddExceptionArgs = arguments
+            .into_iter()
+            .next()
+            .and_then(|argument| serde_json::from_value(argument).ok())
+            .ok_or_else(|| {
+                LspError::invalid_params(format!(
+                    "{ADD_EXCEPTION} expects {{ findingId, reason, expiresAt? }}"
+                ))
+            })?;
+        let registry_path = self.scan_state().await.acceptance.registry_path();
+
+        let added = tokio::task::spawn_blocking(move || {
+            add_exception(&registry_path, &args, Utc::now()).map(|id| (id, args.expires_at))
+        })
+        .await
+        .map_err(|error| anyhow::anyhow!(error))
+        .and_then(|result| result);
+        match added {
+            Ok((id, expires_at)) => {
+                let until = expires_at
+                    .map(|expires_at| format!(" until {}", expires_at.format("%Y-%m-%d")))
+                    .unwrap_or_default();
+                self.client
+                    .show_message(
+                        MessageType::INFO,
+                        format!("veil: accepted {id}{until} in the exception registry"),
+                    )
+                    .await;
+                self.reload_config_and_rules().await;
+            }
+            Err(error) => {
+                self.client
+                    .show_message(
+                        MessageType::ERROR,
+                        format!("veil: failed to add exception: {error:#}"),
+                    )
+                    .await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Masks the findings of a document through `workspace/applyEdit`, so
+    /// unsaved changes of an open document are kept and the edit can be
+    /// undone in the editor.
+    async fn run_mask_file(&self, arguments: Vec<Value>) -> LspResult<Option<Value>> {
+        let args: MaskFileArgs = arguments
+            .into_iter()
+            .next()
+            .and_then(|argument| serde_json::from_value(argument).ok())
+            .ok_or_else(|| LspError::invalid_params(format!("{MASK_FILE} expects {{ uri }}")))?;
+        let Some(text) = self.document_text(&args.uri).await else {
+            return Err(LspError::invalid_params(format!(
+                "cannot read {}",
+                args.uri
+            )));
+        };
+
+        let ScanState { config, rules, .. } = self.scan_state().await;
+        let path = path_for_uri(&args.uri);
+        let original = text.clone();
+        let masked = tokio::task::spawn_blocking(move || mask_text(&text, &path, &rules, &config))
+            .await
+            .map_err(|_| LspError::internal_error())?;
+        let Some((masked, masked_count)) = masked else {
+            self.client
+                .show_message(
+                    MessageType::INFO,
+                    format!("veil: nothing to mask in {}", args.uri),
+                )
+                .await;
+            return Ok(Some(json!({ "masked": 0 })));
+        };
+
+        let edit = mask_file_edit(&args.uri, &original, masked);
+        match self.client.apply_edit(edit).await {
+            Ok(response) if response.applied => Ok(Some(json!({ "masked": masked_count }))),
+            Ok(response) => {
+                self.client
+                    .show_message(
+                        MessageType::WARNING,
+                        format!(
+                            "veil: the editor rejected the mask edit: {}",
+                            response.failure_reason.unwrap_or_default()
+                        ),
+                    )
+                    .await;
+                Ok(Some(json!({ "masked": 0 })))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn run_scan_workspace(&self) {
+        if self.workspace_roots.lock().await.is_empty() {
+            self.client
+                .show_message(MessageType::WARNING, "veil: no workspace folder to scan")
+                .await;
+            return;
+        }
+        self.refresh_diagnostics().await;
+    }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> LspResult<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> LspResult<InitializeResult> {
+        *self.workspace_roots.lock().await = workspace_roots(&params);
+        *self.watch_support.lock().await = watch_support(&params);
+        let pull_diagnostics = supports_pull_diagnostics(&params);
+        let options = params
+            .initialization_options
+            .and_then(|options| serde_json::from_value::<InitializationOptions>(options).ok())
+            .unwrap_or_default();
+        self.hide_accepted
+            .store(options.hide_accepted_findings, Ordering::Relaxed);
+        self.pull_diagnostics
+            .store(pull_diagnostics, Ordering::Relaxed);
+
         Ok(InitializeResult {
-            capabilities: server_capabilities(),
+            capabilities: server_capabilities(pull_diagnostics),
             server_info: Some(ServerInfo {
                 name: SERVER_NAME.to_string(),
                 version: Some(env!("CARGO_PKG_VERSION").to_string()),
@@ -120,6 +562,14 @@ impl LanguageServer for Backend {
         self.client
             .log_message(MessageType::INFO, "veil-lsp initialized")
             .await;
+        self.register_file_watchers().await;
+        if !self.pull_diagnostics() {
+            self.start_workspace_scan().await;
+        }
+    }
+
+    async fn did_change_watched_files(&self, _: DidChangeWatchedFilesParams) {
+        self.reload_config_and_rules().await;
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
@@ -174,7 +624,106 @@ impl LanguageServer for Backend {
             documents.close(&uri);
         }
 
-        self.client.publish_diagnostics(uri, Vec::new(), None).await;
+        if self.pull_diagnostics() {
+            self.published.lock().await.remove(&uri);
+            return;
+        }
+        publish_diagnostics(&self.client, &self.published, uri, Vec::new(), None).await;
+    }
+
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> LspResult<DocumentDiagnosticReportResult> {
+        let uri = params.text_document.uri;
+        let text = self.document_text(&uri).await.unwrap_or_default();
+        let ScanState {
+            config,
+            rules,
+            acceptance,
+            ..
+        } = self.scan_state().await;
+        let hide_accepted = self.hide_accepted.load(Ordering::Relaxed);
+        let path = path_for_uri(&uri);
+        let diagnostics = tokio::task::spawn_blocking(move || {
+            document_diagnostics(&text, &path, &rules, &config, &acceptance, hide_accepted)
+        })
+        .await
+        .map_err(|_| LspError::internal_error())?;
+
+        // Hovers are answered from the cache in pull mode as well.
+        {
+            let mut published = self.published.lock().await;
+            if diagnostics.is_empty() {
+                published.remove(&uri);
+            } else {
+                published.insert(uri, diagnostics.clone());
+            }
+        }
+
+        Ok(DocumentDiagnosticReportResult::Report(document_report(
+            diagnostics,
+            params.previous_result_id.as_deref(),
+        )))
+    }
+
+    /// Reports unopened files of the workspace roots; open documents are
+    /// pulled one by one. The scan is cached until the next refresh, since
+    /// clients re-request workspace diagnostics continuously.
+    async fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> LspResult<WorkspaceDiagnosticReportResult> {
+        let generation = self.diagnostics_generation.load(Ordering::Relaxed);
+        let files = {
+            let mut workspace_pull = self.workspace_pull.lock().await;
+            match workspace_pull.as_ref() {
+                Some(pull) if pull.generation == generation => pull.files.clone(),
+                _ => {
+                    let roots = self.workspace_roots.lock().await.clone();
+                    let state = self.scan_state().await;
+                    let hide_accepted = self.hide_accepted.load(Ordering::Relaxed);
+                    let cancel = self.cancel_workspace_scan().await;
+                    let Some(files) =
+                        scan_roots(&self.client, roots, state, hide_accepted, cancel).await
+                    else {
+                        return Err(LspError::request_cancelled());
+                    };
+                    *workspace_pull = Some(WorkspacePull {
+                        generation,
+                        files: files.clone(),
+                    });
+                    files
+                }
+            }
+        };
+
+        let documents = self.documents.lock().await;
+        let files = files
+            .into_iter()
+            .filter(|(uri, _)| documents.get(uri).is_none())
+            .collect();
+        let previous_result_ids: Vec<_> = params
+            .previous_result_ids
+            .into_iter()
+            .filter(|previous| documents.get(&previous.uri).is_none())
+            .collect();
+
+        Ok(WorkspaceDiagnosticReportResult::Report(workspace_report(
+            files,
+            &previous_result_ids,
+        )))
+    }
+
+    async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
+        let position = params.text_document_position_params;
+        let rules = self.scan_state().await.rules;
+        let published = self.published.lock().await;
+        let Some(diagnostics) = published.get(&position.text_document.uri) else {
+            return Ok(None);
+        };
+
+        Ok(hover_for_position(diagnostics, position.position, &rules))
     }
 
     async fn code_action(&self, params: CodeActionParams) -> LspResult<Option<CodeActionResponse>> {
@@ -200,7 +749,22 @@ impl LanguageServer for Backend {
         Ok(Some(actions))
     }
 
+    async fn execute_command(&self, params: ExecuteCommandParams) -> LspResult<Option<Value>> {
+        match params.command.as_str() {
+            ADD_EXCEPTION => self.run_add_exception(params.arguments).await?,
+            MASK_FILE => return self.run_mask_file(params.arguments).await,
+            SCAN_WORKSPACE => self.run_scan_workspace().await,
+            command => {
+                return Err(LspError::invalid_params(format!(
+                    "unknown command: {command}"
+                )))
+            }
+        }
+        Ok(None)
+    }
+
     async fn shutdown(&self) -> LspResult<()> {
+        self.cancel_workspace_scan().await;
         Ok(())
     }
 }
@@ -209,14 +773,18 @@ pub async fn run_stdio() -> Result<()> {
     run_stdio_with_config(Config::default()).await
 }
 
+/// Serves a fixed config; rule packs under its `rules_dir` are still
+/// reloaded when they change.
 pub async fn run_stdio_with_config(config: Config) -> Result<()> {
-    let remote_rules = remote_rules_for_config(&config).await?;
-    let rules = Arc::new(try_get_all_rules(&config, remote_rules)?);
-    let config = Arc::new(config);
+    run_stdio_with_config_loader(Arc::new(move || Ok(config.clone().into()))).await
+}
+
+pub async fn run_stdio_with_config_loader(config_loader: ConfigLoader) -> Result<()> {
+    let state = load_scan_state(Arc::clone(&config_loader)).await?;
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
     let (service, socket) = LspService::build(|client| {
-        Backend::with_config_and_rules(client, Arc::clone(&config), Arc::clone(&rules))
+        Backend::with_state(client, Arc::clone(&config_loader), state.clone())
     })
     .finish();
 
@@ -224,12 +792,31 @@ pub async fn run_stdio_with_config(config: Config) -> Result<()> {
     Ok(())
 }
 
-pub fn server_capabilities() -> ServerCapabilities {
+/// Capabilities for a client that pulls diagnostics, or that only receives
+/// `textDocument/publishDiagnostics`.
+pub fn server_capabilities(pull_diagnostics: bool) -> ServerCapabilities {
     ServerCapabilities {
         text_document_sync: Some(TextDocumentSyncCapability::Kind(
             TextDocumentSyncKind::INCREMENTAL,
         )),
         code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        execute_command_provider: Some(ExecuteCommandOptions {
+            commands: vec![
+                ADD_EXCEPTION.to_string(),
+                MASK_FILE.to_string(),
+                SCAN_WORKSPACE.to_string(),
+            ],
+            ..ExecuteCommandOptions::default()
+        }),
+        diagnostic_provider: pull_diagnostics.then(|| {
+            DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                identifier: Some("veil".to_string()),
+                inter_file_dependencies: false,
+                workspace_diagnostics: true,
+                ..DiagnosticOptions::default()
+            })
+        }),
         ..ServerCapabilities::default()
     }
 }
@@ -239,6 +826,19 @@ pub fn diagnostics_for_text(
     path: &Path,
     rules: &[Rule],
     config: &Config,
+) -> Vec<Diagnostic> {
+    document_diagnostics(text, path, rules, config, &Acceptance::default(), false)
+}
+
+/// [`diagnostics_for_text`] with findings accepted by the baseline or the
+/// exception registry shown as hints, or dropped when `hide_accepted`.
+pub fn document_diagnostics(
+    text: &str,
+    path: &Path,
+    rules: &[Rule],
+    config: &Config,
+    acceptance: &Acceptance,
+    hide_accepted: bool,
 ) -> Vec<Diagnostic> {
     if is_ignored_by_config(path, config) {
         return Vec::new();
@@ -253,16 +853,163 @@ pub fn diagnostics_for_text(
         return vec![max_file_size_diagnostic(file_size_bytes, max_size_bytes)];
     }
 
-    findings_to_diagnostics(&scan_content(text, path, rules, config))
+    acceptance.diagnostics(
+        scan_content(text, path, rules, config),
+        rules,
+        hide_accepted,
+        Utc::now(),
+    )
+}
+
+/// Diagnostics for every file under `root` with findings, walked the way
+/// `veil scan` walks it (`.gitignore`, built-in ignores and `[core] ignore`
+/// patterns anchored at the config's patterns root). The walk stops once
+/// `cancel` is set.
+pub fn workspace_diagnostics(
+    root: &Path,
+    rules: &[Rule],
+    config: &Config,
+    acceptance: &Acceptance,
+    hide_accepted: bool,
+    cancel: &AtomicBool,
+) -> Vec<(Url, Vec<Diagnostic>)> {
+    let now = Utc::now();
+    let mut findings_by_path: BTreeMap<PathBuf, Vec<Finding>> = BTreeMap::new();
+    for finding in scan_path_cancellable(root, rules, config, cancel).findings {
+        findings_by_path
+            .entry(finding.path.clone())
+            .or_default()
+            .push(finding);
+    }
+
+    findings_by_path
+        .into_iter()
+        // Archive members are reported under paths no editor can open.
+        .filter(|(path, _)| path.is_file())
+        .filter_map(|(path, findings)| {
+            let uri = Url::from_file_path(&path).ok()?;
+            let diagnostics = acceptance.diagnostics(findings, rules, hide_accepted, now);
+            (!diagnostics.is_empty()).then_some((uri, diagnostics))
+        })
+        .collect()
 }
 
+/// [`workspace_diagnostics`] of every root, each scanned on the blocking
+/// pool; a root whose scan fails is logged and skipped. `None` once `cancel`
+/// is set, since the results are then partial.
+async fn scan_roots(
+    client: &Client,
+    roots: Vec<PathBuf>,
+    state: ScanState,
+    hide_accepted: bool,
+    cancel: Arc<AtomicBool>,
+) -> Option<Vec<(Url, Vec<Diagnostic>)>> {
+    let mut files = Vec::new();
+    for root in roots {
+        let ScanState {
+            config,
+            rules,
+            acceptance,
+            ..
+        } = state.clone();
+        let scan_root = root.clone();
+        let scan_cancel = Arc::clone(&cancel);
+        match tokio::task::spawn_blocking(move || {
+            workspace_diagnostics(
+                &scan_root,
+                &rules,
+                &config,
+                &acceptance,
+                hide_accepted,
+                &scan_cancel,
+            )
+        })
+        .await
+        {
+            Ok(root_files) => files.extend(root_files),
+            Err(error) => {
+                client
+                    .log_message(
+                        MessageType::ERROR,
+                        format!("failed to scan workspace {}: {error}", root.display()),
+                    )
+                    .await;
+            }
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+    }
+    Some(files)
+}
+
+/// Workspace folders, or the legacy `rootUri` for clients without them.
+fn workspace_roots(params: &InitializeParams) -> Vec<PathBuf> {
+    #[allow(deprecated)]
+    let root_uri = params.root_uri.iter();
+    match &params.workspace_folders {
+        Some(folders) if !folders.is_empty() => folders
+            .iter()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect(),
+        _ => root_uri.filter_map(|uri| uri.to_file_path().ok()).collect(),
+    }
+}
+
+/// Whether the client accepts dynamic file-watcher registrations, and with
+/// relative patterns.
+fn watch_support(params: &InitializeParams) -> WatchSupport {
+    let watched_files = params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.did_change_watched_files.as_ref());
+    WatchSupport {
+        dynamic_registration: watched_files
+            .and_then(|capability| capability.dynamic_registration)
+            .unwrap_or(false),
+        relative_patterns: watched_files
+            .and_then(|capability| capability.relative_pattern_support)
+            .unwrap_or(false),
+    }
+}
+
+/// Whether the client supports `textDocument/diagnostic` pull requests.
+fn supports_pull_diagnostics(params: &InitializeParams) -> bool {
+    params
+        .capabilities
+        .text_document
+        .as_ref()
+        .is_some_and(|text_document| text_document.diagnostic.is_some())
+}
+
+/// Documents are matched against the same patterns root as workspace scans:
+/// the directory of the repo config `veil lsp` loaded.
 fn is_ignored_by_config(path: &Path, config: &Config) -> bool {
-    let path_str = path.to_string_lossy();
-    config
-        .core
-        .ignore
-        .iter()
-        .any(|pattern| path_str.contains(pattern))
+    let Ok(filter) = PathFilter::new(&config.core) else {
+        return false;
+    };
+    filter.is_excluded(&filter.relative(path), false)
+}
+
+async fn load_scan_state(config_loader: ConfigLoader) -> Result<ScanState> {
+    let LoadedConfig {
+        config,
+        config_files,
+    } = tokio::task::spawn_blocking(move || config_loader()).await??;
+    let remote_rules = remote_rules_for_config(&config).await?;
+    let rules = try_get_all_rules(&config, remote_rules)?;
+    // Relative to the working directory, like `veil.toml` and `[core] ignore`.
+    let acceptance = tokio::task::spawn_blocking(|| {
+        Acceptance::load(&std::env::current_dir().unwrap_or_default())
+    })
+    .await??;
+    Ok(ScanState {
+        config: Arc::new(config),
+        config_files: Arc::new(config_files),
+        rules: Arc::new(rules),
+        acceptance: Arc::new(acceptance),
+    })
 }
 
 async fn remote_rules_for_config(config: &Config) -> Result<Vec<Rule>> {
@@ -299,6 +1046,32 @@ fn path_for_uri(uri: &Url) -> PathBuf {
 }
 
 type PendingScanMap = HashMap<Url, PendingScan>;
+type PublishedDiagnostics = HashMap<Url, Vec<Diagnostic>>;
+
+/// Last `workspace/diagnostic` scan and the diagnostics generation it was
+/// computed for.
+struct WorkspacePull {
+    generation: u64,
+    files: Vec<(Url, Vec<Diagnostic>)>,
+}
+
+async fn publish_diagnostics(
+    client: &Client,
+    published: &tokio::sync::Mutex<PublishedDiagnostics>,
+    uri: Url,
+    diagnostics: Vec<Diagnostic>,
+    version: Option<i32>,
+) {
+    {
+        let mut published = published.lock().await;
+        if diagnostics.is_empty() {
+            published.remove(&uri);
+        } else {
+            published.insert(uri.clone(), diagnostics.clone());
+        }
+    }
+    client.publish_diagnostics(uri, diagnostics, version).await;
+}
 
 struct PendingScan {
     scan_revision: u64,
@@ -375,7 +1148,7 @@ mod tests {
 
     #[test]
     fn capabilities_advertise_incremental_text_sync() {
-        let capabilities = server_capabilities();
+        let capabilities = server_capabilities(false);
 
         assert_eq!(
             capabilities.text_document_sync,
@@ -388,6 +1161,54 @@ mod tests {
             capabilities.code_action_provider,
             Some(CodeActionProviderCapability::Simple(true))
         );
+        assert_eq!(
+            capabilities.hover_provider,
+            Some(HoverProviderCapability::Simple(true))
+        );
+        assert_eq!(
+            capabilities
+                .execute_command_provider
+                .expect("execute command provider")
+                .commands,
+            vec![
+                ADD_EXCEPTION.to_string(),
+                MASK_FILE.to_string(),
+                SCAN_WORKSPACE.to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn capabilities_advertise_pull_diagnostics_to_supporting_clients() {
+        let params: InitializeParams = serde_json::from_value(serde_json::json!({
+            "capabilities": {
+                "textDocument": { "diagnostic": { "relatedDocumentSupport": false } }
+            }
+        }))
+        .expect("params");
+        assert!(supports_pull_diagnostics(&params));
+        assert!(!supports_pull_diagnostics(&InitializeParams::default()));
+
+        let Some(DiagnosticServerCapabilities::Options(options)) =
+            server_capabilities(true).diagnostic_provider
+        else {
+            panic!("pull diagnostics must be advertised");
+        };
+        assert_eq!(options.identifier.as_deref(), Some("veil"));
+        assert!(options.workspace_diagnostics);
+        assert!(!options.inter_file_dependencies);
+    }
+
+    #[test]
+    fn initialization_options_default_to_showing_accepted_findings() {
+        let options: InitializationOptions =
+            serde_json::from_value(serde_json::json!({})).expect("options");
+        assert!(!options.hide_accepted_findings);
+
+        let options: InitializationOptions =
+            serde_json::from_value(serde_json::json!({ "hideAcceptedFindings": true }))
+                .expect("options");
+        assert!(options.hide_accepted_findings);
     }
 
     #[test]
@@ -465,6 +1286,121 @@ mod tests {
         );
 
         assert!(diagnostics.is_empty());
+
+        let diagnostics = diagnostics_for_text(
+            "contact test@example.com\n",
+            Path::new("src/regenerated.txt"),
+            &rules,
+            &config,
+        );
+        assert!(!diagnostics.is_empty());
+    }
+
+    #[test]
+    fn anchored_ignores_match_documents_and_workspace_scans_alike() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let root = workspace.path();
+        let generated = root.join("src/generated/out.txt");
+        std::fs::create_dir_all(generated.parent().expect("parent")).expect("generated dir");
+        std::fs::write(&generated, "contact test@example.com\n").expect("generated");
+        let mut config = Config::default();
+        config.core.ignore.push("/src/generated".to_string());
+        config.core.patterns_root = Some(root.to_path_buf());
+        let rules = try_get_all_rules(&config, Vec::new()).expect("rules");
+
+        assert!(
+            diagnostics_for_text("contact test@example.com\n", &generated, &rules, &config)
+                .is_empty()
+        );
+        for scan_root in [root.to_path_buf(), root.join("src")] {
+            let files = workspace_diagnostics(
+                &scan_root,
+                &rules,
+                &config,
+                &Acceptance::default(),
+                false,
+                &AtomicBool::new(false),
+            );
+            assert!(files.is_empty(), "{files:?}");
+        }
+    }
+
+    #[test]
+    fn workspace_diagnostics_cover_unopened_files_and_honor_ignores() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let root = workspace.path();
+        std::fs::create_dir_all(root.join("src/generated")).expect("src");
+        std::fs::create_dir_all(root.join("node_modules/dep")).expect("node_modules");
+        std::fs::write(root.join("src/app.txt"), "contact test@example.com\n").expect("app");
+        std::fs::write(root.join("src/clean.txt"), "nothing to see\n").expect("clean");
+        std::fs::write(
+            root.join("src/generated/out.txt"),
+            "contact test@example.com\n",
+        )
+        .expect("generated");
+        std::fs::write(
+            root.join("node_modules/dep/index.txt"),
+            "contact test@example.com\n",
+        )
+        .expect("dependency");
+
+        let mut config = Config::default();
+        config.core.ignore.push("generated".to_string());
+        let rules = try_get_all_rules(&config, Vec::new()).expect("rules");
+
+        let files = workspace_diagnostics(
+            root,
+            &rules,
+            &config,
+            &Acceptance::default(),
+            false,
+            &AtomicBool::new(false),
+        );
+
+        let expected_uri = Url::from_file_path(root.join("src/app.txt")).expect("uri");
+        assert_eq!(files.len(), 1, "{files:?}");
+        let (uri, diagnostics) = &files[0];
+        assert_eq!(uri, &expected_uri);
+        assert!(diagnostics.iter().any(|diagnostic| matches!(
+            diagnostic.code.as_ref(),
+            Some(NumberOrString::String(code)) if code == "pii.net.email"
+        )));
+        assert_eq!(diagnostics[0].range.start.character, 8);
+
+        let cancelled = workspace_diagnostics(
+            root,
+            &rules,
+            &config,
+            &Acceptance::default(),
+            false,
+            &AtomicBool::new(true),
+        );
+        assert!(cancelled.is_empty());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn workspace_roots_prefer_folders_over_root_uri() {
+        let folder = tempfile::tempdir().expect("tempdir");
+        let folder_uri = Url::from_directory_path(folder.path()).expect("folder uri");
+        let legacy_uri = Url::parse("file:///legacy").expect("legacy uri");
+
+        let params = InitializeParams {
+            root_uri: Some(legacy_uri.clone()),
+            workspace_folders: Some(vec![tower_lsp::lsp_types::WorkspaceFolder {
+                uri: folder_uri,
+                name: "folder".to_string(),
+            }]),
+            ..InitializeParams::default()
+        };
+        assert_eq!(workspace_roots(&params), vec![folder.path().to_path_buf()]);
+
+        let params = InitializeParams {
+            root_uri: Some(legacy_uri),
+            ..InitializeParams::default()
+        };
+        assert_eq!(workspace_roots(&params), vec![PathBuf::from("/legacy")]);
+        assert!(workspace_roots(&InitializeParams::default()).is_empty());
     }
 
     #[tokio::test]
@@ -498,6 +1434,82 @@ mod tests {
             .contains("Unknown validator 'unknown_validator'"));
     }
 
+    #[tokio::test]
+    async fn load_scan_state_reloads_rules_from_the_loader() {
+        let generation = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let loader_generation = Arc::clone(&generation);
+        let config_loader: ConfigLoader = Arc::new(move || {
+            let mut config = Config::default();
+            if loader_generation.fetch_add(1, std::sync::atomic::Ordering::SeqCst) > 0 {
+                config.rules.insert(
+                    "custom.reloaded".to_string(),
+                    RuleConfig {
+                        enabled: true,
+                        enabled_is_set: true,
+                        severity: None,
+                        pattern: Some("RELOADED".to_string()),
+                        score: None,
+                        category: None,
+                        tags: None,
+                        base_score: None,
+                        context_lines_before: None,
+                        context_lines_after: None,
+                        validator: None,
+                        description: None,
+                        placeholder: None,
+                    },
+                );
+            }
+            Ok(config.into())
+        });
+
+        let first = load_scan_state(Arc::clone(&config_loader))
+            .await
+            .expect("first load");
+        assert!(!first.rules.iter().any(|rule| rule.id == "custom.reloaded"));
+
+        let second = load_scan_state(config_loader).await.expect("reload");
+        assert!(second.rules.iter().any(|rule| rule.id == "custom.reloaded"));
+        assert!(second.config.rules.contains_key("custom.reloaded"));
+    }
+
+    #[tokio::test]
+    async fn load_scan_state_surfaces_loader_errors() {
+        let config_loader: ConfigLoader =
+            Arc::new(|| Err(anyhow::anyhow!("invalid veil.toml: expected `=`")));
+
+        let error = load_scan_state(config_loader).await.err().expect("error");
+
+        assert!(error.to_string().contains("invalid veil.toml"));
+    }
+
+    #[test]
+    fn watch_support_reads_client_capabilities() {
+        assert_eq!(
+            watch_support(&InitializeParams::default()),
+            WatchSupport::default()
+        );
+
+        let params: InitializeParams = serde_json::from_value(serde_json::json!({
+            "capabilities": {
+                "workspace": {
+                    "didChangeWatchedFiles": {
+                        "dynamicRegistration": true,
+                        "relativePatternSupport": true
+                    }
+                }
+            }
+        }))
+        .expect("params");
+        assert_eq!(
+            watch_support(&params),
+            WatchSupport {
+                dynamic_registration: true,
+                relative_patterns: true,
+            }
+        );
+    }
+
     #[test]
     fn change_scan_debounce_matches_design_window() {
         assert_eq!(CHANGE_SCAN_DEBOUNCE, Duration::from_millis(200));
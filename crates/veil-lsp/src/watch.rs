@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use tower_lsp::lsp_types::{FileSystemWatcher, GlobPattern, OneOf, RelativePattern, Url};
+use veil_config::Config;
+use veil_core::baseline::{COMPAT_BASELINE_FILE, DEFAULT_BASELINE_FILE};
+
+use crate::acceptance::REGISTRY_FILE;
+
+pub const WATCHED_FILES_REGISTRATION_ID: &str = "veil-watched-files";
+pub const WATCHED_FILES_METHOD: &str = "workspace/didChangeWatchedFiles";
+
+/// What the client lets us register, from its `initialize` capabilities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WatchSupport {
+    pub dynamic_registration: bool,
+    pub relative_patterns: bool,
+}
+
+/// Watchers for the files that feed the server's config and rules:
+/// `veil.toml`, the baseline, the exception registry, every file of the
+/// configured `rules_dir` and the config files the loader read.
+pub fn file_watchers(
+    config: &Config,
+    config_files: &[PathBuf],
+    support: WatchSupport,
+) -> Vec<FileSystemWatcher> {
+    let mut patterns: Vec<GlobPattern> = [
+        "veil.toml",
+        DEFAULT_BASELINE_FILE,
+        COMPAT_BASELINE_FILE,
+        REGISTRY_FILE,
+    ]
+    .into_iter()
+    .map(|file_name| GlobPattern::String(format!("**/{file_name}")))
+    .collect();
+
+    if let Some(rules_dir) = config.core.rules_dir.as_deref().map(absolute_path) {
+        patterns.push(glob_under(&rules_dir, "**/*", support));
+    }
+
+    // `--config other.toml` and the user/org layers usually live outside the
+    // workspace, where `**/veil.toml` does not reach.
+    for config_file in config_files {
+        let config_file = absolute_path(&config_file.to_string_lossy());
+        if let (Some(dir), Some(file_name)) = (config_file.parent(), config_file.file_name()) {
+            patterns.push(glob_under(dir, &file_name.to_string_lossy(), support));
+        }
+    }
+
+    patterns
+        .into_iter()
+        .map(|glob_pattern| FileSystemWatcher {
+            glob_pattern,
+            kind: None,
+        })
+        .collect()
+}
+
+/// `pattern` below `dir`, as a relative pattern when the client supports it.
+fn glob_under(dir: &Path, pattern: &str, support: WatchSupport) -> GlobPattern {
+    let relative = support
+        .relative_patterns
+        .then(|| Url::from_directory_path(dir).ok())
+        .flatten();
+    match relative {
+        Some(base_uri) => GlobPattern::Relative(RelativePattern {
+            base_uri: OneOf::Right(base_uri),
+            pattern: pattern.to_string(),
+        }),
+        None => GlobPattern::String(format!(
+            "{}/{pattern}",
+            dir.to_string_lossy().replace('\\', "/")
+        )),
+    }
+}
+
+/// `rules_dir` and config paths are resolved against the server's working directory, like the
+/// rest of the config.
+fn absolute_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    let cwd = std::env::current_dir().unwrap_or_default();
+    path.components()
+        .filter(|component| !matches!(component, std::path::Component::CurDir))
+        .fold(cwd, |resolved, component| resolved.join(component))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(watchers: &[FileSystemWatcher]) -> Vec<String> {
+        watchers
+            .iter()
+            .map(|watcher| match &watcher.glob_pattern {
+                GlobPattern::String(pattern) => pattern.clone(),
+                GlobPattern::Relative(relative) => {
+                    let base = match &relative.base_uri {
+                        OneOf::Left(folder) => folder.uri.to_string(),
+                        OneOf::Right(uri) => uri.to_string(),
+                    };
+                    format!("{base} {}", relative.pattern)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn watchers_cover_config_baseline_and_registry_files() {
+        let watchers = file_watchers(&Config::default(), &[], WatchSupport::default());
+
+        assert_eq!(
+            globs(&watchers),
+            vec![
+                "**/veil.toml",
+                "**/veil.baseline.json",
+                "**/.veil-baseline.json",
+                "**/ops/exceptions.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn watchers_cover_rules_dir() {
+        let mut config = Config::default();
+        config.core.rules_dir = Some("/opt/veil/rules".to_string());
+
+        let watchers = file_watchers(&config, &[], WatchSupport::default());
+        assert_eq!(globs(&watchers)[4], "/opt/veil/rules/**/*");
+
+        let watchers = file_watchers(
+            &config,
+            &[],
+            WatchSupport {
+                dynamic_registration: true,
+                relative_patterns: true,
+            },
+        );
+        assert_eq!(globs(&watchers)[4], "file:///opt/veil/rules/ **/*");
+
+        config.core.rules_dir = Some("./rules/log".to_string());
+        let watchers = file_watchers(&config, &[], WatchSupport::default());
+        let expected = std::env::current_dir()
+            .expect("cwd")
+            .join("rules")
+            .join("log");
+        assert_eq!(
+            globs(&watchers)[4],
+            format!("{}/**/*", expected.to_string_lossy())
+        );
+    }
+
+    #[test]
+    fn watchers_cover_loaded_config_files() {
+        let config_files = vec![
+            PathBuf::from("/home/dev/.config/veil/veil.toml"),
+            PathBuf::from("/etc/veil/org.toml"),
+            PathBuf::from("configs/other.toml"),
+        ];
+
+        let watchers = file_watchers(&Config::default(), &config_files, WatchSupport::default());
+        let cwd = std::env::current_dir().expect("cwd");
+        assert_eq!(
+            globs(&watchers)[4..],
+            [
+                "/home/dev/.config/veil/veil.toml".to_string(),
+                "/etc/veil/org.toml".to_string(),
+                format!("{}/other.toml", cwd.join("configs").to_string_lossy()),
+            ]
+        );
+
+        let watchers = file_watchers(
+            &Config::default(),
+            &config_files[1..2],
+            WatchSupport {
+                dynamic_registration: true,
+                relative_patterns: true,
+            },
+        );
+        assert_eq!(globs(&watchers)[4], "file:///etc/veil/ org.toml");
+    }
+}
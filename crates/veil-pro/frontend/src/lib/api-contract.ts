@@ -95,6 +95,14 @@ export type SafeFindingApiV1 = {
   category: string;
   tags: string[];
   baselineStatus: BaselineStatus;
+  remediation?: RemediationApiV1;
+};
+
+export type RemediationApiV1 = {
+  provider?: string;
+  revokeUrl?: string;
+  rotationSteps: string[];
+  historyRewrite: boolean;
 };
 
 export type ScanResponse = {
@@ -238,6 +246,12 @@ export type PrivacyMeta = {
   bind: BindAddress;
 };
 
+export type RunScanMeta = {
+  mode: ScanMode;
+  range?: string | null;
+  commits?: number | null;
+};
+
 export type RunMetaResponse = {
   schemaVersion: RunMetaSchemaVersion;
   runId: string;
@@ -247,6 +261,7 @@ export type RunMetaResponse = {
   result: RunResultMeta;
   artifacts: EvidenceArtifacts;
   privacy: PrivacyMeta;
+  scan?: RunScanMeta | null;
   extensions?: Record<string, unknown> | null;
 };
 
@@ -6,15 +6,15 @@ use axum::{
 };
 use chrono::{Duration, Utc};
 use std::collections::{BTreeMap, HashMap};
-use std::path::{Path as FsPath, PathBuf};
+use std::path::{Component, Path as FsPath, PathBuf};
 use std::sync::Arc;
 use tower_sessions::Session;
-use veil_core::finding_id::SpanData;
 
 #[allow(dead_code)]
 pub mod dto;
 pub use dto::*;
 
+use crate::evidence_generator::ScanScope;
 use crate::{config_loader::ConfigLayers, AppState};
 
 type ApiErrorResponse = (StatusCode, Json<ErrorEnvelope>);
@@ -335,43 +335,56 @@ fn resolve_baseline_file_from_root(
     Ok(Some(BaselineFileInfo { content, snapshot }))
 }
 
-fn rule_lookup(rules: &[veil_core::Rule]) -> HashMap<String, (String, Vec<String>)> {
+#[derive(Clone)]
+struct RuleMeta {
+    category: String,
+    tags: Vec<String>,
+    remediation: Option<RemediationApiV1>,
+}
+
+fn rule_lookup(rules: &[veil_core::Rule]) -> HashMap<String, RuleMeta> {
     rules
         .iter()
-        .map(|rule| (rule.id.clone(), (rule.category.clone(), rule.tags.clone())))
+        .map(|rule| {
+            let meta = RuleMeta {
+                category: rule.category.clone(),
+                tags: rule.tags.clone(),
+                remediation: rule
+                    .remediation
+                    .as_ref()
+                    .map(|remediation| RemediationApiV1 {
+                        provider: remediation.provider.clone(),
+                        revoke_url: remediation.revoke_url.clone(),
+                        rotation_steps: remediation.rotation_steps.clone(),
+                        history_rewrite: remediation.history_rewrite,
+                    }),
+            };
+            (rule.id.clone(), meta)
+        })
         .collect()
 }
 
-fn finding_id(finding: &veil_core::Finding, ordinal: usize) -> String {
-    let span = SpanData {
-        start_line: finding.line_number as u64,
-        start_col: 0,
-        end_line: finding.line_number as u64,
-        end_col: 0,
-    };
-    let public_discriminator = format!("{}|{}", finding.masked_snippet, ordinal);
-    veil_core::FindingId::new(
-        &finding.rule_id,
-        &finding.path,
-        &span,
-        &public_discriminator,
-    )
-    .to_string()
-}
-
 fn to_safe_finding(
     finding: &veil_core::Finding,
     baseline_status: BaselineStatus,
-    rules: &HashMap<String, (String, Vec<String>)>,
-    ordinal: usize,
+    rules: &HashMap<String, RuleMeta>,
+    root: &FsPath,
 ) -> SafeFindingApiV1 {
-    let (category, tags) = rules
+    let RuleMeta {
+        category,
+        tags,
+        remediation,
+    } = rules
         .get(&finding.rule_id)
         .cloned()
-        .unwrap_or_else(|| ("uncategorized".to_string(), Vec::new()));
+        .unwrap_or_else(|| RuleMeta {
+            category: "uncategorized".to_string(),
+            tags: Vec::new(),
+            remediation: None,
+        });
 
     SafeFindingApiV1 {
-        finding_id: finding_id(finding, ordinal),
+        finding_id: veil_core::FindingId::for_finding(finding, root).to_string(),
         baseline_fingerprint: veil_core::baseline::generate_fingerprint(finding),
         path: finding.path.to_string_lossy().to_string(),
         line_number: finding.line_number,
@@ -383,6 +396,7 @@ fn to_safe_finding(
         category,
         tags,
         baseline_status,
+        remediation,
     }
 }
 
@@ -397,14 +411,15 @@ fn count_severities(findings: &[SafeFindingApiV1]) -> SeverityCounts {
 fn bucket_findings(
     findings: Vec<veil_core::Finding>,
     baseline: Option<&veil_core::baseline::BaselineSnapshot>,
-    rules: &HashMap<String, (String, Vec<String>)>,
+    rules: &HashMap<String, RuleMeta>,
 ) -> FindingBuckets {
     let known_fingerprints = baseline.map(veil_core::baseline::BaselineSnapshot::fingerprint_set);
     let mut all = Vec::new();
     let mut effective = Vec::new();
     let mut suppressed = Vec::new();
 
-    for (ordinal, finding) in findings.iter().enumerate() {
+    let root = repo_root();
+    for finding in &findings {
         let baseline_status = match &known_fingerprints {
             Some(fingerprints)
                 if fingerprints.contains(&veil_core::baseline::generate_fingerprint(finding)) =>
@@ -414,7 +429,7 @@ fn bucket_findings(
             Some(_) => BaselineStatus::New,
             None => BaselineStatus::None,
         };
-        let safe = to_safe_finding(finding, baseline_status, rules, ordinal);
+        let safe = to_safe_finding(finding, baseline_status, rules, &root);
         if matches!(baseline_status, BaselineStatus::Suppressed) {
             suppressed.push(safe.clone());
         } else {
@@ -498,6 +513,9 @@ fn limit_reasons_for(result: &veil_core::ScanResult) -> Vec<String> {
     if result.read_error_reached {
         reasons.push("read-error".to_string());
     }
+    if !result.archive_skips.is_empty() {
+        reasons.push("archive-limit".to_string());
+    }
     if result.limit_reached && !result.file_limit_reached && !result.findings.is_empty() {
         reasons.push("result-limit".to_string());
     }
@@ -558,14 +576,7 @@ fn scan_paths_with_global_limit(
             limit_reasons.push("result-limit".to_string());
             break;
         }
-        let safe_path = validate_safe_path(&path).map_err(|error| {
-            error_response(
-                StatusCode::FORBIDDEN,
-                ErrorCode::PathDenied,
-                format!("Path denied: {error}"),
-                Some(NextAction::NarrowScope),
-            )
-        })?;
+        let safe_path = validate_safe_path(&path).map_err(path_denied_response)?;
         let scan_path = scan_path_for_request(&path, &safe_path);
         let mut run_config = config.clone();
         if let Some(max) = max_findings {
@@ -578,7 +589,8 @@ fn scan_paths_with_global_limit(
         limit_reached |= real_finding_limit_reached
             || result.file_limit_reached
             || result.max_file_size_reached
-            || result.read_error_reached;
+            || result.read_error_reached
+            || !result.archive_skips.is_empty();
         limit_reasons.extend(limit_reasons_for(&result));
         builtin_skips.extend(result.builtin_skips);
         raw_findings_count += result.findings.len();
@@ -600,6 +612,144 @@ fn scan_paths_with_global_limit(
     })
 }
 
+fn path_denied_response(error: String) -> ApiErrorResponse {
+    error_response(
+        StatusCode::FORBIDDEN,
+        ErrorCode::PathDenied,
+        format!("Path denied: {error}"),
+        Some(NextAction::NarrowScope),
+    )
+}
+
+/// Requested paths as repository-relative prefixes for staged and ci runs,
+/// which read git objects and report repository-relative paths. `.` becomes
+/// the empty prefix, which matches every path.
+fn requested_path_prefixes(paths: &[String]) -> Result<Vec<PathBuf>, ApiErrorResponse> {
+    paths
+        .iter()
+        .map(|path| {
+            let safe_path = validate_safe_path(path).map_err(path_denied_response)?;
+            Ok(scan_path_for_request(path, &safe_path)
+                .components()
+                .filter(|component| !matches!(component, Component::CurDir))
+                .collect())
+        })
+        .collect()
+}
+
+fn git_mode_error(mode: &str, root: &FsPath, error: impl std::fmt::Display) -> ApiErrorResponse {
+    error_response(
+        StatusCode::BAD_REQUEST,
+        ErrorCode::InvalidRequest,
+        format!(
+            "mode {mode} requires a git repository at {}: {error}",
+            root.display()
+        ),
+        None,
+    )
+}
+
+/// Findings of a staged or ci run, capped at `output.max_findings` like a
+/// full scan.
+fn aggregate_git_findings(
+    mut findings: Vec<veil_core::Finding>,
+    scanned_files: usize,
+    skipped_files: usize,
+    mut limit_reasons: Vec<String>,
+    config: &veil_config::Config,
+) -> AggregatedScan {
+    if let Some(max) = config.output.max_findings {
+        if findings.len() > max {
+            findings.truncate(max);
+            limit_reasons.push("result-limit".to_string());
+        }
+    }
+
+    AggregatedScan {
+        findings,
+        scanned_files,
+        skipped_files,
+        limit_reached: !limit_reasons.is_empty(),
+        limit_reasons,
+        builtin_skips: Vec::new(),
+    }
+}
+
+/// `mode: staged`: the blobs staged in the index, as the pre-commit hook
+/// (`veil scan --staged`) scans them.
+fn scan_staged_for_api(
+    root: &FsPath,
+    path_prefixes: &[PathBuf],
+    rules: &[veil_core::Rule],
+    config: &veil_config::Config,
+) -> Result<(AggregatedScan, ScanScope), ApiErrorResponse> {
+    let staged = veil_core::scan_staged(root, path_prefixes, rules, config)
+        .map_err(|error| git_mode_error("staged", root, error))?;
+    let limit_reasons = if staged.max_file_size_reached {
+        vec!["max-file-size".to_string()]
+    } else {
+        Vec::new()
+    };
+
+    Ok((
+        aggregate_git_findings(
+            staged.findings,
+            staged.scanned_files,
+            staged.skipped_files,
+            limit_reasons,
+            config,
+        ),
+        ScanScope {
+            mode: ScanMode::Staged,
+            range: None,
+            commits: None,
+        },
+    ))
+}
+
+/// `mode: ci`: the commits of the current branch that are not on its base
+/// branch, as `veil git scan --pr` scans them.
+fn scan_ci_for_api(
+    root: &FsPath,
+    path_prefixes: &[PathBuf],
+    rules: &[veil_core::Rule],
+    config: &veil_config::Config,
+) -> Result<(AggregatedScan, ScanScope), ApiErrorResponse> {
+    let range = veil_core::pr_range(root)
+        .map_err(|error| git_mode_error("ci", root, error))?
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::BAD_REQUEST,
+                ErrorCode::InvalidRequest,
+                "mode ci could not determine the base branch. Tried upstream, origin/main, origin/master.",
+                None,
+            )
+        })?;
+    let history = veil_core::scan_history(
+        root,
+        &veil_core::HistoryScope::Range(range.clone()),
+        path_prefixes,
+        rules,
+        config,
+    )
+    .map_err(|error| git_mode_error("ci", root, error))?;
+
+    Ok((
+        aggregate_git_findings(
+            history.findings,
+            history.blobs_scanned,
+            history.blobs_skipped,
+            Vec::new(),
+            config,
+        ),
+        ScanScope {
+            mode: ScanMode::Ci,
+            range: Some(range),
+            commits: Some(history.commits),
+        },
+    ))
+}
+
 // --- Endpoints ---
 
 pub async fn list_projects(State(_state): State<Arc<AppState>>) -> Json<ProjectsResponse> {
@@ -629,14 +779,6 @@ pub async fn scan_project(
 ) -> Result<Json<ScanResponse>, ApiErrorResponse> {
     let Json(req) = request.map_err(json_rejection_response)?;
     let paths_to_scan = normalized_paths(req.paths.clone());
-    if let Some(ScanMode::Staged | ScanMode::Ci) = req.mode {
-        return Err(error_response(
-            StatusCode::BAD_REQUEST,
-            ErrorCode::InvalidRequest,
-            "mode staged/ci is not implemented by the Local API in PR-0; use mode full or omit mode.",
-            None,
-        ));
-    }
     if req.fail_on_findings == Some(0) {
         return Err(error_response(
             StatusCode::BAD_REQUEST,
@@ -660,7 +802,24 @@ pub async fn scan_project(
     let baseline = resolve_baseline_file(req.baseline_file.as_deref())
         .map_err(baseline_file_error_response)?;
 
-    let aggregate = scan_paths_with_global_limit(paths_to_scan, &rules, &config)?;
+    let (aggregate, scope) = match req.mode.unwrap_or(ScanMode::Full) {
+        ScanMode::Full => (
+            scan_paths_with_global_limit(paths_to_scan, &rules, &config)?,
+            ScanScope::full(),
+        ),
+        ScanMode::Staged => scan_staged_for_api(
+            &repo_root(),
+            &requested_path_prefixes(&paths_to_scan)?,
+            &rules,
+            &config,
+        )?,
+        ScanMode::Ci => scan_ci_for_api(
+            &repo_root(),
+            &requested_path_prefixes(&paths_to_scan)?,
+            &rules,
+            &config,
+        )?,
+    };
 
     let buckets = bucket_findings(
         aggregate.findings,
@@ -694,6 +853,7 @@ pub async fn scan_project(
         aggregate.skipped_files,
         150,
         baseline.map(|info| info.content),
+        &scope,
     );
     let run_id = run_meta.run_id.clone();
 
@@ -995,6 +1155,7 @@ mod tests {
             category: "credentials".to_string(),
             tags: Vec::new(),
             baseline_status: BaselineStatus::New,
+            remediation: None,
         }
     }
 
@@ -1039,6 +1200,7 @@ mod tests {
         let finding = veil_core::Finding {
             path: "src/config.rs".into(),
             line_number: 7,
+            end_line_number: None,
             line_content: "token = secret".to_string(),
             rule_id: "creds.test".to_string(),
             matched_content: "secret".to_string(),
@@ -1053,19 +1215,72 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         };
-        let safe = to_safe_finding(&finding, BaselineStatus::None, &HashMap::new(), 0);
+        let safe = to_safe_finding(
+            &finding,
+            BaselineStatus::None,
+            &HashMap::new(),
+            FsPath::new("."),
+        );
 
         assert_ne!(safe.finding_id, safe.baseline_fingerprint);
         assert!(safe.finding_id.starts_with("fx_"));
         assert!(safe.baseline_fingerprint.starts_with("sha256:"));
     }
 
+    #[test]
+    fn safe_finding_carries_rule_remediation() {
+        let rules = veil_core::get_default_rules();
+        let finding = veil_core::Finding {
+            path: "src/config.rs".into(),
+            line_number: 7,
+            end_line_number: None,
+            line_content: "token = secret".to_string(),
+            rule_id: "creds.github.pat.ghp".to_string(),
+            matched_content: "secret".to_string(),
+            masked_snippet: "token = <REDACTED>".to_string(),
+            severity: veil_core::Severity::High,
+            score: 90,
+            grade: veil_core::Grade::Critical,
+            span: Default::default(),
+            utf16_range: Default::default(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            commit_sha: None,
+            author: None,
+            date: None,
+            verification: None,
+        };
+        let safe = to_safe_finding(
+            &finding,
+            BaselineStatus::None,
+            &rule_lookup(&rules),
+            FsPath::new("."),
+        );
+
+        let remediation = safe.remediation.expect("remediation");
+        assert_eq!(remediation.provider.as_deref(), Some("GitHub"));
+        assert_eq!(
+            remediation.revoke_url.as_deref(),
+            Some("https://github.com/settings/tokens")
+        );
+        let json = serde_json::to_value(to_safe_finding(
+            &finding,
+            BaselineStatus::None,
+            &HashMap::new(),
+            FsPath::new("."),
+        ))
+        .unwrap();
+        assert!(json.get("remediation").is_none());
+    }
+
     #[test]
     fn safe_finding_id_does_not_depend_on_raw_match() {
         let mut finding = veil_core::Finding {
             path: "src/config.rs".into(),
             line_number: 7,
+            end_line_number: None,
             line_content: "token = secret".to_string(),
             rule_id: "creds.test".to_string(),
             matched_content: "secret-one".to_string(),
@@ -1080,14 +1295,31 @@ mod tests {
             commit_sha: None,
             author: None,
             date: None,
+            verification: None,
         };
-        let first = to_safe_finding(&finding, BaselineStatus::None, &HashMap::new(), 0);
+        let first = to_safe_finding(
+            &finding,
+            BaselineStatus::None,
+            &HashMap::new(),
+            FsPath::new("."),
+        );
         finding.matched_content = "secret-two".to_string();
-        let second = to_safe_finding(&finding, BaselineStatus::None, &HashMap::new(), 0);
-        let next_ordinal = to_safe_finding(&finding, BaselineStatus::None, &HashMap::new(), 1);
+        let second = to_safe_finding(
+            &finding,
+            BaselineStatus::None,
+            &HashMap::new(),
+            FsPath::new("."),
+        );
+        finding.utf16_range.start.character = 8;
+        let next_column = to_safe_finding(
+            &finding,
+            BaselineStatus::None,
+            &HashMap::new(),
+            FsPath::new("."),
+        );
 
         assert_eq!(first.finding_id, second.finding_id);
-        assert_ne!(first.finding_id, next_ordinal.finding_id);
+        assert_ne!(first.finding_id, next_column.finding_id);
     }
 
     #[test]
@@ -1677,8 +1909,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn scan_rejects_unimplemented_staged_and_ci_modes() {
-        for mode in [ScanMode::Staged, ScanMode::Ci] {
+    async fn scan_git_modes_require_a_repository_root() {
+        for (mode, name) in [(ScanMode::Staged, "staged"), (ScanMode::Ci, "ci")] {
             let state = Arc::new(AppState {
                 token: "test-token".to_string(),
                 run_cache: Arc::new(tokio::sync::RwLock::new(crate::evidence::RunCache::new(
@@ -1697,12 +1929,109 @@ mod tests {
 
             assert_eq!(status, StatusCode::BAD_REQUEST);
             assert!(matches!(body.error.code, ErrorCode::InvalidRequest));
-            assert_eq!(
-                body.error.message,
-                "mode staged/ci is not implemented by the Local API in PR-0; use mode full or omit mode."
+            assert!(
+                body.error
+                    .message
+                    .starts_with(&format!("mode {name} requires a git repository at ")),
+                "{}",
+                body.error.message
             );
         }
     }
+
+    fn commit_files(repo: &git2::Repository, files: &[(&str, &str)]) -> git2::Oid {
+        let root = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            let file = root.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, content).unwrap();
+            index.add_path(FsPath::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, "c", &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn staged_mode_scans_the_index_under_requested_paths() {
+        let root = unique_target_dir("staged-mode");
+        let repo = git2::Repository::init(&root).unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in [
+            ("src/a.txt", "contact a@example.com\n"),
+            ("docs/b.txt", "contact b@example.com\n"),
+        ] {
+            std::fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            std::fs::write(root.join(path), content).unwrap();
+            index.add_path(FsPath::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let mut config = veil_config::Config::default();
+        let rules = veil_core::get_default_rules();
+
+        let (aggregate, scope) =
+            scan_staged_for_api(&root, &[PathBuf::from("src")], &rules, &config).unwrap();
+
+        assert_eq!(aggregate.scanned_files, 1);
+        assert_eq!(aggregate.findings.len(), 1);
+        assert_eq!(aggregate.findings[0].path, PathBuf::from("src/a.txt"));
+        assert!(!aggregate.limit_reached);
+        assert_eq!(scope.mode, ScanMode::Staged);
+
+        config.output.max_findings = Some(1);
+        let (aggregate, _) =
+            scan_staged_for_api(&root, &[PathBuf::new()], &rules, &config).unwrap();
+        assert_eq!(aggregate.findings.len(), 1);
+        assert!(aggregate.limit_reached);
+        assert_eq!(aggregate.limit_reasons, vec!["result-limit".to_string()]);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn ci_mode_scans_commits_since_the_base_branch() {
+        let root = unique_target_dir("ci-mode");
+        let repo = git2::Repository::init(&root).unwrap();
+        let config = veil_config::Config::default();
+        let rules = veil_core::get_default_rules();
+        let base = commit_files(&repo, &[("old.txt", "contact old@example.com\n")]);
+
+        let Err((status, Json(body))) = scan_ci_for_api(&root, &[PathBuf::new()], &rules, &config)
+        else {
+            panic!("ci mode without a base branch must fail");
+        };
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.error.message.contains("base branch"));
+
+        repo.reference("refs/remotes/origin/main", base, true, "base")
+            .unwrap();
+        commit_files(&repo, &[("new.txt", "contact new@example.com\n")]);
+
+        let (aggregate, scope) =
+            scan_ci_for_api(&root, &[PathBuf::new()], &rules, &config).unwrap();
+
+        assert_eq!(aggregate.findings.len(), 1);
+        assert_eq!(aggregate.findings[0].path, PathBuf::from("new.txt"));
+        assert_eq!(
+            scope,
+            ScanScope {
+                mode: ScanMode::Ci,
+                range: Some("origin/main..HEAD".to_string()),
+                commits: Some(1),
+            }
+        );
+        assert_eq!(aggregate.scanned_files, 1);
+
+        let (aggregate, _) =
+            scan_ci_for_api(&root, &[PathBuf::from("src")], &rules, &config).unwrap();
+        assert!(aggregate.findings.is_empty());
+        assert_eq!(aggregate.scanned_files, 0);
+        let _ = std::fs::remove_dir_all(root);
+    }
 }
 
 #[cfg(test)]
@@ -1743,6 +2072,7 @@ mod evidence_tests {
             0,
             1,
             None,
+            &crate::evidence_generator::ScanScope::full(),
         );
         let run_id = meta.run_id.clone();
 
@@ -99,7 +99,7 @@ pub enum PresetName {
     LogsJp,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ScanMode {
     Full,
@@ -241,6 +241,20 @@ pub struct SafeFindingApiV1 {
     pub category: String,
     pub tags: Vec<String>,
     pub baseline_status: BaselineStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<RemediationApiV1>,
+}
+
+/// Rule-level guidance for revoking and rotating a leaked secret.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RemediationApiV1 {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoke_url: Option<String>,
+    pub rotation_steps: Vec<String>,
+    pub history_rewrite: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
@@ -423,6 +437,9 @@ pub struct RunMetaV1 {
     pub result: RunResultMeta,
     pub artifacts: EvidenceArtifacts,
     pub privacy: PrivacyMeta,
+    /// What the run scanned. Omitted for full working-tree runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan: Option<RunScanMeta>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Object)]
     pub extensions: Option<BTreeMap<String, serde_json::Value>>,
@@ -430,6 +447,18 @@ pub struct RunMetaV1 {
 
 pub type RunMetaResponse = RunMetaV1;
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RunScanMeta {
+    pub mode: ScanMode,
+    /// ci: the revision range scanned, e.g. `origin/main..HEAD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
+    /// ci: number of commits in `range`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commits: Option<usize>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ProductName {
@@ -147,6 +147,7 @@ fn openapi_get_doctor() {}
         RulePackMeta,
         RunMetaResponse,
         RunResultMeta,
+        RunScanMeta,
         SafeFindingApiV1,
         ScanRequest,
         ScanResponse,
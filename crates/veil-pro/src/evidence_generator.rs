@@ -2,14 +2,61 @@ use crate::api::dto::{
     ArtifactMeta, BaselineArtifactMeta, BaselineArtifactPath, BindAddress, BuildProfile,
     EngineMeta, EngineName, EngineSchemaVersion, EvidenceArtifacts, EvidenceReportSchemaVersion,
     EvidenceReportV1, EvidenceSummary, NetworkMode, PrivacyMeta, ProductMeta, ProductName,
-    RulePackMeta, RulePackSource, RunMetaSchemaVersion, RunMetaV1, RunResultMeta, RunStatus,
-    SafeFindingApiV1, TelemetryMode,
+    RulePackMeta, RulePackSource, RunMetaSchemaVersion, RunMetaV1, RunResultMeta, RunScanMeta,
+    RunStatus, SafeFindingApiV1, ScanMode, TelemetryMode,
 };
 use crate::evidence::CachedRun;
 use chrono::Utc;
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+/// What a run scanned. Recorded as `scan` in `run_meta.json` and shown in
+/// `report.html`, since staged and ci runs cover git objects rather than the
+/// working tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanScope {
+    pub mode: ScanMode,
+    /// ci: the revision range scanned, e.g. `origin/main..HEAD`.
+    pub range: Option<String>,
+    /// ci: number of commits in `range`.
+    pub commits: Option<usize>,
+}
+
+impl ScanScope {
+    pub fn full() -> Self {
+        Self {
+            mode: ScanMode::Full,
+            range: None,
+            commits: None,
+        }
+    }
+
+    fn mode_name(&self) -> &'static str {
+        match self.mode {
+            ScanMode::Full => "full",
+            ScanMode::Staged => "staged",
+            ScanMode::Ci => "ci",
+        }
+    }
+
+    /// `None` for full runs, which scan the working tree as before.
+    fn run_meta(&self) -> Option<RunScanMeta> {
+        (self.mode != ScanMode::Full).then(|| RunScanMeta {
+            mode: self.mode,
+            range: self.range.clone(),
+            commits: self.commits,
+        })
+    }
+
+    fn label(&self) -> String {
+        match (&self.mode, &self.range) {
+            (ScanMode::Staged, _) => "staged (git index)".to_string(),
+            (ScanMode::Ci, Some(range)) => format!("ci ({range})"),
+            _ => self.mode_name().to_string(),
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn generate_evidence_pack(
     config: &veil_config::Config,
@@ -22,11 +69,12 @@ pub fn generate_evidence_pack(
     skipped_files: usize,
     _duration_ms: u64,
     baseline_content: Option<String>,
+    scope: &ScanScope,
 ) -> (RunMetaV1, CachedRun) {
     let run_id = Uuid::new_v4().to_string();
     let generated_at = Utc::now().to_rfc3339();
 
-    let html_content = generate_html_report(findings, scanned_files, skipped_files);
+    let html_content = generate_html_report(findings, scanned_files, skipped_files, scope);
     let html_sha = sha256_str(&html_content);
 
     let report = EvidenceReportV1 {
@@ -107,6 +155,7 @@ pub fn generate_evidence_pack(
             network_mode: NetworkMode::LocalOnly,
             bind: BindAddress::LocalhostV4,
         },
+        scan: scope.run_meta(),
         extensions: None,
     };
 
@@ -191,6 +240,7 @@ fn generate_html_report(
     findings: &[SafeFindingApiV1],
     _scanned_files: usize,
     _skipped_files: usize,
+    scope: &ScanScope,
 ) -> String {
     let rows = findings
         .iter()
@@ -230,6 +280,7 @@ fn generate_html_report(
 </head>
 <body>
     <h1>Veil Security Report</h1>
+    <p>Scan Mode: {}</p>
     <p>Total Findings: {}</p>
     <table>
         <thead><tr><th>Severity</th><th>Rule ID</th><th>File</th><th>Match Content</th><th>Line</th></tr></thead>
@@ -237,6 +288,7 @@ fn generate_html_report(
     </table>
 </body>
 </html>"#,
+        html_escape(&scope.label()),
         html_escape(&findings.len().to_string()),
         rows
     )
@@ -270,6 +322,7 @@ mod tests {
             category: "credentials".to_string(),
             tags: vec!["secret".to_string()],
             baseline_status: status,
+            remediation: None,
         }
     }
 
@@ -315,6 +368,7 @@ mod tests {
             0,
             1,
             None,
+            &ScanScope::full(),
         );
         let report: serde_json::Value = serde_json::from_str(&cached.report_json).unwrap();
 
@@ -352,8 +406,11 @@ mod tests {
             0,
             1,
             None,
+            &ScanScope::full(),
         );
 
+        assert!(meta.scan.is_none());
+        assert!(meta.extensions.is_none());
         assert!(!cached.effective_config.contains(token));
         assert!(!cached.effective_config.contains("?token="));
         assert!(cached.effective_config.contains("remote_rules_url"));
@@ -363,4 +420,44 @@ mod tests {
             sha256_str(&cached.effective_config)
         );
     }
+
+    #[test]
+    fn scan_scope_is_recorded_in_run_meta_and_report() {
+        let scope = ScanScope {
+            mode: ScanMode::Ci,
+            range: Some("origin/main..HEAD".to_string()),
+            commits: Some(3),
+        };
+
+        let (meta, cached) = generate_evidence_pack(
+            &veil_config::Config::default(),
+            &[],
+            EvidenceSummary {
+                total_findings: 0,
+                suppressed_findings: 0,
+                effective_findings: 0,
+                severity_counts: SeverityCounts::zero(),
+                all_severity_counts: SeverityCounts::zero(),
+                suppressed_severity_counts: SeverityCounts::zero(),
+                coverage_complete: true,
+            },
+            RunStatus::Success,
+            false,
+            Vec::new(),
+            0,
+            0,
+            1,
+            None,
+            &scope,
+        );
+
+        assert!(meta.extensions.is_none());
+        assert_eq!(
+            serde_json::to_value(&meta).unwrap()["scan"],
+            serde_json::json!({"mode": "ci", "range": "origin/main..HEAD", "commits": 3})
+        );
+        assert!(cached
+            .report_html
+            .contains("<p>Scan Mode: ci (origin/main..HEAD)</p>"));
+    }
 }
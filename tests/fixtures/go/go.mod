@@ -0,0 +1,16 @@
+module example.com/service
+
+go 1.20
+
+require github.com/gin-gonic/gin v1.6.3
+
+require (
+	github.com/sirupsen/logrus v1.8.1
+	golang.org/x/crypto v0.0.0-20200622213623-75b288015ac9 // indirect
+	golang.org/x/net v0.0.0-20200822124328-c89045814202 // indirect
+	example.com/internal/tools v0.1.0
+)
+
+replace golang.org/x/net => golang.org/x/net v0.7.0
+
+replace example.com/internal/tools => ../tools